@@ -12,6 +12,10 @@ pub async fn ai_chat(app: tauri::AppHandle, messages: Vec<AiMessage>) -> Result<
 }
 
 #[tauri::command]
-pub fn ai_execute_tool(tool_name: String, arguments: String) -> Result<String, AppError> {
-    ai_service::execute_tool(&tool_name, &arguments)
+pub fn ai_execute_tool(
+    app: tauri::AppHandle,
+    tool_name: String,
+    arguments: String,
+) -> Result<String, AppError> {
+    ai_service::execute_tool(&app, &tool_name, &arguments)
 }
@@ -0,0 +1,18 @@
+use crate::error::AppError;
+use crate::services::cli_shims::{CliShims, ShimsInfo};
+
+/// Shim status and the PATH line to add to the shell profile.
+#[tauri::command]
+pub fn cli_get_shims_info() -> Result<ShimsInfo, AppError> {
+    Ok(CliShims::get_info())
+}
+
+#[tauri::command]
+pub fn cli_install_shims() -> Result<ShimsInfo, AppError> {
+    CliShims::install()
+}
+
+#[tauri::command]
+pub fn cli_remove_shims() -> Result<ShimsInfo, AppError> {
+    CliShims::remove()
+}
@@ -0,0 +1,35 @@
+use crate::error::AppError;
+use crate::services::composer_manager::{ComposerInfo, ComposerManager};
+use crate::services::download_manager::DownloadManager;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub fn composer_get_info() -> Result<ComposerInfo, AppError> {
+    Ok(ComposerManager::get_info())
+}
+
+/// Download (or update to) the latest stable composer.phar.
+#[tauri::command]
+pub async fn composer_install(app: AppHandle) -> Result<ComposerInfo, AppError> {
+    DownloadManager::run_job(&app, "composer", "Composer", ComposerManager::install(&app, "composer")).await
+}
+
+/// Run `composer <action>` in a site's document root. Output is streamed as
+/// `composer-output` events.
+#[tauri::command]
+pub async fn composer_run(
+    app: AppHandle,
+    site_id: String,
+    action: String,
+    packages: Option<Vec<String>>,
+    dev: Option<bool>,
+) -> Result<(), AppError> {
+    ComposerManager::run(
+        &app,
+        &site_id,
+        &action,
+        &packages.unwrap_or_default(),
+        dev.unwrap_or(false),
+    )
+    .await
+}
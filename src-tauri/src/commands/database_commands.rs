@@ -1,11 +1,13 @@
 use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
 use crate::services::mariadb_manager::{DatabaseEntry, MariaDbInfo, MariaDbManager};
+use crate::services::service_supervisor::ServiceSupervisor;
 use crate::state::{AppState, ServiceInfo, ServiceStatus};
 use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub async fn mariadb_install(app: AppHandle) -> Result<MariaDbInfo, AppError> {
-    MariaDbManager::install(&app).await
+    DownloadManager::run_job(&app, "mariadb", "MariaDB", MariaDbManager::install(&app)).await
 }
 
 #[tauri::command]
@@ -52,10 +54,8 @@ pub fn mariadb_start(state: State<'_, AppState>) -> Result<ServiceInfo, AppError
 
 #[tauri::command]
 pub fn mariadb_stop(state: State<'_, AppState>) -> Result<ServiceInfo, AppError> {
-    // Remove child process handle (don't SIGKILL - let stop() SIGTERM gracefully)
-    if let Ok(mut procs) = state.child_processes.lock() {
-        procs.remove("mariadb");
-    }
+    // Release the handle (don't SIGKILL - let stop() SIGTERM gracefully)
+    ServiceSupervisor::release(&state, "mariadb");
 
     MariaDbManager::stop()?;
 
@@ -83,9 +83,7 @@ pub fn mariadb_stop(state: State<'_, AppState>) -> Result<ServiceInfo, AppError>
 #[tauri::command]
 pub fn mariadb_restart(state: State<'_, AppState>) -> Result<ServiceInfo, AppError> {
     // Stop first (don't SIGKILL - let stop() SIGTERM gracefully)
-    if let Ok(mut procs) = state.child_processes.lock() {
-        procs.remove("mariadb");
-    }
+    ServiceSupervisor::release(&state, "mariadb");
     MariaDbManager::stop()?;
 
     let child = MariaDbManager::start()?;
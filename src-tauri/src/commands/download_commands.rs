@@ -0,0 +1,35 @@
+use crate::error::AppError;
+use crate::services::artifact_cache::{ArtifactCache, CachedArtifact};
+use crate::services::download_manager::{DownloadJob, DownloadManager};
+use crate::state::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub fn downloads_list(state: State<'_, AppState>) -> Result<Vec<DownloadJob>, AppError> {
+    Ok(DownloadManager::list_jobs(&state))
+}
+
+#[tauri::command]
+pub fn downloads_clear_history(state: State<'_, AppState>) -> Result<(), AppError> {
+    DownloadManager::clear_history(&state)
+}
+
+#[tauri::command]
+pub fn download_cancel(state: State<'_, AppState>, id: String) -> Result<bool, AppError> {
+    Ok(DownloadManager::cancel(&state, &id))
+}
+
+#[tauri::command]
+pub fn download_cache_list() -> Result<Vec<CachedArtifact>, AppError> {
+    Ok(ArtifactCache::list())
+}
+
+#[tauri::command]
+pub fn download_cache_evict(url: String) -> Result<(), AppError> {
+    ArtifactCache::evict(&url)
+}
+
+#[tauri::command]
+pub fn download_cache_clear() -> Result<(), AppError> {
+    ArtifactCache::clear()
+}
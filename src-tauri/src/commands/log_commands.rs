@@ -65,7 +65,11 @@ pub fn log_list_files() -> Result<Vec<LogFile>, AppError> {
         let path = entry.path();
         if path.is_file() {
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.ends_with(".log") {
+                // Includes rotated copies of process output ("php-fpm-8.3-output.log.1")
+                let rotated = name
+                    .rsplit_once(".log.")
+                    .is_some_and(|(_, n)| n.parse::<u32>().is_ok());
+                if name.ends_with(".log") || rotated {
                     let metadata = std::fs::metadata(&path)?;
                     files.push(LogFile {
                         name: name.to_string(),
@@ -0,0 +1,41 @@
+use crate::error::AppError;
+use crate::services::mail_catcher::{MailCatcher, MailCatcherInfo, MailMessage, MailSummary};
+use crate::state::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub fn mail_get_info(state: State<'_, AppState>) -> Result<MailCatcherInfo, AppError> {
+    Ok(MailCatcher::get_info(&state))
+}
+
+/// Caught messages, newest first.
+#[tauri::command]
+pub fn mail_list() -> Result<Vec<MailSummary>, AppError> {
+    Ok(MailCatcher::list())
+}
+
+#[tauri::command]
+pub fn mail_get(id: String) -> Result<MailMessage, AppError> {
+    MailCatcher::get(&id)
+}
+
+/// The message source as it was received.
+#[tauri::command]
+pub fn mail_get_raw(id: String) -> Result<String, AppError> {
+    MailCatcher::get_raw(&id)
+}
+
+#[tauri::command]
+pub fn mail_get_attachment_path(id: String, index: usize) -> Result<String, AppError> {
+    Ok(MailCatcher::get_attachment_path(&id, index)?.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn mail_delete(id: String) -> Result<(), AppError> {
+    MailCatcher::delete(&id)
+}
+
+#[tauri::command]
+pub fn mail_clear() -> Result<usize, AppError> {
+    MailCatcher::clear()
+}
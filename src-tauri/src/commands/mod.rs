@@ -9,3 +9,8 @@ pub mod log_commands;
 pub mod settings_commands;
 pub mod phpmyadmin_commands;
 pub mod ai_commands;
+pub mod download_commands;
+pub mod upgrade_commands;
+pub mod composer_commands;
+pub mod cli_commands;
+pub mod mail_commands;
@@ -1,4 +1,5 @@
 use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
 use crate::services::nginx_manager::{NginxInfo, NginxManager};
 use crate::services::php_manager::PhpManager;
 use crate::services::site_manager::SiteManager;
@@ -8,7 +9,7 @@ use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub async fn nginx_install(app: AppHandle) -> Result<NginxInfo, AppError> {
-    NginxManager::install(&app).await
+    DownloadManager::run_job(&app, "nginx", "Nginx", NginxManager::install(&app)).await
 }
 
 #[tauri::command]
@@ -47,7 +48,7 @@ pub(crate) fn auto_start_required_fpm(state: &State<'_, AppState>) {
                             id: key.clone(),
                             name: format!("PHP-FPM {}", version),
                             status: ServiceStatus::Running,
-                            port: Some(info.port),
+                            port: PhpManager::service_port(version),
                             version: Some(version.clone()),
                             pid: Some(pid),
                             installed: true,
@@ -81,7 +82,7 @@ pub(crate) fn auto_start_required_fpm(state: &State<'_, AppState>) {
                         id: key.clone(),
                         name: format!("PHP-FPM {}", version),
                         status: ServiceStatus::Running,
-                        port: Some(info.port),
+                        port: PhpManager::service_port(&version),
                         version: Some(version.clone()),
                         pid: Some(pid),
                         installed: true,
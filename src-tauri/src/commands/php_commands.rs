@@ -1,24 +1,46 @@
+use crate::config::app_config::{AppConfig, FpmSettings};
 use crate::error::AppError;
+use crate::services::cli_shims::CliShims;
+use crate::services::download_manager::DownloadManager;
+use crate::services::extension_installer::{ExtensionInstaller, InstallableExtension};
+use crate::services::fpm_monitor::{FpmMonitor, FpmStatus, SlowlogEntry};
+use crate::services::ini_document::IniChange;
+use crate::services::ini_presets::{IniPreset, IniPresets};
+use crate::services::ini_snapshots::IniSnapshot;
+use crate::services::nginx_manager::NginxManager;
+use crate::services::php_catalogue::PhpCatalogue;
 use crate::services::php_manager::{PhpExtension, PhpIniDirective, PhpManager, PhpVersion};
-use crate::services::utils;
+use crate::services::service_supervisor::ServiceSupervisor;
+use crate::services::xdebug_manager::{XdebugConfig, XdebugManager, XdebugProfile, XdebugStatus};
 use crate::state::{AppState, ServiceInfo, ServiceStatus};
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
 pub fn php_list_versions() -> Result<Vec<PhpVersion>, AppError> {
     Ok(PhpManager::list_versions())
 }
 
+/// Refresh patch levels in the PHP release catalogue from php.net.
+#[tauri::command]
+pub async fn php_refresh_catalogue() -> Result<Vec<PhpVersion>, AppError> {
+    PhpCatalogue::refresh().await?;
+    CliShims::refresh();
+    Ok(PhpManager::list_versions())
+}
+
 #[tauri::command]
 pub async fn php_install_version(
     app: AppHandle,
     version: String,
 ) -> Result<PhpVersion, AppError> {
-    PhpManager::install_version(&app, &version).await
+    let job_id = format!("php-{}", version);
+    let label = format!("PHP {}", version);
+    DownloadManager::run_job(&app, &job_id, &label, PhpManager::install_version(&app, &version)).await
 }
 
 #[tauri::command]
-pub fn php_remove_version(version: String) -> Result<(), AppError> {
+pub fn php_remove_version(state: State<'_, AppState>, version: String) -> Result<(), AppError> {
+    ServiceSupervisor::release(&state, &format!("php-fpm-{}", version));
     PhpManager::remove_version(&version)
 }
 
@@ -30,13 +52,13 @@ pub fn php_start_fpm(
     let (child, pid) = PhpManager::start_fpm(&version)?;
 
     let service_id = format!("php-fpm-{}", version);
-    let port = utils::php_version_to_port(&version);
+    let port = PhpManager::service_port(&version);
 
     let info = ServiceInfo {
         id: service_id.clone(),
         name: format!("PHP-FPM {}", version),
         status: ServiceStatus::Running,
-        port: Some(port),
+        port,
         version: Some(version),
         pid: Some(pid),
         installed: true,
@@ -65,10 +87,8 @@ pub fn php_stop_fpm(
 ) -> Result<ServiceInfo, AppError> {
     let service_id = format!("php-fpm-{}", version);
 
-    // Remove child process handle (don't SIGKILL - let stop_fpm() SIGTERM gracefully)
-    if let Ok(mut procs) = state.child_processes.lock() {
-        procs.remove(&service_id);
-    }
+    // Release the handle (don't SIGKILL - let stop_fpm() SIGTERM gracefully)
+    ServiceSupervisor::release(&state, &service_id);
 
     PhpManager::stop_fpm(&version)?;
 
@@ -76,7 +96,7 @@ pub fn php_stop_fpm(
         id: service_id.clone(),
         name: format!("PHP-FPM {}", version),
         status: ServiceStatus::Stopped,
-        port: Some(utils::php_version_to_port(&version)),
+        port: PhpManager::service_port(&version),
         version: Some(version),
         pid: None,
         installed: true,
@@ -100,20 +120,18 @@ pub fn php_restart_fpm(
     let service_id = format!("php-fpm-{}", version);
 
     // Stop first (don't SIGKILL - let stop_fpm() SIGTERM gracefully)
-    if let Ok(mut procs) = state.child_processes.lock() {
-        procs.remove(&service_id);
-    }
+    ServiceSupervisor::release(&state, &service_id);
     PhpManager::stop_fpm(&version)?;
 
     // Start again
     let (child, pid) = PhpManager::start_fpm(&version)?;
-    let port = utils::php_version_to_port(&version);
+    let port = PhpManager::service_port(&version);
 
     let info = ServiceInfo {
         id: service_id.clone(),
         name: format!("PHP-FPM {}", version),
         status: ServiceStatus::Running,
-        port: Some(port),
+        port,
         version: Some(version),
         pid: Some(pid),
         installed: true,
@@ -145,8 +163,76 @@ pub fn php_set_ini_directive(
     version: String,
     key: String,
     value: String,
+    section: Option<String>,
+) -> Result<(), AppError> {
+    PhpManager::set_ini_directive(&version, &key, &value, section.as_deref())
+}
+
+#[tauri::command]
+pub fn php_remove_ini_directive(
+    version: String,
+    key: String,
+    section: Option<String>,
 ) -> Result<(), AppError> {
-    PhpManager::set_ini_directive(&version, &key, &value)
+    PhpManager::remove_ini_directive(&version, &key, section.as_deref())
+}
+
+#[tauri::command]
+pub fn php_uncomment_ini_directive(
+    version: String,
+    key: String,
+    section: Option<String>,
+) -> Result<String, AppError> {
+    PhpManager::uncomment_ini_directive(&version, &key, section.as_deref())
+}
+
+#[tauri::command]
+pub fn php_get_effective_ini_value(version: String, key: String) -> Result<Option<String>, AppError> {
+    PhpManager::get_effective_ini_value(&version, &key)
+}
+
+#[tauri::command]
+pub fn php_list_ini_presets() -> Result<Vec<IniPreset>, AppError> {
+    Ok(IniPresets::list())
+}
+
+#[tauri::command]
+pub fn php_save_ini_preset(preset: IniPreset) -> Result<IniPreset, AppError> {
+    IniPresets::save(preset)
+}
+
+#[tauri::command]
+pub fn php_delete_ini_preset(name: String) -> Result<(), AppError> {
+    IniPresets::delete(&name)
+}
+
+#[tauri::command]
+pub fn php_apply_ini_preset(version: String, name: String) -> Result<(), AppError> {
+    PhpManager::apply_ini_preset(&version, &name)
+}
+
+#[tauri::command]
+pub fn php_list_ini_snapshots(version: String) -> Result<Vec<IniSnapshot>, AppError> {
+    PhpManager::list_ini_snapshots(&version)
+}
+
+#[tauri::command]
+pub fn php_diff_ini_snapshots(
+    version: String,
+    from: String,
+    to: Option<String>,
+) -> Result<Vec<IniChange>, AppError> {
+    PhpManager::diff_ini_snapshots(&version, &from, to.as_deref())
+}
+
+#[tauri::command]
+pub fn php_restore_ini_snapshot(version: String, id: String) -> Result<(), AppError> {
+    PhpManager::restore_ini_snapshot(&version, &id)
+}
+
+#[tauri::command]
+pub fn php_delete_ini_snapshot(version: String, id: String) -> Result<(), AppError> {
+    PhpManager::delete_ini_snapshot(&version, &id)
 }
 
 #[tauri::command]
@@ -162,3 +248,101 @@ pub fn php_toggle_extension(
 ) -> Result<(), AppError> {
     PhpManager::toggle_extension(&version, &extension, enable)
 }
+
+#[tauri::command]
+pub fn php_list_installable_extensions(version: String) -> Result<Vec<InstallableExtension>, AppError> {
+    ExtensionInstaller::list(&version)
+}
+
+/// Install a PECL extension, enable it and restart FPM if it is running.
+#[tauri::command]
+pub async fn php_install_extension(
+    app: AppHandle,
+    version: String,
+    name: String,
+) -> Result<Vec<PhpExtension>, AppError> {
+    let job_id = ExtensionInstaller::job_id(&version, &name);
+    let label = format!("{} for PHP {}", name, version);
+    DownloadManager::run_job(&app, &job_id, &label, ExtensionInstaller::install(&app, &version, &name)).await?;
+
+    if PhpManager::is_fpm_running(&version) {
+        php_restart_fpm(app.state::<AppState>(), version.clone())?;
+    }
+    PhpManager::list_extensions(&version)
+}
+
+/// Worker usage of the version's shared pool, or of a site's own pool.
+#[tauri::command]
+pub fn php_fpm_status(version: String, site_id: Option<String>) -> Result<FpmStatus, AppError> {
+    FpmMonitor::get_status(&version, site_id.as_deref())
+}
+
+#[tauri::command]
+pub fn php_fpm_slowlog(
+    version: String,
+    site_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SlowlogEntry>, AppError> {
+    FpmMonitor::read_slowlog(&version, site_id.as_deref(), limit.unwrap_or(50))
+}
+
+#[tauri::command]
+pub fn php_get_xdebug(version: String) -> Result<XdebugStatus, AppError> {
+    XdebugManager::get_status(&version)
+}
+
+/// Configure Xdebug for a version and restart its FPM if it is running.
+#[tauri::command]
+pub fn php_set_xdebug(
+    state: State<'_, AppState>,
+    version: String,
+    enabled: bool,
+    config: XdebugConfig,
+) -> Result<XdebugStatus, AppError> {
+    let status = XdebugManager::apply(&version, enabled, &config)?;
+    if PhpManager::is_fpm_running(&version) {
+        php_restart_fpm(state, version)?;
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn php_list_xdebug_profiles() -> Result<Vec<XdebugProfile>, AppError> {
+    XdebugManager::list_profiles()
+}
+
+#[tauri::command]
+pub fn php_get_xdebug_profile_path(name: String) -> Result<String, AppError> {
+    Ok(XdebugManager::get_profile_path(&name)?.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn php_delete_xdebug_profile(name: String) -> Result<(), AppError> {
+    XdebugManager::delete_profile(&name)
+}
+
+#[tauri::command]
+pub fn php_clear_xdebug_profiles() -> Result<usize, AppError> {
+    XdebugManager::clear_profiles()
+}
+
+#[tauri::command]
+pub fn php_get_fpm_settings(version: String) -> Result<FpmSettings, AppError> {
+    Ok(AppConfig::load().fpm_settings(&version))
+}
+
+#[tauri::command]
+pub fn php_set_fpm_settings(version: String, settings: FpmSettings) -> Result<FpmSettings, AppError> {
+    settings.validate()?;
+
+    let mut config = AppConfig::load();
+    config.php_fpm.insert(version.clone(), settings.clone());
+    config.save()?;
+
+    // Rewrite the pool config and let a running master pick it up
+    PhpManager::apply_pool_config(&version)?;
+    // Site configs derive fastcgi_read_timeout from request_terminate_timeout
+    NginxManager::refresh_php_upstreams()?;
+    log::info!("Updated PHP-FPM {} settings", version);
+    Ok(settings)
+}
@@ -1,10 +1,11 @@
 use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
 use crate::services::phpmyadmin_manager::{PhpMyAdminInfo, PhpMyAdminManager};
 use tauri::AppHandle;
 
 #[tauri::command]
 pub async fn phpmyadmin_install(app: AppHandle) -> Result<PhpMyAdminInfo, AppError> {
-    PhpMyAdminManager::install(&app).await
+    DownloadManager::run_job(&app, "phpmyadmin", "phpMyAdmin", PhpMyAdminManager::install(&app)).await
 }
 
 #[tauri::command]
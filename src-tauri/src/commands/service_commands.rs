@@ -1,12 +1,13 @@
 use crate::commands::nginx_commands::auto_start_required_fpm;
 use crate::error::AppError;
+use crate::services::mail_catcher::MailCatcher;
 use crate::services::mariadb_manager::MariaDbManager;
 use crate::services::nginx_manager::NginxManager;
 use crate::services::php_manager::PhpManager;
 use crate::services::phpmyadmin_manager::PhpMyAdminManager;
-use crate::services::utils;
+use crate::services::service_supervisor::{ServiceExit, ServiceSupervisor};
 use crate::state::{AppState, ServiceInfo, ServiceStatus};
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn get_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceInfo>, AppError> {
@@ -15,6 +16,7 @@ pub fn get_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceInfo>,
     let mariadb_info = MariaDbManager::get_info();
     let pma_info = PhpMyAdminManager::get_info();
     let php_versions = PhpManager::list_versions();
+    let mail_running = MailCatcher::is_running(&state);
 
     let mut services = state
         .services
@@ -61,6 +63,18 @@ pub fn get_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceInfo>,
         };
     }
 
+    // The mail catcher runs inside the app
+    if let Some(svc) = services.get_mut("mailcatcher") {
+        svc.installed = true;
+        svc.initialized = true;
+        svc.port = Some(MailCatcher::port());
+        svc.status = if mail_running {
+            ServiceStatus::Running
+        } else {
+            ServiceStatus::Stopped
+        };
+    }
+
     // Update PHP-FPM services for all installed versions
     for php in &php_versions {
         if !php.installed {
@@ -71,14 +85,14 @@ pub fn get_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceInfo>,
             id: key.clone(),
             name: format!("PHP-FPM {}", php.version),
             status: ServiceStatus::Stopped,
-            port: Some(php.port),
+            port: PhpManager::service_port(&php.version),
             version: Some(php.version.clone()),
             pid: None,
             installed: true,
             initialized: true,
         });
         svc.installed = true;
-        svc.port = Some(php.port);
+        svc.port = PhpManager::service_port(&php.version);
         svc.version = Some(php.version.clone());
         if php.running {
             svc.status = ServiceStatus::Running;
@@ -104,6 +118,19 @@ pub fn get_all_services(state: State<'_, AppState>) -> Result<Vec<ServiceInfo>,
     Ok(result)
 }
 
+fn mail_catcher_info(status: ServiceStatus) -> ServiceInfo {
+    ServiceInfo {
+        id: "mailcatcher".to_string(),
+        name: "Mail Catcher".to_string(),
+        status,
+        port: Some(MailCatcher::port()),
+        version: None,
+        pid: None,
+        installed: true,
+        initialized: true,
+    }
+}
+
 #[tauri::command]
 pub fn get_service(
     state: State<'_, AppState>,
@@ -121,6 +148,7 @@ pub fn get_service(
 
 #[tauri::command]
 pub fn start_service(
+    app: AppHandle,
     state: State<'_, AppState>,
     service_id: String,
 ) -> Result<ServiceInfo, AppError> {
@@ -183,13 +211,13 @@ pub fn start_service(
         id if id.starts_with("php-fpm-") => {
             let version = id.strip_prefix("php-fpm-").unwrap();
             let (child, pid) = PhpManager::start_fpm(version)?;
-            let port = utils::php_version_to_port(version);
+            let port = PhpManager::service_port(version);
 
             let info = ServiceInfo {
                 id: service_id.clone(),
                 name: format!("PHP-FPM {}", version),
                 status: ServiceStatus::Running,
-                port: Some(port),
+                port,
                 version: Some(version.to_string()),
                 pid: Some(pid),
                 installed: true,
@@ -226,6 +254,16 @@ pub fn start_service(
             };
             Ok(info)
         }
+        "mailcatcher" => {
+            MailCatcher::start(&app)?;
+            let info = mail_catcher_info(ServiceStatus::Running);
+            state
+                .services
+                .lock()
+                .map_err(|e| AppError::Service(e.to_string()))?
+                .insert("mailcatcher".to_string(), info.clone());
+            Ok(info)
+        }
         _ => Err(AppError::Service(format!(
             "Unknown service: '{}'",
             service_id
@@ -263,10 +301,8 @@ pub fn stop_service(
             Ok(info)
         }
         "mariadb" => {
-            // Remove child process handle (don't SIGKILL - let stop() SIGTERM gracefully)
-            if let Ok(mut procs) = state.child_processes.lock() {
-                procs.remove("mariadb");
-            }
+            // Release the handle (don't SIGKILL - let stop() SIGTERM gracefully)
+            ServiceSupervisor::release(&state, "mariadb");
             MariaDbManager::stop()?;
 
             let db_info = MariaDbManager::get_info();
@@ -292,17 +328,15 @@ pub fn stop_service(
         id if id.starts_with("php-fpm-") => {
             let version = id.strip_prefix("php-fpm-").unwrap();
 
-            // Remove child process handle (don't SIGKILL - let stop_fpm() SIGTERM gracefully)
-            if let Ok(mut procs) = state.child_processes.lock() {
-                procs.remove(&service_id);
-            }
+            // Release the handle (don't SIGKILL - let stop_fpm() SIGTERM gracefully)
+            ServiceSupervisor::release(&state, &service_id);
             PhpManager::stop_fpm(version)?;
 
             let info = ServiceInfo {
                 id: service_id.clone(),
                 name: format!("PHP-FPM {}", version),
                 status: ServiceStatus::Stopped,
-                port: Some(utils::php_version_to_port(version)),
+                port: PhpManager::service_port(version),
                 version: Some(version.to_string()),
                 pid: None,
                 installed: true,
@@ -333,6 +367,16 @@ pub fn stop_service(
             };
             Ok(info)
         }
+        "mailcatcher" => {
+            MailCatcher::stop(&state);
+            let info = mail_catcher_info(ServiceStatus::Stopped);
+            state
+                .services
+                .lock()
+                .map_err(|e| AppError::Service(e.to_string()))?
+                .insert("mailcatcher".to_string(), info.clone());
+            Ok(info)
+        }
         _ => Err(AppError::Service(format!(
             "Unknown service: '{}'",
             service_id
@@ -342,9 +386,16 @@ pub fn stop_service(
 
 #[tauri::command]
 pub fn restart_service(
+    app: AppHandle,
     state: State<'_, AppState>,
     service_id: String,
 ) -> Result<ServiceInfo, AppError> {
     stop_service(State::clone(&state), service_id.clone())?;
-    start_service(state, service_id)
+    start_service(app, state, service_id)
+}
+
+/// The most recent crash of each supervised service, newest first.
+#[tauri::command]
+pub fn service_last_exits(state: State<'_, AppState>) -> Result<Vec<ServiceExit>, AppError> {
+    Ok(ServiceSupervisor::last_exits(&state))
 }
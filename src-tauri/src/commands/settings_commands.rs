@@ -1,49 +1,36 @@
 use crate::config::app_config::AppConfig;
 use crate::config::paths;
 use crate::error::AppError;
+use crate::services::cli_shims::CliShims;
+use crate::services::mail_catcher::MailCatcher;
+use tauri::AppHandle;
 
 fn get_settings_path() -> std::path::PathBuf {
     paths::get_config_dir().join("settings.toml")
 }
 
+/// Defaults when settings.toml is missing or does not parse; a broken file
+/// is kept for the user to fix (see `AppConfig::save`).
 #[tauri::command]
 pub fn settings_get() -> Result<AppConfig, AppError> {
-    let path = get_settings_path();
-    if path.exists() {
-        let content = std::fs::read_to_string(&path)?;
-        // Fall back to defaults if file has old format (snake_case) or is corrupt
-        match toml::from_str(&content) {
-            Ok(config) => Ok(config),
-            Err(e) => {
-                log::warn!("Settings file has invalid format, using defaults: {}", e);
-                let config = AppConfig::default();
-                // Overwrite with correct format
-                if let Ok(new_content) = toml::to_string_pretty(&config) {
-                    let _ = std::fs::write(&path, new_content);
-                }
-                Ok(config)
-            }
-        }
-    } else {
-        Ok(AppConfig::default())
-    }
+    Ok(AppConfig::load())
 }
 
 #[tauri::command]
-pub fn settings_save(config: AppConfig) -> Result<(), AppError> {
-    let path = get_settings_path();
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let content = toml::to_string_pretty(&config)
-        .map_err(|e| AppError::Config(e.to_string()))?;
-    std::fs::write(&path, content)?;
+pub fn settings_save(app: AppHandle, mut config: AppConfig) -> Result<(), AppError> {
+    let current = AppConfig::load();
+    let previous_port = current.mail_catcher.port;
+    // FPM settings are edited on the PHP page; the settings form may hold a stale copy
+    config.php_fpm = current.php_fpm;
+    config.save()?;
+    CliShims::refresh();
     log::info!("Settings saved");
-    Ok(())
+    MailCatcher::apply_settings(&app, previous_port)
 }
 
 #[tauri::command]
-pub fn settings_reset() -> Result<AppConfig, AppError> {
+pub fn settings_reset(app: AppHandle) -> Result<AppConfig, AppError> {
+    let previous_port = AppConfig::load().mail_catcher.port;
     let config = AppConfig::default();
     let path = get_settings_path();
     if let Some(parent) = path.parent() {
@@ -52,6 +39,8 @@ pub fn settings_reset() -> Result<AppConfig, AppError> {
     let content = toml::to_string_pretty(&config)
         .map_err(|e| AppError::Config(e.to_string()))?;
     std::fs::write(&path, content)?;
+    CliShims::refresh();
     log::info!("Settings reset to defaults");
+    MailCatcher::apply_settings(&app, previous_port)?;
     Ok(config)
 }
@@ -1,5 +1,8 @@
 use crate::error::AppError;
-use crate::services::site_manager::{CreateSiteRequest, Site, SiteManager, UpdateSiteRequest};
+use crate::services::download_manager::DownloadManager;
+use crate::services::site_manager::{
+    CreateSiteRequest, Site, SiteIniOverrides, SiteManager, SitePool, UpdateSiteRequest,
+};
 use crate::services::template_manager::TemplateManager;
 use tauri::AppHandle;
 
@@ -14,6 +17,7 @@ pub fn site_get(id: String) -> Result<Site, AppError> {
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn site_create(
     name: String,
     domain: String,
@@ -21,6 +25,8 @@ pub fn site_create(
     php_version: String,
     ssl: bool,
     template: Option<String>,
+    pool: Option<SitePool>,
+    ini_overrides: Option<SiteIniOverrides>,
 ) -> Result<Site, AppError> {
     SiteManager::create(CreateSiteRequest {
         name,
@@ -29,10 +35,13 @@ pub fn site_create(
         php_version,
         ssl,
         template,
+        pool,
+        ini_overrides,
     })
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn site_update(
     id: String,
     name: Option<String>,
@@ -41,6 +50,9 @@ pub fn site_update(
     php_version: Option<String>,
     ssl: Option<bool>,
     active: Option<bool>,
+    pool: Option<SitePool>,
+    ini_overrides: Option<SiteIniOverrides>,
+    xdebug: Option<bool>,
 ) -> Result<Site, AppError> {
     SiteManager::update(
         &id,
@@ -51,6 +63,9 @@ pub fn site_update(
             php_version,
             ssl,
             active,
+            pool,
+            ini_overrides,
+            xdebug,
         },
     )
 }
@@ -66,5 +81,7 @@ pub async fn site_setup_template(
     site_id: String,
     template: String,
 ) -> Result<(), AppError> {
-    TemplateManager::setup(&app, &site_id, &template).await
+    let job_id = format!("template-{}", site_id);
+    let label = format!("{} template", template);
+    DownloadManager::run_job(&app, &job_id, &label, TemplateManager::setup(&app, &site_id, &template)).await
 }
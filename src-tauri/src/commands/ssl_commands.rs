@@ -1,11 +1,12 @@
 use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
 use crate::services::dns_manager::{DnsEntry, DnsManager, ResolverStatus};
 use crate::services::ssl_manager::{CertificateInfo, SslManager};
 use tauri::AppHandle;
 
 #[tauri::command]
 pub async fn ssl_install_mkcert(app: AppHandle) -> Result<(), AppError> {
-    SslManager::install_mkcert(&app).await
+    DownloadManager::run_job(&app, "mkcert", "mkcert", SslManager::install_mkcert(&app)).await
 }
 
 #[tauri::command]
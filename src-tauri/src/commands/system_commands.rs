@@ -1,5 +1,6 @@
 use crate::config::paths;
 use crate::error::AppError;
+use crate::services::cli_shims::CliShims;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -36,6 +37,7 @@ pub fn initialize_app() -> Result<String, AppError> {
         "ssl",
         "ssl/ca",
         "sites",
+        "cache",
         "binaries",
         "binaries/php",
         "binaries/nginx",
@@ -50,6 +52,9 @@ pub fn initialize_app() -> Result<String, AppError> {
         }
     }
 
+    // Pick up data dir or binary path changes from an app update
+    CliShims::refresh();
+
     log::info!("App initialized. Data dir: {}", data_dir.display());
     Ok(data_dir.to_string_lossy().to_string())
 }
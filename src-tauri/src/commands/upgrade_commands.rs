@@ -0,0 +1,47 @@
+use crate::commands::service_commands::{start_service, stop_service};
+use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
+use crate::services::upgrade_manager::{AvailableUpgrade, UpgradeManager};
+use crate::state::AppState;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub fn upgrades_check() -> Result<Vec<AvailableUpgrade>, AppError> {
+    Ok(UpgradeManager::check())
+}
+
+/// Upgrade a component to its latest patch release. A running service is
+/// stopped for the swap and started again afterwards, also when the upgrade
+/// failed and the previous binaries were restored.
+#[tauri::command]
+pub async fn upgrade_component(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<AvailableUpgrade, AppError> {
+    let component = UpgradeManager::check()
+        .into_iter()
+        .find(|u| u.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("'{}' is not installed", id)))?;
+
+    if component.running {
+        stop_service(state.clone(), component.service_id.clone())?;
+    }
+
+    let label = format!("{} upgrade", component.name);
+    let result = DownloadManager::run_job(&app, &id, &label, UpgradeManager::upgrade(&app, &id)).await;
+
+    if component.running {
+        if let Err(e) = start_service(app.clone(), state.clone(), component.service_id.clone()) {
+            log::error!("Failed to restart {} after upgrade: {}", component.service_id, e);
+            result?;
+            return Err(e);
+        }
+    }
+    result?;
+
+    UpgradeManager::check()
+        .into_iter()
+        .find(|u| u.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("'{}' is not installed", id)))
+}
@@ -1,4 +1,6 @@
+use crate::error::AppError;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,12 +14,195 @@ pub struct AppConfig {
     pub nginx_ssl_port: u16,
     pub mariadb_port: u16,
     pub php_fpm_base_port: u16,
+    /// Serve PHP-FPM over Unix sockets under the data dir instead of TCP
+    /// ports (macOS/Linux; applied when PHP-FPM and Nginx restart).
+    #[serde(default)]
+    pub php_fpm_unix_sockets: bool,
     #[serde(default)]
     pub openrouter_api_key: String,
     #[serde(default = "default_ai_model")]
     pub ai_model: String,
     #[serde(default = "default_ai_system_prompt")]
     pub ai_system_prompt: String,
+    /// Base URLs (or local folders) tried before the upstream URL.
+    /// Artifacts are looked up as `<mirror>/<upstream host>/<upstream path>`.
+    #[serde(default)]
+    pub download_mirrors: Vec<String>,
+    #[serde(default = "default_true")]
+    pub download_cache_enabled: bool,
+    /// Refuse downloads that have no known SHA-256 to check against.
+    #[serde(default)]
+    pub require_verified_downloads: bool,
+    /// Refuse PHP, Nginx, MariaDB, mkcert, extension and Composer binaries
+    /// that have no known SHA-256. Off by default: static-php.dev and nginx.org
+    /// publish no checksums the app can check against.
+    #[serde(default)]
+    pub require_verified_binaries: bool,
+    /// Installs/downloads allowed to run at the same time (applied on restart).
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    /// PHP-FPM process manager settings keyed by PHP version ("8.3").
+    /// Versions without an entry use `FpmSettings::default()`.
+    #[serde(default)]
+    pub php_fpm: BTreeMap<String, FpmSettings>,
+    /// What to do when PHP-FPM or MariaDB exits without being stopped.
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub mail_catcher: MailCatcherSettings,
+}
+
+/// The built-in SMTP server PHP's mail() and the site templates send to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MailCatcherSettings {
+    /// Start the mail catcher with LokcalDev
+    pub enabled: bool,
+    /// SMTP port on 127.0.0.1 (applied when the mail catcher restarts)
+    pub port: u16,
+}
+
+impl Default for MailCatcherSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            port: 1025,
+        }
+    }
+}
+
+/// Automatic restart of crashed services, with exponential backoff.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestartPolicy {
+    pub enabled: bool,
+    /// Restarts in a row before giving up; the count resets once a service
+    /// stays up for a few minutes
+    pub max_restarts: u32,
+    /// Delay before the first restart, doubled for each further one
+    pub backoff_secs: u64,
+    pub max_backoff_secs: u64,
+    /// Show a desktop notification when a service crashes
+    pub notify: bool,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_restarts: 3,
+            backoff_secs: 2,
+            max_backoff_secs: 60,
+            notify: true,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Delay before restart number `attempt` (0-based).
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let secs = self
+            .backoff_secs
+            .saturating_mul(1u64 << attempt.min(16))
+            .min(self.max_backoff_secs.max(self.backoff_secs));
+        std::time::Duration::from_secs(secs)
+    }
+}
+
+/// Process manager settings of a version's shared `[www]` pool (php-fpm only;
+/// the Windows php-cgi process ignores them).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FpmSettings {
+    /// "static", "dynamic" or "ondemand"
+    pub pm: String,
+    pub max_children: u32,
+    /// Used by `dynamic` only
+    pub start_servers: u32,
+    pub min_spare_servers: u32,
+    pub max_spare_servers: u32,
+    /// Seconds an idle `ondemand` worker is kept
+    pub process_idle_timeout: u32,
+    /// Seconds before a request is killed (0 = never)
+    pub request_terminate_timeout: u32,
+    /// Log a backtrace of requests slower than this many seconds (0 = off)
+    #[serde(default = "default_slowlog_timeout")]
+    pub slowlog_timeout: u32,
+    /// Requests a worker serves before it is recycled (0 = never)
+    pub max_requests: u32,
+    /// "alert", "error", "warning", "notice" or "debug"
+    pub log_level: String,
+}
+
+impl Default for FpmSettings {
+    fn default() -> Self {
+        Self {
+            pm: "dynamic".to_string(),
+            max_children: 5,
+            start_servers: 2,
+            min_spare_servers: 1,
+            max_spare_servers: 3,
+            process_idle_timeout: 10,
+            request_terminate_timeout: 0,
+            slowlog_timeout: default_slowlog_timeout(),
+            max_requests: 0,
+            log_level: "notice".to_string(),
+        }
+    }
+}
+
+impl FpmSettings {
+    /// Reject combinations php-fpm would refuse to start with.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !["static", "dynamic", "ondemand"].contains(&self.pm.as_str()) {
+            return Err(AppError::Config(format!(
+                "Invalid pm mode '{}': expected static, dynamic or ondemand",
+                self.pm
+            )));
+        }
+        if !["alert", "error", "warning", "notice", "debug"].contains(&self.log_level.as_str()) {
+            return Err(AppError::Config(format!("Invalid log level '{}'", self.log_level)));
+        }
+        if !(1..=1024).contains(&self.max_children) {
+            return Err(AppError::Config("pm.max_children must be between 1 and 1024".to_string()));
+        }
+        if self.pm == "dynamic" {
+            if self.min_spare_servers < 1 {
+                return Err(AppError::Config("pm.min_spare_servers must be at least 1".to_string()));
+            }
+            if self.min_spare_servers > self.max_spare_servers {
+                return Err(AppError::Config(
+                    "pm.min_spare_servers must not exceed pm.max_spare_servers".to_string(),
+                ));
+            }
+            if self.max_spare_servers > self.max_children {
+                return Err(AppError::Config(
+                    "pm.max_spare_servers must not exceed pm.max_children".to_string(),
+                ));
+            }
+            if self.start_servers < self.min_spare_servers || self.start_servers > self.max_spare_servers {
+                return Err(AppError::Config(
+                    "pm.start_servers must be between pm.min_spare_servers and pm.max_spare_servers".to_string(),
+                ));
+            }
+        }
+        if self.pm == "ondemand" && self.process_idle_timeout == 0 {
+            return Err(AppError::Config("pm.process_idle_timeout must be at least 1 second".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn default_slowlog_timeout() -> u32 {
+    5
+}
+
+fn default_max_concurrent_downloads() -> usize {
+    2
+}
+
+fn default_true() -> bool {
+    true
 }
 
 fn default_ai_model() -> String {
@@ -41,9 +226,64 @@ impl Default for AppConfig {
             nginx_ssl_port: 8443,
             mariadb_port: 3306,
             php_fpm_base_port: 9081,
+            php_fpm_unix_sockets: false,
             openrouter_api_key: String::new(),
             ai_model: default_ai_model(),
             ai_system_prompt: default_ai_system_prompt(),
+            download_mirrors: Vec::new(),
+            download_cache_enabled: true,
+            require_verified_downloads: false,
+            require_verified_binaries: false,
+            max_concurrent_downloads: default_max_concurrent_downloads(),
+            php_fpm: BTreeMap::new(),
+            restart_policy: RestartPolicy::default(),
+            mail_catcher: MailCatcherSettings::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    fn get_path() -> std::path::PathBuf {
+        crate::config::paths::get_config_dir().join("settings.toml")
+    }
+
+    /// Parse settings.toml; `Ok(None)` when there is none yet.
+    fn read() -> Result<Option<Self>, AppError> {
+        match std::fs::read_to_string(Self::get_path()) {
+            Ok(content) => toml::from_str(&content)
+                .map(Some)
+                .map_err(|e| AppError::Config(format!("settings.toml could not be parsed: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
         }
     }
+
+    /// Read settings.toml, falling back to defaults if missing or unreadable.
+    pub fn load() -> Self {
+        match Self::read() {
+            Ok(config) => config.unwrap_or_default(),
+            Err(e) => {
+                log::error!("{}; using default settings", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write settings.toml. A file that does not parse is left alone, since
+    /// these values may be defaults that replaced the user's settings.
+    pub fn save(&self) -> Result<(), AppError> {
+        if let Err(e) = Self::read() {
+            return Err(AppError::Config(format!("{}. Fix it or reset the settings before saving", e)));
+        }
+        let config_dir = crate::config::paths::get_config_dir();
+        std::fs::create_dir_all(&config_dir)?;
+        let content = toml::to_string_pretty(self).map_err(|e| AppError::Config(e.to_string()))?;
+        std::fs::write(Self::get_path(), content)?;
+        Ok(())
+    }
+
+    /// FPM settings of a PHP version, falling back to the defaults.
+    pub fn fpm_settings(&self, version: &str) -> FpmSettings {
+        self.php_fpm.get(version).cloned().unwrap_or_default()
+    }
 }
@@ -35,6 +35,10 @@ pub fn get_ssl_dir() -> PathBuf {
     get_data_dir().join("ssl")
 }
 
+pub fn get_cache_dir() -> PathBuf {
+    get_data_dir().join("cache")
+}
+
 pub fn get_binaries_dir() -> PathBuf {
     get_data_dir().join("binaries")
 }
@@ -49,6 +53,10 @@ pub fn get_nginx_dir() -> PathBuf {
     get_binaries_dir().join("nginx")
 }
 
+pub fn get_composer_dir() -> PathBuf {
+    get_binaries_dir().join("composer")
+}
+
 #[allow(dead_code)]
 pub fn get_mariadb_dir() -> PathBuf {
     get_binaries_dir().join("mariadb")
@@ -69,3 +77,18 @@ pub fn get_nginx_config_dir() -> PathBuf {
 pub fn get_phpmyadmin_dir() -> PathBuf {
     get_data_dir().join("phpmyadmin")
 }
+
+/// Unix sockets of PHP-FPM pools.
+pub fn get_run_dir() -> PathBuf {
+    get_data_dir().join("run")
+}
+
+/// Command-line shims (php, composer, mysql, ...) users put on their PATH.
+pub fn get_bin_dir() -> PathBuf {
+    get_data_dir().join("bin")
+}
+
+/// Messages received by the built-in mail catcher.
+pub fn get_mail_dir() -> PathBuf {
+    get_data_dir().join("mail")
+}
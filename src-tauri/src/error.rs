@@ -14,6 +14,9 @@ pub enum AppError {
     #[error("Download error: {0}")]
     Download(String),
 
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
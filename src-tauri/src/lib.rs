@@ -4,10 +4,14 @@ mod error;
 mod services;
 mod state;
 
+use config::app_config::AppConfig;
 use config::paths;
+use services::mail_catcher::MailCatcher;
 use services::mariadb_manager::MariaDbManager;
 use services::nginx_manager::NginxManager;
+use services::php_catalogue::PhpCatalogue;
 use services::php_manager::PhpManager;
+use services::service_supervisor::ServiceSupervisor;
 use state::AppState;
 use tauri::image::Image;
 use tauri::menu::{AboutMetadata, Menu, PredefinedMenuItem, Submenu};
@@ -101,6 +105,12 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            ServiceSupervisor::spawn(app.handle().clone());
+            if AppConfig::load().mail_catcher.enabled {
+                if let Err(e) = MailCatcher::start(app.handle()) {
+                    log::warn!("Mail catcher not started: {}", e);
+                }
+            }
             Ok(())
         })
         .manage(app_state)
@@ -111,11 +121,13 @@ pub fn run() {
             commands::service_commands::start_service,
             commands::service_commands::stop_service,
             commands::service_commands::restart_service,
+            commands::service_commands::service_last_exits,
             // System commands
             commands::system_commands::get_system_info,
             commands::system_commands::initialize_app,
             // PHP commands
             commands::php_commands::php_list_versions,
+            commands::php_commands::php_refresh_catalogue,
             commands::php_commands::php_install_version,
             commands::php_commands::php_remove_version,
             commands::php_commands::php_start_fpm,
@@ -123,8 +135,31 @@ pub fn run() {
             commands::php_commands::php_restart_fpm,
             commands::php_commands::php_get_ini,
             commands::php_commands::php_set_ini_directive,
+            commands::php_commands::php_remove_ini_directive,
+            commands::php_commands::php_uncomment_ini_directive,
+            commands::php_commands::php_get_effective_ini_value,
+            commands::php_commands::php_list_ini_presets,
+            commands::php_commands::php_save_ini_preset,
+            commands::php_commands::php_delete_ini_preset,
+            commands::php_commands::php_apply_ini_preset,
+            commands::php_commands::php_list_ini_snapshots,
+            commands::php_commands::php_diff_ini_snapshots,
+            commands::php_commands::php_restore_ini_snapshot,
+            commands::php_commands::php_delete_ini_snapshot,
             commands::php_commands::php_list_extensions,
             commands::php_commands::php_toggle_extension,
+            commands::php_commands::php_list_installable_extensions,
+            commands::php_commands::php_install_extension,
+            commands::php_commands::php_fpm_status,
+            commands::php_commands::php_fpm_slowlog,
+            commands::php_commands::php_get_xdebug,
+            commands::php_commands::php_set_xdebug,
+            commands::php_commands::php_list_xdebug_profiles,
+            commands::php_commands::php_get_xdebug_profile_path,
+            commands::php_commands::php_delete_xdebug_profile,
+            commands::php_commands::php_clear_xdebug_profiles,
+            commands::php_commands::php_get_fpm_settings,
+            commands::php_commands::php_set_fpm_settings,
             // Nginx commands
             commands::nginx_commands::nginx_install,
             commands::nginx_commands::nginx_get_info,
@@ -177,6 +212,32 @@ pub fn run() {
             // phpMyAdmin commands
             commands::phpmyadmin_commands::phpmyadmin_install,
             commands::phpmyadmin_commands::phpmyadmin_get_info,
+            // Composer commands
+            commands::composer_commands::composer_get_info,
+            commands::composer_commands::composer_install,
+            commands::composer_commands::composer_run,
+            // CLI shim commands
+            commands::cli_commands::cli_get_shims_info,
+            commands::cli_commands::cli_install_shims,
+            commands::cli_commands::cli_remove_shims,
+            // Mail commands
+            commands::mail_commands::mail_get_info,
+            commands::mail_commands::mail_list,
+            commands::mail_commands::mail_get,
+            commands::mail_commands::mail_get_raw,
+            commands::mail_commands::mail_get_attachment_path,
+            commands::mail_commands::mail_delete,
+            commands::mail_commands::mail_clear,
+            // Download commands
+            commands::download_commands::downloads_list,
+            commands::download_commands::downloads_clear_history,
+            commands::download_commands::download_cancel,
+            commands::download_commands::download_cache_list,
+            commands::download_commands::download_cache_evict,
+            commands::download_commands::download_cache_clear,
+            // Upgrade commands
+            commands::upgrade_commands::upgrades_check,
+            commands::upgrade_commands::upgrade_component,
             // AI commands
             commands::ai_commands::ai_fetch_models,
             commands::ai_commands::ai_chat,
@@ -195,6 +256,8 @@ pub fn run() {
 fn shutdown_services(app_handle: &tauri::AppHandle) {
     // 1. Kill all tracked child processes
     let state = app_handle.state::<AppState>();
+    ServiceSupervisor::shutdown(&state);
+    MailCatcher::stop(&state);
     if let Ok(mut children) = state.child_processes.lock() {
         for (name, child) in children.iter_mut() {
             log::info!("Shutting down child process: {}", name);
@@ -210,8 +273,8 @@ fn shutdown_services(app_handle: &tauri::AppHandle) {
     let _ = MariaDbManager::stop();
 
     // Stop all PHP-FPM versions
-    for version in &["8.1", "8.2", "8.3", "8.4"] {
-        let _ = PhpManager::stop_fpm(version);
+    for version in PhpCatalogue::versions() {
+        let _ = PhpManager::stop_fpm(&version);
     }
 
     log::info!("All services stopped");
@@ -1,14 +1,17 @@
+use crate::commands::service_commands;
 use crate::config::app_config::AppConfig;
 use crate::config::paths;
 use crate::error::AppError;
 use crate::services::mariadb_manager::MariaDbManager;
 use crate::services::nginx_manager::NginxManager;
+use crate::services::php_catalogue::PhpCatalogue;
 use crate::services::php_manager::PhpManager;
 use crate::services::site_manager::{CreateSiteRequest, SiteManager};
+use crate::state::AppState;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
 // ── Types ─────────────────────────────────────────────────────────
 
@@ -58,6 +61,12 @@ pub struct AiStreamChunk {
 // ── Tool Definitions ──────────────────────────────────────────────
 
 fn get_tool_definitions() -> Vec<Value> {
+    let service_ids = std::iter::once("nginx".to_string())
+        .chain(std::iter::once("mariadb".to_string()))
+        .chain(PhpCatalogue::versions().into_iter().map(|v| format!("php-fpm-{}", v)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
     vec![
         json!({
             "type": "function",
@@ -71,7 +80,7 @@ fn get_tool_definitions() -> Vec<Value> {
             "type": "function",
             "function": {
                 "name": "start_service",
-                "description": "Start a service. Valid service IDs: nginx, mariadb, php-fpm-8.1, php-fpm-8.2, php-fpm-8.3, php-fpm-8.4",
+                "description": format!("Start a service. Valid service IDs: {}", service_ids),
                 "parameters": {
                     "type": "object",
                     "properties": {
@@ -85,7 +94,7 @@ fn get_tool_definitions() -> Vec<Value> {
             "type": "function",
             "function": {
                 "name": "stop_service",
-                "description": "Stop a service. Valid service IDs: nginx, mariadb, php-fpm-8.1, php-fpm-8.2, php-fpm-8.3, php-fpm-8.4",
+                "description": format!("Stop a service. Valid service IDs: {}", service_ids),
                 "parameters": {
                     "type": "object",
                     "properties": {
@@ -395,7 +404,7 @@ pub async fn chat_stream(app: &AppHandle, messages: Vec<AiMessage>) -> Result<()
 
 // ── Tool Execution ────────────────────────────────────────────────
 
-pub fn execute_tool(tool_name: &str, arguments: &str) -> Result<String, AppError> {
+pub fn execute_tool(app: &AppHandle, tool_name: &str, arguments: &str) -> Result<String, AppError> {
     let args: Value = serde_json::from_str(arguments).unwrap_or(json!({}));
 
     match tool_name {
@@ -436,45 +445,21 @@ pub fn execute_tool(tool_name: &str, arguments: &str) -> Result<String, AppError
             Ok(serde_json::to_string_pretty(&services).unwrap_or_default())
         }
 
+        // Same paths as the Services page, so started processes are tracked
+        // and supervised and stopped ones are released
         "start_service" => {
-            let service_id = args["service_id"].as_str().unwrap_or("");
-            match service_id {
-                "nginx" => {
-                    NginxManager::start()?;
-                    Ok(format!("Nginx started successfully on port {}", NginxManager::get_info().port))
-                }
-                "mariadb" => {
-                    let _child = MariaDbManager::start()?;
-                    // Note: child process is not tracked here, service_commands handles that
-                    Ok("MariaDB started successfully".to_string())
-                }
-                id if id.starts_with("php-fpm-") => {
-                    let version = id.strip_prefix("php-fpm-").unwrap();
-                    let (_child, _pid) = PhpManager::start_fpm(version)?;
-                    Ok(format!("PHP-FPM {} started successfully", version))
-                }
-                _ => Err(AppError::Service(format!("Unknown service: {}", service_id))),
-            }
+            let service_id = args["service_id"].as_str().unwrap_or("").to_string();
+            let info = service_commands::start_service(app.clone(), app.state::<AppState>(), service_id)?;
+            Ok(match info.port {
+                Some(port) => format!("{} started successfully on port {}", info.name, port),
+                None => format!("{} started successfully", info.name),
+            })
         }
 
         "stop_service" => {
-            let service_id = args["service_id"].as_str().unwrap_or("");
-            match service_id {
-                "nginx" => {
-                    NginxManager::stop()?;
-                    Ok("Nginx stopped successfully".to_string())
-                }
-                "mariadb" => {
-                    MariaDbManager::stop()?;
-                    Ok("MariaDB stopped successfully".to_string())
-                }
-                id if id.starts_with("php-fpm-") => {
-                    let version = id.strip_prefix("php-fpm-").unwrap();
-                    PhpManager::stop_fpm(version)?;
-                    Ok(format!("PHP-FPM {} stopped successfully", version))
-                }
-                _ => Err(AppError::Service(format!("Unknown service: {}", service_id))),
-            }
+            let service_id = args["service_id"].as_str().unwrap_or("").to_string();
+            let info = service_commands::stop_service(app.state::<AppState>(), service_id)?;
+            Ok(format!("{} stopped successfully", info.name))
         }
 
         "list_sites" => {
@@ -515,6 +500,8 @@ pub fn execute_tool(tool_name: &str, arguments: &str) -> Result<String, AppError
                 php_version: php_version.to_string(),
                 ssl: false,
                 template: None,
+                pool: None,
+                ini_overrides: None,
             };
 
             let site = SiteManager::create(req)?;
@@ -0,0 +1,328 @@
+use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+/// Archive formats recognised by their leading magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveFormat {
+    Zip,
+    TarGz,
+    TarXz,
+    TarBz2,
+    TarZst,
+    Tar,
+}
+
+/// Reader wrapper that counts the (compressed) bytes consumed so extraction
+/// progress can be reported against the archive's size on disk.
+struct CountingReader<R> {
+    inner: R,
+    read: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+pub struct ArchiveExtractor;
+
+impl ArchiveExtractor {
+    fn emit_progress(app: &AppHandle, id: &str, done: u64, total: Option<u64>, percent: f64, message: String) {
+        let _ = app.emit(
+            "download-progress",
+            DownloadManager::progress(id, done, total, percent, "extracting", Some(message)),
+        );
+    }
+
+    fn detect_format(archive_path: &Path) -> Result<ArchiveFormat, AppError> {
+        let mut magic = [0u8; 6];
+        let mut file = std::fs::File::open(archive_path)?;
+        let n = file.read(&mut magic)?;
+        let magic = &magic[..n];
+
+        let format = if magic.starts_with(b"PK\x03\x04") || magic.starts_with(b"PK\x05\x06") {
+            ArchiveFormat::Zip
+        } else if magic.starts_with(&[0x1f, 0x8b]) {
+            ArchiveFormat::TarGz
+        } else if magic.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            ArchiveFormat::TarXz
+        } else if magic.starts_with(b"BZh") {
+            ArchiveFormat::TarBz2
+        } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            ArchiveFormat::TarZst
+        } else {
+            ArchiveFormat::Tar
+        };
+        Ok(format)
+    }
+
+    /// Extract a zip or (optionally compressed) tar archive into `dest_dir`.
+    ///
+    /// The format is detected from the file contents. The first
+    /// `strip_components` path components of every entry are dropped (like
+    /// `tar --strip-components`), so an archive wrapping everything in
+    /// `nginx-1.28.2/` can be unpacked straight into the install directory.
+    /// Entries with absolute paths, `..` components or links pointing outside
+    /// `dest_dir` abort the extraction.
+    pub fn extract(
+        app: &AppHandle,
+        id: &str,
+        archive_path: &Path,
+        dest_dir: &Path,
+        strip_components: usize,
+    ) -> Result<(), AppError> {
+        log::info!(
+            "Extracting {} to {} (strip {})",
+            archive_path.display(),
+            dest_dir.display(),
+            strip_components
+        );
+        std::fs::create_dir_all(dest_dir)?;
+        let root = dest_dir.canonicalize()?;
+
+        Self::emit_progress(app, id, 0, None, 0.0, "Extracting archive...".to_string());
+
+        let count = match Self::detect_format(archive_path)? {
+            ArchiveFormat::Zip => Self::extract_zip(app, id, archive_path, &root, strip_components)?,
+            format => Self::extract_tar(app, id, archive_path, format, &root, strip_components)?,
+        };
+
+        Self::emit_progress(app, id, count, Some(count), 100.0, format!("Extracted {} files", count));
+
+        log::info!("Extraction complete: {} ({} files)", dest_dir.display(), count);
+        Ok(())
+    }
+
+    // ── Tar ─────────────────────────────────────────────────────────
+
+    fn extract_tar(
+        app: &AppHandle,
+        id: &str,
+        archive_path: &Path,
+        format: ArchiveFormat,
+        root: &Path,
+        strip_components: usize,
+    ) -> Result<u64, AppError> {
+        let size = std::fs::metadata(archive_path)?.len().max(1);
+        let read = Arc::new(AtomicU64::new(0));
+        let file = CountingReader {
+            inner: BufReader::new(std::fs::File::open(archive_path)?),
+            read: read.clone(),
+        };
+
+        let reader: Box<dyn Read> = match format {
+            ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+            ArchiveFormat::TarXz => Box::new(xz2::read::XzDecoder::new(file)),
+            ArchiveFormat::TarBz2 => Box::new(bzip2::read::BzDecoder::new(file)),
+            ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::new(file)?),
+            _ => Box::new(file),
+        };
+
+        let mut archive = tar::Archive::new(reader);
+        let mut count = 0u64;
+        let mut last_percent = -1i64;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+            let Some(relative) = Self::sanitize(&entry_path, strip_components)? else {
+                continue;
+            };
+            let out_path = root.join(&relative);
+            let entry_type = entry.header().entry_type();
+
+            if entry_type.is_dir() {
+                Self::prepare_parent(root, &out_path)?;
+                std::fs::create_dir_all(&out_path)?;
+                Self::ensure_inside(root, &out_path)?;
+                continue;
+            }
+
+            let parent = Self::prepare_parent(root, &out_path)?;
+
+            if entry_type.is_symlink() {
+                let target = entry
+                    .link_name()?
+                    .ok_or_else(|| Self::unsafe_entry(&entry_path, "symlink without a target"))?
+                    .into_owned();
+                if target.is_absolute() || !Self::normalize(&parent.join(&target)).starts_with(root) {
+                    return Err(Self::unsafe_entry(&entry_path, "symlink points outside the target directory"));
+                }
+                Self::remove_existing(&out_path)?;
+                entry.unpack(&out_path)?;
+                // The lexical check can be fooled by chains of links; re-check the real target
+                if let Ok(resolved) = out_path.canonicalize() {
+                    if !resolved.starts_with(root) {
+                        let _ = std::fs::remove_file(&out_path);
+                        return Err(Self::unsafe_entry(&entry_path, "symlink points outside the target directory"));
+                    }
+                }
+            } else if entry_type.is_hard_link() {
+                let target = entry
+                    .link_name()?
+                    .ok_or_else(|| Self::unsafe_entry(&entry_path, "hard link without a target"))?
+                    .into_owned();
+                let Some(target) = Self::sanitize(&target, strip_components)? else {
+                    return Err(Self::unsafe_entry(&entry_path, "hard link target was stripped"));
+                };
+                let source = root.join(target);
+                Self::ensure_inside(root, &source)?;
+                Self::remove_existing(&out_path)?;
+                std::fs::hard_link(&source, &out_path).or_else(|_| std::fs::copy(&source, &out_path).map(|_| ()))?;
+            } else if entry_type.is_file() || entry_type.is_contiguous() {
+                Self::remove_existing(&out_path)?;
+                entry.unpack(&out_path)?;
+            } else {
+                // Device nodes, FIFOs and the like have no business in a package
+                log::warn!("Skipping unsupported tar entry {}", entry_path.display());
+                continue;
+            }
+
+            count += 1;
+            let percent = (read.load(Ordering::Relaxed) as f64 / size as f64 * 100.0).min(99.0);
+            if percent as i64 != last_percent {
+                last_percent = percent as i64;
+                Self::emit_progress(app, id, count, None, percent, format!("Extracting {}", relative.display()));
+            }
+        }
+
+        Ok(count)
+    }
+
+    // ── Zip ─────────────────────────────────────────────────────────
+
+    fn extract_zip(
+        app: &AppHandle,
+        id: &str,
+        archive_path: &Path,
+        root: &Path,
+        strip_components: usize,
+    ) -> Result<u64, AppError> {
+        let file = std::fs::File::open(archive_path)?;
+        let mut archive =
+            zip::ZipArchive::new(file).map_err(|e| AppError::Download(e.to_string()))?;
+        let total = archive.len() as u64;
+        let mut count = 0u64;
+        let mut last_percent = -1i64;
+
+        for i in 0..archive.len() {
+            let mut entry = archive
+                .by_index(i)
+                .map_err(|e| AppError::Download(e.to_string()))?;
+
+            let entry_path = PathBuf::from(entry.name());
+            let Some(relative) = Self::sanitize(&entry_path, strip_components)? else {
+                continue;
+            };
+            let out_path = root.join(&relative);
+
+            if entry.is_dir() {
+                Self::prepare_parent(root, &out_path)?;
+                std::fs::create_dir_all(&out_path)?;
+                Self::ensure_inside(root, &out_path)?;
+                continue;
+            }
+
+            Self::prepare_parent(root, &out_path)?;
+            Self::remove_existing(&out_path)?;
+            let mut outfile = std::fs::File::create(&out_path)?;
+            std::io::copy(&mut entry, &mut outfile)?;
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if let Some(mode) = entry.unix_mode() {
+                    std::fs::set_permissions(&out_path, std::fs::Permissions::from_mode(mode & 0o777))?;
+                }
+            }
+
+            count += 1;
+            let percent = (i as f64 + 1.0) / total as f64 * 100.0;
+            if percent as i64 != last_percent {
+                last_percent = percent as i64;
+                Self::emit_progress(app, id, count, Some(total), percent, format!("Extracting {}", relative.display()));
+            }
+        }
+
+        Ok(count)
+    }
+
+    // ── Path safety ─────────────────────────────────────────────────
+
+    /// Validate an entry path and drop its first `strip_components` components.
+    /// Returns `None` for entries that are consumed entirely by the strip.
+    fn sanitize(path: &Path, strip_components: usize) -> Result<Option<PathBuf>, AppError> {
+        let mut parts = Vec::new();
+        for component in path.components() {
+            match component {
+                Component::Normal(part) => parts.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => return Err(Self::unsafe_entry(path, "path contains '..'")),
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err(Self::unsafe_entry(path, "absolute path"))
+                }
+            }
+        }
+        if parts.len() <= strip_components {
+            return Ok(None);
+        }
+        Ok(Some(parts[strip_components..].iter().collect()))
+    }
+
+    /// Create the parent directory of `out_path` and make sure that, once
+    /// symlinks created by earlier entries are resolved, it is still inside `root`.
+    fn prepare_parent(root: &Path, out_path: &Path) -> Result<PathBuf, AppError> {
+        let parent = out_path.parent().unwrap_or(root);
+        std::fs::create_dir_all(parent)?;
+        Self::ensure_inside(root, parent)
+    }
+
+    fn ensure_inside(root: &Path, path: &Path) -> Result<PathBuf, AppError> {
+        let resolved = path.canonicalize()?;
+        if !resolved.starts_with(root) {
+            return Err(Self::unsafe_entry(path, "resolves outside the target directory"));
+        }
+        Ok(resolved)
+    }
+
+    /// Lexically resolve `.` and `..` without touching the filesystem.
+    fn normalize(path: &Path) -> PathBuf {
+        let mut out = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::ParentDir => {
+                    out.pop();
+                }
+                Component::CurDir => {}
+                other => out.push(other.as_os_str()),
+            }
+        }
+        out
+    }
+
+    /// Remove a file or symlink left at `path` so writing the new entry never
+    /// follows a link planted by an earlier entry or a previous install.
+    fn remove_existing(path: &Path) -> Result<(), AppError> {
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if !meta.is_dir() => std::fs::remove_file(path)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn unsafe_entry(path: &Path, reason: &str) -> AppError {
+        AppError::Download(format!(
+            "Refusing to extract archive entry {}: {}",
+            path.display(),
+            reason
+        ))
+    }
+}
@@ -0,0 +1,172 @@
+use crate::config::paths;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// One cached download, keyed by the upstream URL it was fetched for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedArtifact {
+    pub url: String,
+    pub sha256: String,
+    pub size: u64,
+    pub cached_at: String,
+    /// Whether the digest matched a known checksum when it was stored.
+    #[serde(default)]
+    pub verified: bool,
+}
+
+/// Content-addressed artifact store under `<data>/cache`.
+///
+/// Blobs live in `cache/sha256/<digest>`; `cache/index.json` maps upstream
+/// URLs to digests so the same archive is never fetched twice, and a cache
+/// copied from another machine works with no network at all. Entries never
+/// checked against a known digest are only served when upstream is
+/// unreachable, since their URL may since point at a newer file.
+pub struct ArtifactCache;
+
+impl ArtifactCache {
+    fn get_index_path() -> PathBuf {
+        paths::get_cache_dir().join("index.json")
+    }
+
+    fn get_blob_path(sha256: &str) -> PathBuf {
+        paths::get_cache_dir().join("sha256").join(sha256)
+    }
+
+    fn read_index() -> HashMap<String, CachedArtifact> {
+        std::fs::read_to_string(Self::get_index_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_index(index: &HashMap<String, CachedArtifact>) -> Result<(), AppError> {
+        let index_path = Self::get_index_path();
+        if let Some(parent) = index_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&index_path, serde_json::to_string_pretty(index)?)?;
+        Ok(())
+    }
+
+    /// SHA-256 of a file on disk, as lowercase hex.
+    pub fn hash_file(path: &Path) -> Result<String, AppError> {
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = file.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Return the blob path for `url` if it is cached and still on disk.
+    pub fn lookup(url: &str) -> Option<(CachedArtifact, PathBuf)> {
+        let artifact = Self::read_index().remove(url)?;
+        let blob = Self::get_blob_path(&artifact.sha256);
+        if blob.is_file() {
+            Some((artifact, blob))
+        } else {
+            None
+        }
+    }
+
+    /// Copy a freshly downloaded (and already hashed) file into the store and index it under `url`.
+    pub fn store(url: &str, file: &Path, sha256: &str, verified: bool) -> Result<CachedArtifact, AppError> {
+        let sha256 = sha256.to_string();
+        let blob = Self::get_blob_path(&sha256);
+        if !blob.exists() {
+            if let Some(parent) = blob.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(file, &blob)?;
+        }
+
+        let artifact = CachedArtifact {
+            url: url.to_string(),
+            sha256,
+            size: std::fs::metadata(&blob)?.len(),
+            cached_at: chrono::Utc::now().to_rfc3339(),
+            verified,
+        };
+
+        let mut index = Self::read_index();
+        let replaced = index.insert(url.to_string(), artifact.clone());
+        Self::write_index(&index)?;
+        // A moving URL now serves a different file; drop the old blob if unused
+        if let Some(old) = replaced.filter(|old| old.sha256 != artifact.sha256) {
+            if !index.values().any(|a| a.sha256 == old.sha256) {
+                let _ = std::fs::remove_file(Self::get_blob_path(&old.sha256));
+            }
+        }
+
+        log::info!("Cached {} as {}", url, artifact.sha256);
+        Ok(artifact)
+    }
+
+    /// Drop `url` from the index (the blob is kept if another URL shares it).
+    pub fn evict(url: &str) -> Result<(), AppError> {
+        let mut index = Self::read_index();
+        if let Some(artifact) = index.remove(url) {
+            Self::write_index(&index)?;
+            if !index.values().any(|a| a.sha256 == artifact.sha256) {
+                let _ = std::fs::remove_file(Self::get_blob_path(&artifact.sha256));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn list() -> Vec<CachedArtifact> {
+        let mut artifacts: Vec<CachedArtifact> = Self::read_index()
+            .into_values()
+            .filter(|a| Self::get_blob_path(&a.sha256).is_file())
+            .collect();
+        artifacts.sort_by(|a, b| a.url.cmp(&b.url));
+        artifacts
+    }
+
+    pub fn clear() -> Result<(), AppError> {
+        let cache_dir = paths::get_cache_dir();
+        if cache_dir.exists() {
+            std::fs::remove_dir_all(&cache_dir)?;
+        }
+        log::info!("Download cache cleared");
+        Ok(())
+    }
+
+    /// Candidate locations for `url` on each mirror, in configured order.
+    /// A mirror is either an http(s) base URL or a local folder (optionally `file://`).
+    pub fn mirror_candidates(url: &str, mirrors: &[String]) -> Vec<String> {
+        let Ok(parsed) = reqwest::Url::parse(url) else {
+            return Vec::new();
+        };
+        let host = parsed.host_str().unwrap_or_default();
+        let relative = format!("{}{}", host, parsed.path());
+
+        mirrors
+            .iter()
+            .map(|m| m.trim().trim_end_matches('/'))
+            .filter(|m| !m.is_empty())
+            .map(|m| format!("{}/{}", m, relative))
+            .collect()
+    }
+
+    /// Resolve a mirror candidate to a local path if it points at the filesystem.
+    pub fn local_path(candidate: &str) -> Option<PathBuf> {
+        if let Some(rest) = candidate.strip_prefix("file://") {
+            return Some(PathBuf::from(rest));
+        }
+        if candidate.starts_with("http://") || candidate.starts_with("https://") {
+            return None;
+        }
+        Some(PathBuf::from(candidate))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::config::paths;
+use crate::services::php_catalogue::PhpCatalogue;
+use crate::services::service_catalogue::ServiceCatalogue;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Expected SHA-256 digests for downloaded artifacts.
+///
+/// Digests come from, in order:
+/// 1. `config/checksums.json` — a user/company manifest mapping URL → digest
+/// 2. the `sha256` maps of the release catalogues (php-releases.json,
+///    service-releases.json), keyed by file name
+/// 3. the checksum file the upstream publishes next to the artifact
+///    (phpMyAdmin `.sha256`, MariaDB `sha256sums.txt`, windows.php.net
+///    `sha256sum.txt`, GitHub release asset digests)
+pub struct ChecksumManifest;
+
+impl ChecksumManifest {
+    fn get_manifest_path() -> PathBuf {
+        paths::get_config_dir().join("checksums.json")
+    }
+
+    fn read_manifest() -> HashMap<String, String> {
+        std::fs::read_to_string(Self::get_manifest_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Look up the expected digest for `url`. `None` means nothing is known
+    /// about it (e.g. no manifest entry and no network for the sidecar file).
+    pub async fn expected_sha256(url: &str) -> Option<String> {
+        if let Some(digest) = Self::read_manifest().get(url) {
+            return Some(digest.trim().to_lowercase());
+        }
+
+        let file_name = url.rsplit('/').next().unwrap_or_default();
+        if let Some(digest) = PhpCatalogue::sha256(file_name).or_else(|| ServiceCatalogue::sha256(file_name)) {
+            return Some(digest.trim().to_lowercase());
+        }
+
+        match Self::fetch_published(url).await {
+            Ok(digest) => digest,
+            Err(e) => {
+                log::warn!("Could not fetch published checksum for {}: {}", url, e);
+                None
+            }
+        }
+    }
+
+    async fn fetch_published(url: &str) -> Result<Option<String>, reqwest::Error> {
+        let Ok(parsed) = reqwest::Url::parse(url) else {
+            return Ok(None);
+        };
+        let host = parsed.host_str().unwrap_or_default();
+        let file_name = parsed
+            .path_segments()
+            .and_then(|mut s| s.next_back())
+            .unwrap_or_default()
+            .to_string();
+        let dir_url = url.rsplit_once('/').map(|(dir, _)| dir).unwrap_or(url);
+
+        let client = reqwest::Client::builder()
+            .user_agent("LokcalDev/0.1")
+            .timeout(std::time::Duration::from_secs(15))
+            .build()?;
+
+        let sums_url = match host {
+            "files.phpmyadmin.net" | "getcomposer.org" => format!("{}.sha256", url),
+            "archive.mariadb.org" => format!("{}/sha256sums.txt", dir_url),
+            "windows.php.net" => format!("{}/sha256sum.txt", dir_url),
+            "github.com" if parsed.path().contains("/releases/download/") => {
+                return Self::fetch_github_asset_digest(&client, parsed.path(), &file_name).await;
+            }
+            _ => return Ok(None),
+        };
+
+        let response = client.get(&sums_url).send().await?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let body = response.text().await?;
+        Ok(Self::find_in_sums(&body, &file_name))
+    }
+
+    /// GitHub exposes a `digest` ("sha256:<hex>") on release assets via the REST API.
+    async fn fetch_github_asset_digest(
+        client: &reqwest::Client,
+        path: &str,
+        file_name: &str,
+    ) -> Result<Option<String>, reqwest::Error> {
+        // /<owner>/<repo>/releases/download/<tag>/<asset>
+        let parts: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        if parts.len() < 6 {
+            return Ok(None);
+        }
+        let api_url = format!(
+            "https://api.github.com/repos/{}/{}/releases/tags/{}",
+            parts[0], parts[1], parts[4]
+        );
+        let response = client.get(&api_url).send().await?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let body: serde_json::Value = response.json().await?;
+        let digest = body["assets"]
+            .as_array()
+            .and_then(|assets| assets.iter().find(|a| a["name"].as_str() == Some(file_name)))
+            .and_then(|asset| asset["digest"].as_str())
+            .and_then(|d| d.strip_prefix("sha256:"))
+            .map(|d| d.to_lowercase());
+        Ok(digest)
+    }
+
+    /// Parse `sha256sum`-style output ("<hex>  [*./]name") or a bare digest file.
+    fn find_in_sums(body: &str, file_name: &str) -> Option<String> {
+        let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+
+        for line in body.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(digest), name) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if !is_digest(digest) {
+                continue;
+            }
+            match name {
+                None => return Some(digest.to_lowercase()),
+                Some(name) => {
+                    let name = name.trim_start_matches('*');
+                    if name.rsplit('/').next() == Some(file_name) {
+                        return Some(digest.to_lowercase());
+                    }
+                }
+            }
+        }
+        None
+    }
+}
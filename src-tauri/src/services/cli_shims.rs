@@ -0,0 +1,344 @@
+use crate::config::app_config::AppConfig;
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::composer_manager::ComposerManager;
+use crate::services::mariadb_manager::MariaDbManager;
+use crate::services::php_catalogue::PhpCatalogue;
+use crate::services::php_manager::PhpManager;
+use crate::services::site_manager::SiteManager;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const SHIMS: [&str; 4] = ["php", "composer", "mysql", "mysqldump"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShimsInfo {
+    pub installed: bool,
+    pub bin_dir: String,
+    /// Line to add to the shell profile (PowerShell on Windows)
+    pub path_line: String,
+    /// The bin dir is already on the PATH the app was started with
+    pub on_path: bool,
+    pub shims: Vec<String>,
+}
+
+/// Command-line shims in `<data dir>/bin` for the managed PHP, Composer and
+/// MariaDB clients.
+///
+/// `php` and `composer` pick the PHP version from the working directory: the
+/// site whose document root contains it, else the nearest `.php-version`
+/// file, else `default_php_version`. Site roots and the default are baked in
+/// when the shims are written, so they are rewritten whenever those change.
+pub struct CliShims;
+
+impl CliShims {
+    #[cfg(not(target_os = "windows"))]
+    fn shim_path(name: &str) -> PathBuf {
+        paths::get_bin_dir().join(name)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn shim_path(name: &str) -> PathBuf {
+        paths::get_bin_dir().join(format!("{}.cmd", name))
+    }
+
+    pub fn is_installed() -> bool {
+        Self::shim_path("php").exists()
+    }
+
+    pub fn get_info() -> ShimsInfo {
+        let bin_dir = paths::get_bin_dir();
+        let on_path = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).any(|p| p == bin_dir))
+            .unwrap_or(false);
+        ShimsInfo {
+            installed: Self::is_installed(),
+            bin_dir: bin_dir.to_string_lossy().to_string(),
+            path_line: Self::path_line(&bin_dir.to_string_lossy()),
+            on_path,
+            shims: SHIMS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn path_line(bin_dir: &str) -> String {
+        format!("export PATH=\"{}:$PATH\"", bin_dir)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn path_line(bin_dir: &str) -> String {
+        format!(
+            "[Environment]::SetEnvironmentVariable(\"Path\", \"{};\" + [Environment]::GetEnvironmentVariable(\"Path\", \"User\"), \"User\")",
+            bin_dir
+        )
+    }
+
+    /// Write (or rewrite) every shim.
+    pub fn install() -> Result<ShimsInfo, AppError> {
+        let bin_dir = paths::get_bin_dir();
+        std::fs::create_dir_all(&bin_dir)?;
+
+        let config = AppConfig::load();
+        let mut roots: Vec<(String, String)> = SiteManager::list()?
+            .into_iter()
+            .map(|site| {
+                let root = std::fs::canonicalize(&site.document_root)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or(site.document_root);
+                (root.trim_end_matches(['/', '\\']).to_string(), site.php_version)
+            })
+            .filter(|(root, _)| !root.is_empty())
+            .collect();
+        // Nested roots must win over their parents
+        roots.sort_by_key(|(root, _)| std::cmp::Reverse(root.len()));
+
+        let resolve = Self::resolve_version(&roots, &config.default_php_version);
+        let mysql = MariaDbManager::get_mysql_binary();
+        let mysqldump = mysql.with_file_name(if cfg!(target_os = "windows") {
+            "mysqldump.exe"
+        } else {
+            "mysqldump"
+        });
+
+        Self::write_shim("php", &Self::php_shim(&resolve, None))?;
+        Self::write_shim("composer", &Self::php_shim(&resolve, Some(&ComposerManager::get_phar_path())))?;
+        Self::write_shim("mysql", &Self::client_shim(&mysql))?;
+        Self::write_shim("mysqldump", &Self::client_shim(&mysqldump))?;
+
+        log::info!("Wrote CLI shims to {}", bin_dir.display());
+        Ok(Self::get_info())
+    }
+
+    pub fn remove() -> Result<ShimsInfo, AppError> {
+        for name in SHIMS {
+            let path = Self::shim_path(name);
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        log::info!("Removed CLI shims from {}", paths::get_bin_dir().display());
+        Ok(Self::get_info())
+    }
+
+    /// Rewrite the shims if they are in use, after sites or the default PHP
+    /// version changed. Failures are only logged.
+    pub fn refresh() {
+        if !Self::is_installed() {
+            return;
+        }
+        if let Err(e) = Self::install() {
+            log::warn!("Could not refresh CLI shims: {}", e);
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn write_shim(name: &str, content: &str) -> Result<(), AppError> {
+        use std::os::unix::fs::PermissionsExt;
+        let path = Self::shim_path(name);
+        std::fs::write(&path, content)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn write_shim(name: &str, content: &str) -> Result<(), AppError> {
+        std::fs::write(Self::shim_path(name), content.replace('\n', "\r\n"))?;
+        Ok(())
+    }
+
+    // --- POSIX sh ---
+
+    #[cfg(not(target_os = "windows"))]
+    fn quote(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+
+    /// Sets `$version`, `$php` and `$ini` or exits.
+    #[cfg(not(target_os = "windows"))]
+    fn resolve_version(roots: &[(String, String)], default_version: &str) -> String {
+        let mut script = String::from("version=\"\"\ndir=$(pwd -P)\ncase \"$dir/\" in\n");
+        for (root, version) in roots {
+            script.push_str(&format!("  {}/*) version={} ;;\n", Self::quote(root), Self::quote(version)));
+        }
+        script.push_str(
+            "esac\n\
+             if [ -z \"$version\" ]; then\n\
+             \x20 d=\"$dir\"\n\
+             \x20 while :; do\n\
+             \x20   if [ -f \"$d/.php-version\" ]; then\n\
+             \x20     version=$(head -n 1 \"$d/.php-version\" | tr -d ' \\t\\r' | cut -d. -f1,2)\n\
+             \x20     break\n\
+             \x20   fi\n\
+             \x20   [ \"$d\" = \"/\" ] && break\n\
+             \x20   d=$(dirname \"$d\")\n\
+             \x20 done\n\
+             fi\n",
+        );
+        script.push_str(&format!("[ -n \"$version\" ] || version={}\n", Self::quote(default_version)));
+
+        script.push_str("case \"$version\" in\n");
+        for version in PhpCatalogue::versions() {
+            script.push_str(&format!(
+                "  {}) php={}; ini={} ;;\n",
+                Self::quote(&version),
+                Self::quote(&PhpManager::get_php_binary(&version).to_string_lossy()),
+                Self::quote(&PhpManager::get_php_ini_path(&version).to_string_lossy()),
+            ));
+        }
+        script.push_str(
+            "  *) echo \"lokcaldev: PHP $version is not available\" >&2; exit 1 ;;\n\
+             esac\n\
+             if [ ! -x \"$php\" ]; then\n\
+             \x20 echo \"lokcaldev: PHP $version is not installed\" >&2\n\
+             \x20 exit 1\n\
+             fi\n",
+        );
+        script
+    }
+
+    /// Static builds have no compiled-in ini path, as in `PhpManager::php_command`.
+    #[cfg(not(target_os = "windows"))]
+    fn php_args() -> &'static str {
+        if cfg!(target_os = "macos") {
+            ""
+        } else {
+            " -c \"$ini\""
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn php_shim(resolve: &str, phar: Option<&std::path::Path>) -> String {
+        let mut script = format!("#!/bin/sh\n# Generated by LokcalDev; rewritten when sites or settings change.\n{}", resolve);
+        match phar {
+            Some(phar) => {
+                let phar = Self::quote(&phar.to_string_lossy());
+                script.push_str(&format!(
+                    "if [ ! -f {phar} ]; then\n\
+                     \x20 echo \"lokcaldev: Composer is not installed\" >&2\n\
+                     \x20 exit 1\n\
+                     fi\n\
+                     exec \"$php\"{} {phar} \"$@\"\n",
+                    Self::php_args()
+                ));
+            }
+            None => script.push_str(&format!("exec \"$php\"{} \"$@\"\n", Self::php_args())),
+        }
+        script
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn client_shim(binary: &std::path::Path) -> String {
+        let args: Vec<String> = MariaDbManager::get_connection_args()
+            .iter()
+            .map(|a| Self::quote(a))
+            .collect();
+        format!(
+            "#!/bin/sh\n\
+             # Generated by LokcalDev.\n\
+             bin={}\n\
+             if [ ! -x \"$bin\" ]; then\n\
+             \x20 echo \"lokcaldev: MariaDB is not installed\" >&2\n\
+             \x20 exit 1\n\
+             fi\n\
+             exec \"$bin\" {} \"$@\"\n",
+            Self::quote(&binary.to_string_lossy()),
+            args.join(" ")
+        )
+    }
+
+    // --- Windows batch ---
+
+    /// Sets `LD_VERSION` and `LD_PHP` or exits.
+    #[cfg(target_os = "windows")]
+    fn resolve_version(roots: &[(String, String)], default_version: &str) -> String {
+        let mut script = String::from("set \"LD_VERSION=\"\nset \"LD_DIR=%CD%\\\"\n");
+        for (root, version) in roots {
+            let prefix = format!("{}\\", root);
+            script.push_str(&format!(
+                "if not defined LD_VERSION if /I \"!LD_DIR:~0,{}!\"==\"{}\" set \"LD_VERSION={}\"\n",
+                prefix.encode_utf16().count(),
+                prefix,
+                version
+            ));
+        }
+        script.push_str(
+            "if not defined LD_VERSION (\n\
+             \x20 set \"LD_SEARCH=%CD%\"\n\
+             \x20 call :find_php_version\n\
+             )\n",
+        );
+        script.push_str(&format!("if not defined LD_VERSION set \"LD_VERSION={}\"\n", default_version));
+        script.push_str("set \"LD_PHP=\"\n");
+        for version in PhpCatalogue::versions() {
+            script.push_str(&format!(
+                "if \"!LD_VERSION!\"==\"{}\" set \"LD_PHP={}\"\n",
+                version,
+                PhpManager::get_php_binary(&version).display()
+            ));
+        }
+        script.push_str(
+            "if not defined LD_PHP (\n\
+             \x20 echo lokcaldev: PHP !LD_VERSION! is not available 1>&2\n\
+             \x20 exit /b 1\n\
+             )\n\
+             if not exist \"!LD_PHP!\" (\n\
+             \x20 echo lokcaldev: PHP !LD_VERSION! is not installed 1>&2\n\
+             \x20 exit /b 1\n\
+             )\n",
+        );
+        script
+    }
+
+    /// Walks up from `LD_SEARCH` looking for `.php-version`.
+    #[cfg(target_os = "windows")]
+    const FIND_PHP_VERSION: &'static str = ":find_php_version\n\
+        if exist \"!LD_SEARCH!\\.php-version\" (\n\
+        \x20 set /p LD_RAW=<\"!LD_SEARCH!\\.php-version\"\n\
+        \x20 for /f \"tokens=1,2 delims=. \" %%a in (\"!LD_RAW!\") do set \"LD_VERSION=%%a.%%b\"\n\
+        \x20 goto :eof\n\
+        )\n\
+        for %%p in (\"!LD_SEARCH!\\..\") do set \"LD_PARENT=%%~fp\"\n\
+        if /I \"!LD_PARENT!\"==\"!LD_SEARCH!\" goto :eof\n\
+        set \"LD_SEARCH=!LD_PARENT!\"\n\
+        goto :find_php_version\n";
+
+    #[cfg(target_os = "windows")]
+    fn php_shim(resolve: &str, phar: Option<&std::path::Path>) -> String {
+        let mut script = format!(
+            "@echo off\nrem Generated by LokcalDev; rewritten when sites or settings change.\nsetlocal EnableDelayedExpansion\n{}",
+            resolve
+        );
+        // `endlocal` first: with delayed expansion on, every '!' in %* is eaten.
+        // %LD_PHP% is expanded while the line is read, before it runs.
+        match phar {
+            Some(phar) => script.push_str(&format!(
+                "if not exist \"{phar}\" (\n\
+                 \x20 echo lokcaldev: Composer is not installed 1>&2\n\
+                 \x20 exit /b 1\n\
+                 )\n\
+                 endlocal & \"%LD_PHP%\" \"{phar}\" %*\n",
+                phar = phar.display()
+            )),
+            None => script.push_str("endlocal & \"%LD_PHP%\" %*\n"),
+        }
+        script.push_str("exit /b %ERRORLEVEL%\n\n");
+        script.push_str(Self::FIND_PHP_VERSION);
+        script
+    }
+
+    #[cfg(target_os = "windows")]
+    fn client_shim(binary: &std::path::Path) -> String {
+        format!(
+            "@echo off\n\
+             rem Generated by LokcalDev.\n\
+             if not exist \"{bin}\" (\n\
+             \x20 echo lokcaldev: MariaDB is not installed 1>&2\n\
+             \x20 exit /b 1\n\
+             )\n\
+             \"{bin}\" {} %*\n",
+            MariaDbManager::get_connection_args().join(" "),
+            bin = binary.display()
+        )
+    }
+}
@@ -0,0 +1,240 @@
+use crate::config::app_config::AppConfig;
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::download_manager::DownloadManager;
+use crate::services::php_manager::PhpManager;
+use crate::services::site_manager::{Site, SiteManager};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter};
+
+const PHAR_URL: &str = "https://getcomposer.org/download/latest-stable/composer.phar";
+
+/// Lines of output kept for the error message of a failed run.
+const ERROR_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposerInfo {
+    pub installed: bool,
+    pub version: Option<String>,
+    pub path: String,
+}
+
+/// One line of a running Composer command (`composer-output` event).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposerOutput {
+    pub site_id: String,
+    /// "stdout" or "stderr"; Composer reports progress on stderr
+    pub stream: String,
+    pub line: String,
+}
+
+/// A managed composer.phar, run with the PHP version of the site it is
+/// used for.
+pub struct ComposerManager;
+
+impl ComposerManager {
+    pub fn get_phar_path() -> PathBuf {
+        paths::get_composer_dir().join("composer.phar")
+    }
+
+    pub fn is_installed() -> bool {
+        Self::get_phar_path().exists()
+    }
+
+    pub fn get_info() -> ComposerInfo {
+        let installed = Self::is_installed();
+        ComposerInfo {
+            installed,
+            version: if installed { Self::detect_version() } else { None },
+            path: Self::get_phar_path().to_string_lossy().to_string(),
+        }
+    }
+
+    /// "Composer version 2.8.4 2024-12-11 10:57:47" -> "2.8.4", run with
+    /// the default PHP version.
+    fn detect_version() -> Option<String> {
+        let version = AppConfig::load().default_php_version;
+        if !PhpManager::get_php_binary(&version).exists() {
+            return None;
+        }
+        let output = PhpManager::php_command(&version)
+            .arg(Self::get_phar_path())
+            .args(["--version", "--no-ansi"])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .skip_while(|word| *word != "version")
+            .nth(1)
+            .map(|v| v.to_string())
+    }
+
+    /// Download the latest stable composer.phar, replacing an existing one
+    /// only once the new copy is complete.
+    pub async fn install(app: &AppHandle, id: &str) -> Result<ComposerInfo, AppError> {
+        let phar = Self::get_phar_path();
+        let download = phar.with_extension("phar.download");
+        DownloadManager::download_executable(app, id, PHAR_URL, &download).await?;
+        std::fs::rename(&download, &phar)?;
+        log::info!("Installed composer.phar to {}", phar.display());
+        Ok(Self::get_info())
+    }
+
+    pub async fn ensure_installed(app: &AppHandle, id: &str) -> Result<(), AppError> {
+        if !Self::is_installed() {
+            Self::install(app, id).await?;
+        }
+        Ok(())
+    }
+
+    /// `composer install`, `update [packages]` or `require packages` in the
+    /// site's document root.
+    pub async fn run(
+        app: &AppHandle,
+        site_id: &str,
+        action: &str,
+        packages: &[String],
+        dev: bool,
+    ) -> Result<(), AppError> {
+        let mut args = vec![action.to_string()];
+        match action {
+            "install" => {}
+            "update" => args.extend(packages.iter().cloned()),
+            "require" => {
+                if packages.is_empty() {
+                    return Err(AppError::Config("composer require needs at least one package".to_string()));
+                }
+                args.extend(packages.iter().cloned());
+                if dev {
+                    args.push("--dev".to_string());
+                }
+            }
+            _ => {
+                return Err(AppError::Config(format!(
+                    "Unsupported composer command '{}' (install, update or require)",
+                    action
+                )))
+            }
+        }
+        if let Some(invalid) = packages.iter().find(|p| !Self::is_valid_package(p)) {
+            return Err(AppError::Config(format!("Invalid package: '{}'", invalid)));
+        }
+
+        let site = SiteManager::get(site_id)?;
+        Self::ensure_installed(app, &format!("composer-{}", site_id)).await?;
+        Self::execute(app, &site, args).await
+    }
+
+    /// `vendor/name` with an optional `:constraint`. Anything that could be
+    /// read as an option is rejected.
+    fn is_valid_package(package: &str) -> bool {
+        let (name, constraint) = package.split_once(':').unwrap_or((package, ""));
+        let valid_part = |part: &str| {
+            !part.is_empty()
+                && !part.starts_with(['-', '.'])
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.'))
+        };
+        let valid_name = name
+            .split_once('/')
+            .is_some_and(|(vendor, project)| valid_part(vendor) && valid_part(project));
+        let valid_constraint = !constraint.starts_with('-')
+            && constraint
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || " ^~*.,|<>=!@-".contains(c));
+        valid_name && valid_constraint
+    }
+
+    /// Run composer.phar with the site's PHP, emitting every output line as
+    /// a `composer-output` event.
+    pub(crate) async fn execute(app: &AppHandle, site: &Site, args: Vec<String>) -> Result<(), AppError> {
+        if !PhpManager::get_php_binary(&site.php_version).exists() {
+            return Err(AppError::NotFound(format!(
+                "PHP {} is not installed; Composer runs with the site's PHP version",
+                site.php_version
+            )));
+        }
+
+        let app = app.clone();
+        let site = site.clone();
+        tauri::async_runtime::spawn_blocking(move || Self::execute_blocking(&app, &site, &args))
+            .await
+            .map_err(|e| AppError::Process(format!("Composer task failed: {}", e)))?
+    }
+
+    fn execute_blocking(app: &AppHandle, site: &Site, args: &[String]) -> Result<(), AppError> {
+        log::info!(
+            "Running composer {} for {} with PHP {}",
+            args.join(" "),
+            site.domain,
+            site.php_version
+        );
+        let mut child = PhpManager::php_command(&site.php_version)
+            .arg(Self::get_phar_path())
+            .args(args)
+            .args(["--no-interaction", "--no-ansi"])
+            .current_dir(&site.document_root)
+            .env("COMPOSER_NO_INTERACTION", "1")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::Process(format!("Failed to start Composer: {}", e)))?;
+
+        let emit = {
+            let app = app.clone();
+            let site_id = site.id.clone();
+            move |stream: &str, line: &str| {
+                let _ = app.emit(
+                    "composer-output",
+                    ComposerOutput {
+                        site_id: site_id.clone(),
+                        stream: stream.to_string(),
+                        line: line.to_string(),
+                    },
+                );
+            }
+        };
+
+        let stderr = child.stderr.take();
+        let stderr_emit = emit.clone();
+        let stderr_reader = std::thread::spawn(move || {
+            let mut lines = Vec::new();
+            if let Some(stderr) = stderr {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    stderr_emit("stderr", &line);
+                    lines.push(line);
+                }
+            }
+            lines
+        });
+
+        let mut output = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                emit("stdout", &line);
+                output.push(line);
+            }
+        }
+        output.extend(stderr_reader.join().unwrap_or_default());
+
+        let status = child.wait()?;
+        if !status.success() {
+            let tail = output[output.len().saturating_sub(ERROR_TAIL_LINES)..].join("\n");
+            return Err(AppError::Process(format!(
+                "composer {} failed ({}):\n{}",
+                args.first().map(String::as_str).unwrap_or_default(),
+                status,
+                tail
+            )));
+        }
+        log::info!("composer {} finished for {}", args.join(" "), site.domain);
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
+use crate::config::app_config::AppConfig;
+use crate::config::paths;
 use crate::error::AppError;
-use flate2::read::GzDecoder;
+use crate::services::artifact_cache::{ArtifactCache, CachedArtifact};
+use crate::services::checksum_manifest::ChecksumManifest;
+use crate::state::AppState;
 use futures_util::StreamExt;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use tar::Archive;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct DownloadProgress {
@@ -15,6 +19,39 @@ pub struct DownloadProgress {
     pub percent: f64,
     pub status: String,
     pub message: Option<String>,
+    /// Checksum result once known: "verified", "unverified" or "mismatch".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<String>,
+}
+
+const MAX_FETCH_ATTEMPTS: u32 = 5;
+const MAX_JOB_HISTORY: usize = 100;
+
+/// An install/download job tracked by the queue and persisted to history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadJob {
+    pub id: String,
+    pub label: String,
+    /// "queued", "running", "completed", "failed" or "cancelled"
+    pub status: String,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+}
+
+impl DownloadJob {
+    fn is_active(&self) -> bool {
+        self.status == "queued" || self.status == "running"
+    }
+}
+
+/// Outcome of a single HTTP attempt: network hiccups are retried and
+/// resumed, everything else (4xx, disk errors, cancellation) is final.
+enum FetchError {
+    Retryable(AppError),
+    Fatal(AppError),
 }
 
 pub struct DownloadManager;
@@ -28,86 +65,373 @@ impl DownloadManager {
             percent,
             status: status.to_string(),
             message,
+            verification: None,
         }
     }
 
     fn emit_progress(app: &AppHandle, id: &str, downloaded: u64, total: Option<u64>, percent: f64, status: &str, message: Option<String>) {
         let _ = app.emit(
             "download-progress",
-            DownloadProgress {
-                id: id.to_string(),
-                downloaded,
-                total,
-                percent,
-                status: status.to_string(),
-                message,
-            },
+            Self::progress(id, downloaded, total, percent, status, message),
         );
     }
 
+    fn emit_verification(app: &AppHandle, id: &str, size: u64, status: &str, verification: &str, message: String) {
+        let mut progress = Self::progress(id, size, Some(size), 100.0, status, Some(message));
+        progress.verification = Some(verification.to_string());
+        let _ = app.emit("download-progress", progress);
+    }
+
+    /// Download `url` to `dest`, serving it from the local artifact cache or a
+    /// configured mirror when possible and falling back to the upstream URL.
+    /// The file is checked against the expected SHA-256 before it is returned;
+    /// on mismatch it is deleted and never reaches the caller.
     pub async fn download_file(
         app: &AppHandle,
         id: &str,
         url: &str,
         dest: &Path,
     ) -> Result<PathBuf, AppError> {
-        log::info!("Downloading {} to {}", url, dest.display());
+        Self::download(app, id, url, dest, false).await
+    }
 
-        // Emit "starting" so the UI shows immediately
-        Self::emit_progress(app, id, 0, None, 0.0, "downloading", Some("Connecting...".to_string()));
+    /// `download_file` for binaries (PHP, Nginx, MariaDB, mkcert, extensions,
+    /// Composer), which are refused without a known SHA-256 when
+    /// `require_verified_binaries` is set.
+    pub async fn download_executable(
+        app: &AppHandle,
+        id: &str,
+        url: &str,
+        dest: &Path,
+    ) -> Result<PathBuf, AppError> {
+        Self::download(app, id, url, dest, true).await
+    }
+
+    async fn download(
+        app: &AppHandle,
+        id: &str,
+        url: &str,
+        dest: &Path,
+        executable: bool,
+    ) -> Result<PathBuf, AppError> {
+        // Reuse the job's cancellation token if this download runs inside one,
+        // otherwise register our own so `download_cancel` can abort it
+        let state = app.state::<AppState>();
+        let (token, owned) = match state.download_cancel.lock() {
+            Ok(mut tokens) => match tokens.get(id) {
+                Some(token) => (token.clone(), false),
+                None => {
+                    let token = CancellationToken::new();
+                    tokens.insert(id.to_string(), token.clone());
+                    (token, true)
+                }
+            },
+            Err(_) => (CancellationToken::new(), false),
+        };
+
+        let result = Self::download_file_inner(app, id, url, dest, &token, executable).await;
+
+        if owned {
+            if let Ok(mut tokens) = state.download_cancel.lock() {
+                tokens.remove(id);
+            }
+        }
+
+        if token.is_cancelled() {
+            let _ = std::fs::remove_file(Self::part_path(dest));
+            let _ = std::fs::remove_file(dest);
+            Self::emit_progress(app, id, 0, None, 0.0, "cancelled", Some("Download cancelled".to_string()));
+            return Err(Self::cancelled_error());
+        }
+        result
+    }
+
+    async fn download_file_inner(
+        app: &AppHandle,
+        id: &str,
+        url: &str,
+        dest: &Path,
+        token: &CancellationToken,
+        executable: bool,
+    ) -> Result<PathBuf, AppError> {
+        let config = AppConfig::load();
+        let require_verified =
+            config.require_verified_downloads || (executable && config.require_verified_binaries);
 
         if let Some(parent) = dest.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
+        let expected = ChecksumManifest::expected_sha256(url).await;
+
+        // 1. Local cache hit — no network needed. Without a known digest the
+        // URL may point at a moving target (e.g. latest.zip), so such copies
+        // are only a fallback for when no source can be reached.
+        let mut stale = None;
+        if config.download_cache_enabled {
+            if let Some(cached) = ArtifactCache::lookup(url) {
+                if expected.is_some() || cached.0.verified {
+                    if Self::use_cached(app, id, url, dest, &cached, expected.as_deref(), require_verified)? {
+                        return Ok(dest.to_path_buf());
+                    }
+                } else {
+                    stale = Some(cached);
+                }
+            }
+        }
+
+        // 2. Mirrors in configured order, then upstream
+        let mut candidates = ArtifactCache::mirror_candidates(url, &config.download_mirrors);
+        candidates.push(url.to_string());
+
+        let mut last_error = None;
+        for candidate in &candidates {
+            if token.is_cancelled() {
+                return Err(Self::cancelled_error());
+            }
+            let result = match ArtifactCache::local_path(candidate) {
+                Some(local) => Self::copy_local(app, id, &local, dest),
+                None => Self::fetch(app, id, candidate, dest, token).await,
+            };
+            match result {
+                Ok(()) => match Self::verify(app, id, url, dest, "", expected.as_deref(), require_verified) {
+                    Ok((sha256, verified)) => {
+                        if config.download_cache_enabled {
+                            if let Err(e) = ArtifactCache::store(url, dest, &sha256, verified) {
+                                log::warn!("Could not cache {}: {}", url, e);
+                            }
+                        }
+                        log::info!("Download complete: {}", dest.display());
+                        return Ok(dest.to_path_buf());
+                    }
+                    // A bad copy on one mirror should not stop us trying the next source
+                    Err(e) => last_error = Some(e),
+                },
+                Err(e) => {
+                    if token.is_cancelled() {
+                        return Err(e);
+                    }
+                    log::warn!("Download from {} failed: {}", candidate, e);
+                    let _ = std::fs::remove_file(dest);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if let Some(cached) = stale {
+            log::warn!("No source reachable for {}, falling back to the cached copy", url);
+            if Self::use_cached(app, id, url, dest, &cached, None, require_verified)? {
+                return Ok(dest.to_path_buf());
+            }
+        }
+
+        let err = last_error.unwrap_or_else(|| AppError::Download(format!("No source available for {}", url)));
+        Self::emit_progress(app, id, 0, None, 0.0, "failed", Some(err.to_string()));
+        Err(err)
+    }
+
+    /// Copy a cache blob to `dest` and verify it. A rejected blob is evicted
+    /// and `false` returned so the caller downloads a fresh copy.
+    fn use_cached(
+        app: &AppHandle,
+        id: &str,
+        url: &str,
+        dest: &Path,
+        (artifact, blob): &(CachedArtifact, PathBuf),
+        expected: Option<&str>,
+        require_verified: bool,
+    ) -> Result<bool, AppError> {
+        log::info!("Using cached {} for {}", artifact.sha256, url);
+        std::fs::copy(blob, dest)?;
+        // A previously verified blob stays trusted offline as long as it is intact
+        let expected = expected.map(|e| e.to_string()).or_else(|| artifact.verified.then(|| artifact.sha256.clone()));
+        match Self::verify(app, id, url, dest, &artifact.sha256, expected.as_deref(), require_verified) {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                log::warn!("Cached copy of {} rejected: {}", url, e);
+                let _ = ArtifactCache::evict(url);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Hash `file` and compare it with `expected` (and with `cached_digest`, the
+    /// content address of a cache blob, when non-empty). Deletes the file and
+    /// fails on mismatch. Returns the digest and whether it was checked against a known one.
+    fn verify(
+        app: &AppHandle,
+        id: &str,
+        url: &str,
+        file: &Path,
+        cached_digest: &str,
+        expected: Option<&str>,
+        require_verified: bool,
+    ) -> Result<(String, bool), AppError> {
+        let size = std::fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+        Self::emit_progress(app, id, size, Some(size), 100.0, "verifying", Some("Verifying checksum...".to_string()));
+
+        let actual = ArtifactCache::hash_file(file)?;
+
+        let mismatch = if !cached_digest.is_empty() && actual != cached_digest {
+            Some(format!("cached copy is corrupt (expected {}, got {})", cached_digest, actual))
+        } else {
+            match expected {
+                Some(exp) if exp != actual => Some(format!("expected {}, got {}", exp, actual)),
+                _ => None,
+            }
+        };
+
+        if let Some(detail) = mismatch {
+            let _ = std::fs::remove_file(file);
+            let msg = format!("Checksum mismatch for {}: {}", url, detail);
+            log::error!("{}", msg);
+            Self::emit_verification(app, id, size, "failed", "mismatch", msg.clone());
+            return Err(AppError::Download(msg));
+        }
+
+        if expected.is_none() {
+            if require_verified {
+                let _ = std::fs::remove_file(file);
+                let msg = format!(
+                    "No known checksum for {}. Add its SHA-256 to config/checksums.json or turn off checksum enforcement in Settings",
+                    url
+                );
+                log::error!("{}", msg);
+                Self::emit_verification(app, id, size, "failed", "unverified", msg.clone());
+                return Err(AppError::Download(msg));
+            }
+            log::warn!("No known checksum for {} (sha256 {})", url, actual);
+            Self::emit_verification(app, id, size, "completed", "unverified", "Download complete (checksum not verified)".to_string());
+            return Ok((actual, false));
+        }
+
+        log::info!("Verified {} (sha256 {})", url, actual);
+        Self::emit_verification(app, id, size, "completed", "verified", "Download complete (checksum verified)".to_string());
+        Ok((actual, true))
+    }
+
+    /// Copy an artifact from a pre-seeded local mirror folder.
+    fn copy_local(app: &AppHandle, id: &str, source: &Path, dest: &Path) -> Result<(), AppError> {
+        if !source.is_file() {
+            return Err(AppError::NotFound(format!("{} not found in local mirror", source.display())));
+        }
+        log::info!("Copying {} to {}", source.display(), dest.display());
+        let size = std::fs::copy(source, dest)?;
+        Self::emit_progress(app, id, size, Some(size), 100.0, "downloading", Some("Copied from local mirror".to_string()));
+        Ok(())
+    }
+
+    fn part_path(dest: &Path) -> PathBuf {
+        let mut name = dest.file_name().unwrap_or_default().to_os_string();
+        name.push(".part");
+        dest.with_file_name(name)
+    }
+
+    /// Fetch `url` into `<dest>.part`, resuming with HTTP Range after network
+    /// errors (exponential backoff), then rename it to `dest`.
+    async fn fetch(app: &AppHandle, id: &str, url: &str, dest: &Path, token: &CancellationToken) -> Result<(), AppError> {
+        log::info!("Downloading {} to {}", url, dest.display());
+
+        // Emit "starting" so the UI shows immediately
+        Self::emit_progress(app, id, 0, None, 0.0, "downloading", Some("Connecting...".to_string()));
+
         let client = reqwest::Client::builder()
             .user_agent("LokcalDev/0.1")
+            .connect_timeout(std::time::Duration::from_secs(30))
             .build()
-            .map_err(|e| {
-                let msg = format!("Failed to create HTTP client: {}", e);
-                Self::emit_progress(app, id, 0, None, 0.0, "failed", Some(msg.clone()));
-                AppError::Download(msg)
-            })?;
-
-        let response = client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| {
-                let msg = format!("HTTP request failed: {}", e);
-                log::error!("{}", msg);
-                Self::emit_progress(app, id, 0, None, 0.0, "failed", Some(msg.clone()));
-                AppError::Download(msg)
-            })?;
+            .map_err(|e| AppError::Download(format!("Failed to create HTTP client: {}", e)))?;
 
-        if !response.status().is_success() {
-            let msg = format!("HTTP {} for {}", response.status(), url);
-            log::error!("{}", msg);
-            Self::emit_progress(app, id, 0, None, 0.0, "failed", Some(msg.clone()));
-            return Err(AppError::Download(msg));
+        let part = Self::part_path(dest);
+        let mut attempt = 1;
+        loop {
+            match Self::fetch_attempt(app, id, &client, url, &part, token).await {
+                Ok(()) => {
+                    std::fs::rename(&part, dest)?;
+                    return Ok(());
+                }
+                Err(FetchError::Retryable(e)) if attempt < MAX_FETCH_ATTEMPTS => {
+                    let delay = std::time::Duration::from_secs(1 << (attempt - 1));
+                    log::warn!("{} (attempt {}/{}), retrying in {:?}", e, attempt, MAX_FETCH_ATTEMPTS, delay);
+                    let downloaded = std::fs::metadata(&part).map(|m| m.len()).unwrap_or(0);
+                    Self::emit_progress(
+                        app, id, downloaded, None, 0.0, "downloading",
+                        Some(format!("Connection lost, retrying in {}s ({}/{})...", delay.as_secs(), attempt, MAX_FETCH_ATTEMPTS)),
+                    );
+                    tokio::select! {
+                        _ = token.cancelled() => return Err(Self::cancelled_error()),
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+                    attempt += 1;
+                }
+                Err(FetchError::Retryable(e)) | Err(FetchError::Fatal(e)) => return Err(e),
+            }
+        }
+    }
+
+    async fn fetch_attempt(
+        app: &AppHandle,
+        id: &str,
+        client: &reqwest::Client,
+        url: &str,
+        part: &Path,
+        token: &CancellationToken,
+    ) -> Result<(), FetchError> {
+        let resume_from = std::fs::metadata(part).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = client.get(url);
+        if resume_from > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        }
+
+        let response = tokio::select! {
+            _ = token.cancelled() => return Err(FetchError::Fatal(Self::cancelled_error())),
+            response = request.send() => response.map_err(|e| {
+                FetchError::Retryable(AppError::Download(format!("HTTP request failed: {}", e)))
+            })?,
+        };
+
+        let status = response.status();
+        if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // Stale or oversized .part — start over
+            let _ = std::fs::remove_file(part);
+            return Err(FetchError::Retryable(AppError::Download(format!("HTTP {} for {}", status, url))));
+        }
+        if status.is_server_error() {
+            return Err(FetchError::Retryable(AppError::Download(format!("HTTP {} for {}", status, url))));
+        }
+        if !status.is_success() {
+            return Err(FetchError::Fatal(AppError::Download(format!("HTTP {} for {}", status, url))));
         }
 
-        let total = response.content_length();
-        let mut downloaded: u64 = 0;
-        let mut file =
-            std::fs::File::create(dest).map_err(|e| {
-                let msg = format!("Failed to create file: {}", e);
-                Self::emit_progress(app, id, 0, None, 0.0, "failed", Some(msg.clone()));
-                AppError::Download(msg)
-            })?;
+        // Server may ignore Range and send the whole body (200) — restart then
+        let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+        let mut downloaded: u64 = if resumed { resume_from } else { 0 };
+        let total = response.content_length().map(|len| len + downloaded);
+        let file = if resumed {
+            std::fs::OpenOptions::new().append(true).open(part)
+        } else {
+            std::fs::File::create(part)
+        };
+        let mut file = file
+            .map_err(|e| FetchError::Fatal(AppError::Download(format!("Failed to create file: {}", e))))?;
 
-        Self::emit_progress(app, id, 0, total, 0.0, "downloading", Some("Downloading...".to_string()));
+        let message = if resumed { "Resuming download..." } else { "Downloading..." };
+        Self::emit_progress(app, id, downloaded, total, 0.0, "downloading", Some(message.to_string()));
 
         let mut stream = response.bytes_stream();
         let mut last_emit = std::time::Instant::now();
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| {
-                let msg = format!("Stream error: {}", e);
-                Self::emit_progress(app, id, downloaded, total, 0.0, "failed", Some(msg.clone()));
-                AppError::Download(msg)
-            })?;
-            file.write_all(&chunk)?;
+        loop {
+            let chunk = tokio::select! {
+                _ = token.cancelled() => return Err(FetchError::Fatal(Self::cancelled_error())),
+                chunk = stream.next() => chunk,
+            };
+            let Some(chunk) = chunk else {
+                break;
+            };
+            let chunk = chunk
+                .map_err(|e| FetchError::Retryable(AppError::Download(format!("Stream error: {}", e))))?;
+            file.write_all(&chunk).map_err(|e| FetchError::Fatal(e.into()))?;
             downloaded += chunk.len() as u64;
 
             // Throttle events to every 100ms to avoid flooding
@@ -118,82 +442,200 @@ impl DownloadManager {
             }
         }
 
-        Self::emit_progress(app, id, downloaded, total, 100.0, "completed", Some("Download complete".to_string()));
+        file.flush().map_err(|e| FetchError::Fatal(e.into()))?;
+
+        if let Some(total) = total {
+            if downloaded < total {
+                return Err(FetchError::Retryable(AppError::Download(format!(
+                    "Connection closed after {} of {} bytes",
+                    downloaded, total
+                ))));
+            }
+        }
+        Ok(())
+    }
 
-        log::info!("Download complete: {}", dest.display());
-        Ok(dest.to_path_buf())
+    fn cancelled_error() -> AppError {
+        AppError::Cancelled("Download cancelled".to_string())
     }
 
-    #[allow(dead_code)]
-    pub fn extract_tar_gz(app: &AppHandle, id: &str, archive_path: &Path, dest_dir: &Path) -> Result<(), AppError> {
-        log::info!(
-            "Extracting {} to {}",
-            archive_path.display(),
-            dest_dir.display()
-        );
-        std::fs::create_dir_all(dest_dir)?;
+    // ── Job queue ───────────────────────────────────────────────────
 
-        Self::emit_progress(app, id, 0, None, 100.0, "extracting", Some("Extracting archive...".to_string()));
+    fn get_history_path() -> PathBuf {
+        paths::get_data_dir().join("data").join("download-history.json")
+    }
 
-        let file = std::fs::File::open(archive_path)?;
-        let gz = GzDecoder::new(file);
-        let mut archive = Archive::new(gz);
-        archive.unpack(dest_dir)?;
+    /// Load persisted job history. Jobs that were still queued/running when the
+    /// app last exited are reported as interrupted.
+    pub fn load_history() -> Vec<DownloadJob> {
+        let mut jobs: Vec<DownloadJob> = std::fs::read_to_string(Self::get_history_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        for job in jobs.iter_mut().filter(|j| j.is_active()) {
+            job.status = "failed".to_string();
+            job.error = Some("Interrupted: the app exited before this job finished".to_string());
+            job.finished_at = job.finished_at.take().or_else(|| Some(chrono::Utc::now().to_rfc3339()));
+        }
+        jobs
+    }
 
-        Self::emit_progress(app, id, 0, None, 100.0, "completed", Some("Installation complete".to_string()));
+    fn save_history(jobs: &[DownloadJob]) {
+        let path = Self::get_history_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let start = jobs.len().saturating_sub(MAX_JOB_HISTORY);
+        if let Ok(content) = serde_json::to_string_pretty(&jobs[start..]) {
+            let _ = std::fs::write(&path, content);
+        }
+    }
 
-        log::info!("Extraction complete: {}", dest_dir.display());
-        Ok(())
+    /// Drop the oldest finished jobs beyond `MAX_JOB_HISTORY`, so the
+    /// in-memory list stays as short as the saved one.
+    fn trim_history(jobs: &mut Vec<DownloadJob>) {
+        let mut excess = jobs.len().saturating_sub(MAX_JOB_HISTORY);
+        jobs.retain(|j| {
+            if excess > 0 && !j.is_active() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
     }
 
-    #[allow(dead_code)]
-    pub fn extract_zip(app: &AppHandle, id: &str, archive_path: &Path, dest_dir: &Path) -> Result<(), AppError> {
-        log::info!(
-            "Extracting ZIP {} to {}",
-            archive_path.display(),
-            dest_dir.display()
-        );
-        std::fs::create_dir_all(dest_dir)?;
+    fn update_job(state: &AppState, id: &str, update: impl FnOnce(&mut DownloadJob)) {
+        if let Ok(mut jobs) = state.download_jobs.lock() {
+            let finished = match jobs.iter_mut().rev().find(|j| j.id == id) {
+                Some(job) => {
+                    update(job);
+                    !job.is_active()
+                }
+                None => false,
+            };
+            if finished {
+                Self::trim_history(&mut jobs);
+            }
+            Self::save_history(&jobs);
+        }
+    }
 
-        Self::emit_progress(app, id, 0, None, 100.0, "extracting", Some("Extracting archive...".to_string()));
+    /// Run an install/download job under `id`.
+    ///
+    /// A second job with the same id is rejected while the first is queued or
+    /// running, and at most `max_concurrent_downloads` jobs run at once; the
+    /// rest wait (status "queued") and can be cancelled while waiting.
+    pub async fn run_job<T, F>(app: &AppHandle, id: &str, label: &str, job: F) -> Result<T, AppError>
+    where
+        F: std::future::Future<Output = Result<T, AppError>>,
+    {
+        let state = app.state::<AppState>();
+        let token = CancellationToken::new();
 
-        let file = std::fs::File::open(archive_path)?;
-        let mut archive =
-            zip::ZipArchive::new(file).map_err(|e| AppError::Download(e.to_string()))?;
+        {
+            let mut jobs = state
+                .download_jobs
+                .lock()
+                .map_err(|e| AppError::Service(e.to_string()))?;
+            if jobs.iter().any(|j| j.id == id && j.is_active()) {
+                return Err(AppError::Service(format!("{} is already queued or running", label)));
+            }
+            jobs.push(DownloadJob {
+                id: id.to_string(),
+                label: label.to_string(),
+                status: "queued".to_string(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                started_at: None,
+                finished_at: None,
+                error: None,
+            });
+            Self::save_history(&jobs);
 
-        for i in 0..archive.len() {
-            let mut entry = archive
-                .by_index(i)
-                .map_err(|e| AppError::Download(e.to_string()))?;
+            state
+                .download_cancel
+                .lock()
+                .map_err(|e| AppError::Service(e.to_string()))?
+                .insert(id.to_string(), token.clone());
+        }
 
-            let safe_name = match entry.enclosed_name() {
-                Some(name) => name.to_owned(),
-                None => continue, // skip entries with unsafe paths
+        let result = async {
+            let slots = state.download_slots.clone();
+            let _permit = match slots.clone().try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    Self::emit_progress(app, id, 0, None, 0.0, "queued", Some("Waiting for other downloads to finish...".to_string()));
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            Self::emit_progress(app, id, 0, None, 0.0, "cancelled", Some(format!("{} cancelled", label)));
+                            return Err(Self::cancelled_error());
+                        }
+                        permit = slots.acquire_owned() => permit.map_err(|e| AppError::Service(e.to_string()))?,
+                    }
+                }
             };
-            let out_path = dest_dir.join(safe_name);
 
-            if entry.is_dir() {
-                std::fs::create_dir_all(&out_path)?;
-            } else {
-                if let Some(parent) = out_path.parent() {
-                    std::fs::create_dir_all(parent)?;
-                }
-                let mut outfile = std::fs::File::create(&out_path)?;
-                std::io::copy(&mut entry, &mut outfile)?;
-
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    if let Some(mode) = entry.unix_mode() {
-                        std::fs::set_permissions(&out_path, std::fs::Permissions::from_mode(mode))?;
-                    }
+            Self::update_job(&state, id, |j| {
+                j.status = "running".to_string();
+                j.started_at = Some(chrono::Utc::now().to_rfc3339());
+            });
+            job.await
+        }
+        .await;
+
+        if let Ok(mut tokens) = state.download_cancel.lock() {
+            tokens.remove(id);
+        }
+
+        Self::update_job(&state, id, |j| {
+            j.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            match &result {
+                Ok(_) => j.status = "completed".to_string(),
+                Err(AppError::Cancelled(_)) => j.status = "cancelled".to_string(),
+                Err(e) => {
+                    j.status = "failed".to_string();
+                    j.error = Some(e.to_string());
                 }
             }
-        }
+        });
 
-        Self::emit_progress(app, id, 0, None, 100.0, "completed", Some("Installation complete".to_string()));
+        result
+    }
+
+    /// All known jobs, newest first.
+    pub fn list_jobs(state: &AppState) -> Vec<DownloadJob> {
+        let mut jobs = state
+            .download_jobs
+            .lock()
+            .map(|jobs| jobs.clone())
+            .unwrap_or_default();
+        jobs.reverse();
+        jobs
+    }
 
-        log::info!("ZIP extraction complete: {}", dest_dir.display());
+    /// Drop finished jobs from the history, keeping queued/running ones.
+    pub fn clear_history(state: &AppState) -> Result<(), AppError> {
+        let mut jobs = state
+            .download_jobs
+            .lock()
+            .map_err(|e| AppError::Service(e.to_string()))?;
+        jobs.retain(|j| j.is_active());
+        Self::save_history(&jobs);
         Ok(())
     }
+
+    /// Cancel a queued or running download by id. Returns `false` if nothing was running.
+    pub fn cancel(state: &AppState, id: &str) -> bool {
+        match state.download_cancel.lock() {
+            Ok(tokens) => match tokens.get(id) {
+                Some(token) => {
+                    token.cancel();
+                    log::info!("Cancelling download {}", id);
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
 }
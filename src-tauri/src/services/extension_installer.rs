@@ -0,0 +1,352 @@
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::archive_extractor::ArchiveExtractor;
+use crate::services::download_manager::DownloadManager;
+#[cfg(target_os = "windows")]
+use crate::services::php_catalogue::PhpCatalogue;
+use crate::services::php_manager::PhpManager;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+#[cfg(unix)]
+use std::process::Command;
+use tauri::{AppHandle, Emitter};
+
+/// PECL packages that can be added to a managed PHP version.
+pub const INSTALLABLE: [&str; 6] = ["redis", "imagick", "xdebug", "pcov", "swoole", "mongodb"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallableExtension {
+    pub name: String,
+    /// Module file present in extension_dir (or loaded)
+    pub installed: bool,
+    pub enabled: bool,
+}
+
+/// Adds PECL extensions to a PHP version: a prebuilt binary where one
+/// exists (Homebrew's shivammathur/extensions tap, windows.php.net DLLs),
+/// otherwise a phpize build from the PECL tarball.
+pub struct ExtensionInstaller;
+
+impl ExtensionInstaller {
+    fn emit_progress(app: &AppHandle, id: &str, status: &str, message: &str) {
+        let _ = app.emit(
+            "download-progress",
+            DownloadManager::progress(
+                id,
+                0,
+                None,
+                if status == "completed" { 100.0 } else { 0.0 },
+                status,
+                Some(message.to_string()),
+            ),
+        );
+    }
+
+    fn module_file(name: &str) -> String {
+        if cfg!(target_os = "windows") {
+            format!("php_{}.dll", name)
+        } else {
+            format!("{}.so", name)
+        }
+    }
+
+    /// Module file of `name` is present in the version's extension_dir.
+    pub fn is_installed(version: &str, name: &str) -> Result<bool, AppError> {
+        Ok(PhpManager::extension_dir(version)?.join(Self::module_file(name)).exists())
+    }
+
+    pub fn list(version: &str) -> Result<Vec<InstallableExtension>, AppError> {
+        let extension_dir = PhpManager::extension_dir(version)?;
+        INSTALLABLE
+            .iter()
+            .map(|name| {
+                let enabled = PhpManager::is_extension_loaded(version, name)?;
+                Ok(InstallableExtension {
+                    name: name.to_string(),
+                    installed: enabled || extension_dir.join(Self::module_file(name)).exists(),
+                    enabled,
+                })
+            })
+            .collect()
+    }
+
+    /// Install, enable and verify `name` for `version`. The extension is
+    /// disabled again when PHP fails to load it, so FPM keeps starting.
+    pub async fn install(app: &AppHandle, version: &str, name: &str) -> Result<(), AppError> {
+        if !INSTALLABLE.contains(&name) {
+            return Err(AppError::Config(format!(
+                "'{}' is not installable (available: {})",
+                name,
+                INSTALLABLE.join(", ")
+            )));
+        }
+        if !PhpManager::get_php_binary(version).exists() {
+            return Err(AppError::NotFound(format!("PHP {} is not installed", version)));
+        }
+        if PhpManager::is_extension_loaded(version, name)? {
+            return Err(AppError::Service(format!(
+                "{} is already enabled for PHP {}",
+                name, version
+            )));
+        }
+
+        let id = Self::job_id(version, name);
+        let extension_dir = PhpManager::extension_dir(version)?;
+        if !extension_dir.join(Self::module_file(name)).exists() {
+            if let Err(e) = Self::fetch_module(app, &id, version, name, &extension_dir).await {
+                Self::emit_progress(app, &id, "failed", &e.to_string());
+                return Err(e);
+            }
+        }
+
+        // Homebrew bottles ship their own conf.d ini and may already be loaded
+        if !PhpManager::is_extension_loaded(version, name)? {
+            Self::emit_progress(app, &id, "extracting", &format!("Enabling {}...", name));
+            PhpManager::toggle_extension(version, name, true)?;
+        }
+
+        if !PhpManager::is_extension_loaded(version, name)? {
+            let _ = PhpManager::toggle_extension(version, name, false);
+            let msg = format!(
+                "{} was installed but PHP {} does not load it (check `php -m` for startup errors)",
+                name, version
+            );
+            Self::emit_progress(app, &id, "failed", &msg);
+            return Err(AppError::Process(msg));
+        }
+
+        log::info!("Installed PHP {} extension {}", version, name);
+        Self::emit_progress(app, &id, "completed", &format!("{} installed for PHP {}", name, version));
+        Ok(())
+    }
+
+    pub fn job_id(version: &str, name: &str) -> String {
+        format!("php-{}-ext-{}", version, name)
+    }
+
+    // --- Fetching the module ---
+
+    /// Homebrew: prebuilt bottle from shivammathur/extensions, phpize build
+    /// as a fallback.
+    #[cfg(target_os = "macos")]
+    async fn fetch_module(
+        app: &AppHandle,
+        id: &str,
+        version: &str,
+        name: &str,
+        extension_dir: &Path,
+    ) -> Result<(), AppError> {
+        Self::emit_progress(app, id, "extracting", "Tapping shivammathur/extensions...");
+        let _ = Command::new("brew").args(["tap", "shivammathur/extensions"]).output();
+
+        let formula = format!("shivammathur/extensions/{}@{}", name, version);
+        Self::emit_progress(app, id, "extracting", &format!("Installing {} via Homebrew...", formula));
+        let install = Command::new("brew")
+            .args(["install", &formula])
+            .output()
+            .map_err(|e| AppError::Process(format!("brew install failed: {}", e)))?;
+        if install.status.success() {
+            return Ok(());
+        }
+
+        log::warn!(
+            "brew install {} failed, building from source: {}",
+            formula,
+            Self::tail(&String::from_utf8_lossy(&install.stderr))
+        );
+        Self::build_from_source(app, id, version, name, extension_dir).await
+    }
+
+    /// Windows: the DLL published for this PHP minor, thread safety and
+    /// toolchain. Libraries bundled with it go next to php.exe.
+    #[cfg(target_os = "windows")]
+    async fn fetch_module(
+        app: &AppHandle,
+        id: &str,
+        version: &str,
+        name: &str,
+        extension_dir: &Path,
+    ) -> Result<(), AppError> {
+        if name == "swoole" {
+            return Err(AppError::Service("swoole is not available for Windows".to_string()));
+        }
+
+        let package_version = Self::latest_pecl_version(name).await?;
+        let release = PhpCatalogue::find(version)?;
+        let url = format!(
+            "https://downloads.php.net/~windows/pecl/releases/{name}/{pv}/php_{name}-{pv}-{php}-nts-{vs}-x64.zip",
+            name = name,
+            pv = package_version,
+            php = version,
+            vs = release.windows_toolchain.as_deref().unwrap_or("vs17"),
+        );
+
+        // Version numbers contain dots, so the archive name is built rather
+        // than derived with with_extension()
+        let stem = format!("{}-{}-php{}", name, package_version, version);
+        let work_dir = paths::get_cache_dir().join("pecl").join(&stem);
+        let archive = paths::get_cache_dir().join("pecl").join(format!("{}.zip", stem));
+        let _ = std::fs::remove_dir_all(&work_dir);
+        DownloadManager::download_executable(app, id, &url, &archive).await?;
+        ArchiveExtractor::extract(app, id, &archive, &work_dir, 0)?;
+
+        let php_dir = PhpManager::get_php_binary(version)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        std::fs::create_dir_all(extension_dir)?;
+        let module = Self::module_file(name);
+        for entry in std::fs::read_dir(&work_dir)?.flatten() {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.eq_ignore_ascii_case(&module) {
+                std::fs::copy(&path, extension_dir.join(&file_name))?;
+            } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("dll")) {
+                std::fs::copy(&path, php_dir.join(&file_name))?;
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&work_dir);
+        let _ = std::fs::remove_file(&archive);
+        if !extension_dir.join(&module).exists() {
+            return Err(AppError::Download(format!("{} did not contain {}", url, module)));
+        }
+        Ok(())
+    }
+
+    /// Linux: the static builds we install have no phpize, so this only
+    /// works for builds that ship the development tools.
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    async fn fetch_module(
+        app: &AppHandle,
+        id: &str,
+        version: &str,
+        name: &str,
+        extension_dir: &Path,
+    ) -> Result<(), AppError> {
+        Self::build_from_source(app, id, version, name, extension_dir).await
+    }
+
+    /// `phpize && ./configure && make` in the PECL tarball, then copy the
+    /// module into extension_dir.
+    #[cfg(unix)]
+    async fn build_from_source(
+        app: &AppHandle,
+        id: &str,
+        version: &str,
+        name: &str,
+        extension_dir: &Path,
+    ) -> Result<(), AppError> {
+        let php_bin = PhpManager::get_php_binary(version);
+        let phpize = php_bin.with_file_name("phpize");
+        let php_config = php_bin.with_file_name("php-config");
+        if !phpize.exists() || !php_config.exists() {
+            return Err(AppError::Service(format!(
+                "No prebuilt {} for PHP {} and this build has no phpize/php-config to compile it",
+                name, version
+            )));
+        }
+        for tool in ["make", "cc", "autoconf"] {
+            if Command::new("which").arg(tool).output().map_or(true, |o| !o.status.success()) {
+                return Err(AppError::Service(format!(
+                    "Building {} needs '{}'; install a compiler toolchain first",
+                    name, tool
+                )));
+            }
+        }
+
+        let package_version = Self::latest_pecl_version(name).await?;
+        let stem = format!("{}-{}-php{}", name, package_version, version);
+        let build_dir = paths::get_cache_dir().join("pecl").join(&stem);
+        let archive = paths::get_cache_dir().join("pecl").join(format!("{}.tgz", stem));
+        let _ = std::fs::remove_dir_all(&build_dir);
+
+        let url = format!("https://pecl.php.net/get/{}-{}.tgz", name, package_version);
+        DownloadManager::download_file(app, id, &url, &archive).await?;
+        // package.xml sits next to the <name>-<version>/ source dir and is dropped
+        ArchiveExtractor::extract(app, id, &archive, &build_dir, 1)?;
+
+        let configure = format!("--with-php-config={}", php_config.display());
+        let jobs = format!(
+            "-j{}",
+            std::thread::available_parallelism().map(|n| n.get()).unwrap_or(2)
+        );
+        let steps: [(&str, &Path, Vec<&str>); 3] = [
+            ("phpize", &phpize, vec![]),
+            ("configure", Path::new("./configure"), vec![configure.as_str()]),
+            ("make", Path::new("make"), vec![jobs.as_str()]),
+        ];
+        for (label, program, args) in steps {
+            Self::emit_progress(app, id, "extracting", &format!("Building {} ({})...", name, label));
+            let output = Command::new(program)
+                .args(&args)
+                .current_dir(&build_dir)
+                .output()
+                .map_err(|e| AppError::Process(format!("{} failed to run: {}", label, e)))?;
+            if !output.status.success() {
+                let log = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                return Err(AppError::Process(format!(
+                    "Building {} failed at {}: {}",
+                    name,
+                    label,
+                    Self::tail(&log)
+                )));
+            }
+        }
+
+        let module = build_dir.join("modules").join(Self::module_file(name));
+        if !module.exists() {
+            return Err(AppError::Process(format!("Build of {} produced no {}", name, module.display())));
+        }
+        std::fs::create_dir_all(extension_dir)?;
+        std::fs::copy(&module, extension_dir.join(Self::module_file(name)))?;
+
+        let _ = std::fs::remove_dir_all(&build_dir);
+        let _ = std::fs::remove_file(&archive);
+        Ok(())
+    }
+
+    /// Latest stable release from the PECL REST API.
+    async fn latest_pecl_version(name: &str) -> Result<String, AppError> {
+        let client = reqwest::Client::builder()
+            .user_agent("LokcalDev/0.1")
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| AppError::Download(e.to_string()))?;
+        let url = format!("https://pecl.php.net/rest/r/{}/stable.txt", name);
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| AppError::Download(format!("Failed to query PECL for {}: {}", name, e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::Download(format!(
+                "PECL returned {} for {}",
+                response.status(),
+                name
+            )));
+        }
+        let version = response
+            .text()
+            .await
+            .map_err(|e| AppError::Download(e.to_string()))?
+            .trim()
+            .to_string();
+        if version.is_empty() {
+            return Err(AppError::Download(format!("PECL has no stable release of {}", name)));
+        }
+        Ok(version)
+    }
+
+    /// Last part of a build log, enough to show the actual error.
+    #[cfg(unix)]
+    fn tail(log: &str) -> String {
+        let lines: Vec<&str> = log.trim().lines().collect();
+        lines[lines.len().saturating_sub(15)..].join("\n")
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::AppError;
+use std::io::{Read, Write};
+use std::time::Duration;
+
+const VERSION: u8 = 1;
+const BEGIN_REQUEST: u8 = 1;
+const END_REQUEST: u8 = 3;
+const PARAMS: u8 = 4;
+const STDIN: u8 = 5;
+const STDOUT: u8 = 6;
+const STDERR: u8 = 7;
+const RESPONDER: u16 = 1;
+const REQUEST_ID: u16 = 1;
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Response of a FastCGI responder, split into CGI headers and body.
+#[derive(Debug, Clone)]
+pub struct FastCgiResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// FCGI_STDERR output, e.g. PHP warnings
+    pub stderr: String,
+}
+
+/// Minimal FastCGI client for talking to PHP-FPM directly, without going
+/// through nginx. One request per connection.
+pub struct FastCgiClient;
+
+impl FastCgiClient {
+    /// Send a request without body to `address` ("127.0.0.1:9081" or
+    /// "unix:/path/to.sock", as used by nginx's `fastcgi_pass`).
+    pub fn request(address: &str, params: &[(&str, &str)]) -> Result<FastCgiResponse, AppError> {
+        let connect_err = |e: std::io::Error| AppError::Process(format!("Cannot connect to {}: {}", address, e));
+
+        if let Some(path) = address.strip_prefix("unix:") {
+            #[cfg(unix)]
+            {
+                let stream = std::os::unix::net::UnixStream::connect(path).map_err(connect_err)?;
+                stream.set_read_timeout(Some(TIMEOUT))?;
+                stream.set_write_timeout(Some(TIMEOUT))?;
+                return Self::exchange(stream, params);
+            }
+            #[cfg(not(unix))]
+            {
+                return Err(AppError::Process(format!("Unix sockets are not supported here: {}", path)));
+            }
+        }
+
+        let addr: std::net::SocketAddr = address
+            .parse()
+            .map_err(|_| AppError::Config(format!("Invalid FastCGI address: {}", address)))?;
+        let stream = std::net::TcpStream::connect_timeout(&addr, TIMEOUT).map_err(connect_err)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        Self::exchange(stream, params)
+    }
+
+    fn exchange<S: Read + Write>(mut stream: S, params: &[(&str, &str)]) -> Result<FastCgiResponse, AppError> {
+        let mut begin = Vec::with_capacity(8);
+        begin.extend_from_slice(&RESPONDER.to_be_bytes());
+        begin.extend_from_slice(&[0; 6]); // flags (no keep-alive) + reserved
+
+        let mut encoded = Vec::new();
+        for (name, value) in params {
+            Self::encode_length(&mut encoded, name.len());
+            Self::encode_length(&mut encoded, value.len());
+            encoded.extend_from_slice(name.as_bytes());
+            encoded.extend_from_slice(value.as_bytes());
+        }
+
+        let mut request = Self::record(BEGIN_REQUEST, &begin);
+        for chunk in encoded.chunks(u16::MAX as usize) {
+            request.extend(Self::record(PARAMS, chunk));
+        }
+        request.extend(Self::record(PARAMS, &[]));
+        request.extend(Self::record(STDIN, &[]));
+        stream.write_all(&request)?;
+        stream.flush()?;
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        loop {
+            let mut header = [0u8; 8];
+            stream.read_exact(&mut header)?;
+            let content_length = u16::from_be_bytes([header[4], header[5]]) as usize;
+            let padding_length = header[6] as usize;
+            let mut content = vec![0u8; content_length + padding_length];
+            stream.read_exact(&mut content)?;
+            content.truncate(content_length);
+
+            match header[1] {
+                STDOUT => stdout.extend(content),
+                STDERR => stderr.extend(content),
+                END_REQUEST => break,
+                _ => {}
+            }
+        }
+
+        Ok(Self::parse_response(
+            &String::from_utf8_lossy(&stdout),
+            String::from_utf8_lossy(&stderr).to_string(),
+        ))
+    }
+
+    fn record(record_type: u8, content: &[u8]) -> Vec<u8> {
+        let mut record = Vec::with_capacity(8 + content.len());
+        record.extend_from_slice(&[VERSION, record_type]);
+        record.extend_from_slice(&REQUEST_ID.to_be_bytes());
+        record.extend_from_slice(&(content.len() as u16).to_be_bytes());
+        record.extend_from_slice(&[0, 0]); // padding length + reserved
+        record.extend_from_slice(content);
+        record
+    }
+
+    /// Lengths below 128 take one byte, longer ones four with the high bit set.
+    fn encode_length(buf: &mut Vec<u8>, len: usize) {
+        if len < 128 {
+            buf.push(len as u8);
+        } else {
+            buf.extend_from_slice(&((len as u32) | 0x8000_0000).to_be_bytes());
+        }
+    }
+
+    fn parse_response(output: &str, stderr: String) -> FastCgiResponse {
+        let (head, body) = output
+            .split_once("\r\n\r\n")
+            .or_else(|| output.split_once("\n\n"))
+            .unwrap_or(("", output));
+        let headers: Vec<(String, String)> = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+        // CGI responses only carry a Status header when it is not 200
+        let status = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("status"))
+            .and_then(|(_, v)| v.split_whitespace().next()?.parse().ok())
+            .unwrap_or(200);
+        FastCgiResponse {
+            status,
+            headers,
+            body: body.to_string(),
+            stderr,
+        }
+    }
+}
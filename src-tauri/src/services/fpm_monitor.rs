@@ -0,0 +1,227 @@
+use crate::config::app_config::AppConfig;
+use crate::error::AppError;
+use crate::services::fastcgi_client::FastCgiClient;
+use crate::services::php_manager::{PhpManager, FPM_STATUS_PATH};
+use crate::services::site_manager::SiteManager;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// Only the end of a slowlog is parsed; older entries are of little use.
+const SLOWLOG_TAIL_BYTES: u64 = 1024 * 1024;
+
+/// PHP-FPM's `?json&full` status page, re-keyed for the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct FpmStatus {
+    pub pool: String,
+    #[serde(rename(deserialize = "process manager"))]
+    pub process_manager: String,
+    /// Seconds since the pool started
+    #[serde(rename(deserialize = "start since"))]
+    pub start_since: u64,
+    #[serde(rename(deserialize = "accepted conn"))]
+    pub accepted_conn: u64,
+    /// Requests waiting for a free worker right now
+    #[serde(rename(deserialize = "listen queue"))]
+    pub listen_queue: u64,
+    #[serde(rename(deserialize = "max listen queue"))]
+    pub max_listen_queue: u64,
+    #[serde(rename(deserialize = "idle processes"))]
+    pub idle_processes: u64,
+    #[serde(rename(deserialize = "active processes"))]
+    pub active_processes: u64,
+    #[serde(rename(deserialize = "total processes"))]
+    pub total_processes: u64,
+    #[serde(rename(deserialize = "max active processes"))]
+    pub max_active_processes: u64,
+    /// Times a request had to wait because pm.max_children was reached
+    #[serde(rename(deserialize = "max children reached"))]
+    pub max_children_reached: u64,
+    #[serde(rename(deserialize = "slow requests"))]
+    pub slow_requests: u64,
+    #[serde(default)]
+    pub processes: Vec<FpmProcess>,
+    /// pm.max_children of the pool (not part of FPM's output)
+    #[serde(default)]
+    pub max_children: u32,
+    /// Every worker is busy and nothing is left to spawn
+    #[serde(default)]
+    pub saturated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct FpmProcess {
+    pub pid: u32,
+    /// "Idle", "Running", ...
+    pub state: String,
+    pub requests: u64,
+    /// Microseconds spent on the current (or last) request
+    #[serde(rename(deserialize = "request duration"))]
+    pub request_duration: u64,
+    #[serde(rename(deserialize = "request method"))]
+    pub request_method: String,
+    #[serde(rename(deserialize = "request uri"))]
+    pub request_uri: String,
+    pub script: String,
+    #[serde(rename(deserialize = "last request cpu"), default)]
+    pub last_request_cpu: f64,
+    #[serde(rename(deserialize = "last request memory"), default)]
+    pub last_request_memory: u64,
+}
+
+/// One request logged by `request_slowlog_timeout`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowlogEntry {
+    pub time: String,
+    pub pool: String,
+    pub pid: u32,
+    pub script: String,
+    /// Innermost frame first, as FPM writes them
+    pub trace: Vec<String>,
+}
+
+/// Worker saturation and slow requests of PHP-FPM pools, read from the
+/// status page (queried over FastCGI) and the slowlog.
+pub struct FpmMonitor;
+
+impl FpmMonitor {
+    /// FastCGI address, pm.max_children and slowlog of the version's shared
+    /// pool, or of a site's own pool.
+    fn target(version: &str, site_id: Option<&str>) -> Result<(String, u32, PathBuf), AppError> {
+        match site_id {
+            Some(id) => {
+                let site = SiteManager::get(id)?;
+                if !SiteManager::has_own_pool(&site) {
+                    return Err(AppError::Config(format!(
+                        "{} uses the shared pool of PHP {}",
+                        site.domain, site.php_version
+                    )));
+                }
+                let max_children = site.pool.as_ref().map_or(0, |p| p.max_children);
+                Ok((
+                    SiteManager::fastcgi_pass(&site),
+                    max_children,
+                    PhpManager::get_site_slowlog_path(&site),
+                ))
+            }
+            None => Ok((
+                PhpManager::fastcgi_address(version),
+                AppConfig::load().fpm_settings(version).max_children,
+                PhpManager::get_fpm_slowlog_path(version),
+            )),
+        }
+    }
+
+    pub fn get_status(version: &str, site_id: Option<&str>) -> Result<FpmStatus, AppError> {
+        if cfg!(target_os = "windows") {
+            return Err(AppError::Service(
+                "php-cgi on Windows has no status page".to_string(),
+            ));
+        }
+        if !PhpManager::is_fpm_running(version) {
+            return Err(AppError::Service(format!("PHP-FPM {} is not running", version)));
+        }
+
+        let (address, max_children, _) = Self::target(version, site_id)?;
+        let query = "json&full";
+        let request_uri = format!("{}?{}", FPM_STATUS_PATH, query);
+        let response = FastCgiClient::request(
+            &address,
+            &[
+                ("GATEWAY_INTERFACE", "CGI/1.1"),
+                ("REQUEST_METHOD", "GET"),
+                ("SCRIPT_NAME", FPM_STATUS_PATH),
+                ("SCRIPT_FILENAME", FPM_STATUS_PATH),
+                ("QUERY_STRING", query),
+                ("REQUEST_URI", &request_uri),
+                ("SERVER_PROTOCOL", "HTTP/1.1"),
+            ],
+        )?;
+        if response.status != 200 {
+            // Started before pm.status_path was added to the pool config
+            return Err(AppError::Service(format!(
+                "PHP-FPM {} has no status page yet; restart it to enable monitoring",
+                version
+            )));
+        }
+
+        let mut status: FpmStatus = serde_json::from_str(&response.body)?;
+        status.max_children = max_children;
+        status.saturated = status.idle_processes == 0
+            && max_children > 0
+            && status.active_processes >= u64::from(max_children);
+        Ok(status)
+    }
+
+    /// Newest entries first, at most `limit`.
+    pub fn read_slowlog(version: &str, site_id: Option<&str>, limit: usize) -> Result<Vec<SlowlogEntry>, AppError> {
+        let (_, _, path) = Self::target(version, site_id)?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut file = std::fs::File::open(&path)?;
+        let len = file.metadata()?.len();
+        file.seek(SeekFrom::Start(len.saturating_sub(SLOWLOG_TAIL_BYTES)))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+
+        let mut entries = Self::parse_slowlog(&String::from_utf8_lossy(&buf));
+        entries.reverse();
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    /// Entries look like:
+    ///
+    /// ```text
+    /// [17-Oct-2026 10:22:13]  [pool www] pid 4242
+    /// script_filename = /Users/me/Sites/app/public/index.php
+    /// [0x00007f3a1c214f20] sleep() /Users/me/Sites/app/src/Slow.php:12
+    /// [0x00007f3a1c214e80] handle() /Users/me/Sites/app/public/index.php:5
+    /// ```
+    fn parse_slowlog(content: &str) -> Vec<SlowlogEntry> {
+        let mut entries = Vec::new();
+        let mut current: Option<SlowlogEntry> = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some((time, rest)) = line
+                .strip_prefix('[')
+                .and_then(|l| l.split_once(']'))
+                .filter(|(_, rest)| rest.contains("[pool "))
+            {
+                entries.extend(current.take());
+                let rest = rest.trim();
+                let pool = rest
+                    .strip_prefix("[pool ")
+                    .and_then(|r| r.split_once(']'))
+                    .map(|(p, _)| p.to_string())
+                    .unwrap_or_default();
+                let pid = rest
+                    .rsplit_once("pid ")
+                    .and_then(|(_, p)| p.trim().parse().ok())
+                    .unwrap_or_default();
+                current = Some(SlowlogEntry {
+                    time: time.to_string(),
+                    pool,
+                    pid,
+                    ..Default::default()
+                });
+            } else if let Some(entry) = current.as_mut() {
+                if let Some(script) = line.strip_prefix("script_filename = ") {
+                    entry.script = script.to_string();
+                } else if line.starts_with("[0x") {
+                    // Drop the frame address
+                    let frame = line.split_once("] ").map_or(line, |(_, f)| f);
+                    entry.trace.push(frame.to_string());
+                }
+            }
+        }
+        entries.extend(current);
+        entries
+    }
+}
@@ -0,0 +1,354 @@
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Directives that legitimately appear more than once; they are matched on
+/// key and value instead of key alone.
+const MULTI_VALUE_KEYS: [&str; 2] = ["extension", "zend_extension"];
+
+/// Name given to directives before the first section header.
+const GLOBAL_SECTION: &str = "PHP";
+
+#[derive(Debug, Clone)]
+enum Line {
+    /// Blank lines, prose comments and anything unparseable, kept verbatim
+    Other(String),
+    Section { name: String, raw: String },
+    Directive {
+        key: String,
+        value: String,
+        /// `;key = value`: a documented default or a disabled setting
+        commented: bool,
+        raw: String,
+    },
+}
+
+/// An active directive with the section it was found in.
+#[derive(Debug, Clone)]
+pub struct IniEntry {
+    pub section: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// A directive that differs between two documents. `None` means unset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IniChange {
+    pub section: String,
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// php.ini as a list of lines. Untouched lines are written back exactly as
+/// they were read, so comments, ordering and formatting survive edits.
+#[derive(Debug, Clone, Default)]
+pub struct IniDocument {
+    lines: Vec<Line>,
+}
+
+impl IniDocument {
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|raw| {
+                let trimmed = raw.trim();
+                if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                    let name = trimmed[1..trimmed.len() - 1].trim().to_string();
+                    return Line::Section { name, raw: raw.to_string() };
+                }
+                let (text, commented) = match trimmed.strip_prefix(';') {
+                    Some(rest) => (rest.trim_start_matches(';').trim(), true),
+                    None => (trimmed, false),
+                };
+                match Self::parse_directive(text) {
+                    Some((key, value)) => Line::Directive { key, value, commented, raw: raw.to_string() },
+                    None => Line::Other(raw.to_string()),
+                }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AppError> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        std::fs::write(path, self.render())?;
+        Ok(())
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for line in &self.lines {
+            match line {
+                Line::Other(raw) | Line::Section { raw, .. } | Line::Directive { raw, .. } => out.push_str(raw),
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Plausible directive name (`date.timezone`, `opcache.jit_buffer_size`).
+    pub fn is_valid_key(key: &str) -> bool {
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '[' | ']'))
+    }
+
+    /// `key = value` with a plausible directive name; prose comments that
+    /// happen to contain '=' are rejected by the key check.
+    fn parse_directive(text: &str) -> Option<(String, String)> {
+        let (key, value) = text.split_once('=')?;
+        let key = key.trim();
+        Self::is_valid_key(key).then(|| (key.to_string(), value.trim().to_string()))
+    }
+
+    fn directive_line(key: &str, value: &str) -> Line {
+        Line::Directive {
+            key: key.to_string(),
+            value: value.to_string(),
+            commented: false,
+            raw: format!("{} = {}", key, value),
+        }
+    }
+
+    /// Section name of every line.
+    fn sections(&self) -> Vec<String> {
+        let mut current = GLOBAL_SECTION.to_string();
+        self.lines
+            .iter()
+            .map(|line| {
+                if let Line::Section { name, .. } = line {
+                    current = name.clone();
+                }
+                current.clone()
+            })
+            .collect()
+    }
+
+    fn has_section(&self, section: &str) -> bool {
+        self.lines
+            .iter()
+            .any(|l| matches!(l, Line::Section { name, .. } if name.eq_ignore_ascii_case(section)))
+    }
+
+    fn is_multi_value(key: &str) -> bool {
+        MULTI_VALUE_KEYS.contains(&key)
+    }
+
+    /// Indices of directive lines for `key` (and `value` for multi-value keys)
+    /// in `section`, or in any section when `None`.
+    fn find(&self, section: Option<&str>, key: &str, value: Option<&str>, commented: bool) -> Vec<usize> {
+        let sections = self.sections();
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(i, line)| {
+                let Line::Directive { key: k, value: v, commented: c, .. } = line else {
+                    return false;
+                };
+                k == key
+                    && *c == commented
+                    && value.map_or(true, |value| v == value)
+                    && section.map_or(true, |s| sections[*i].eq_ignore_ascii_case(s))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Section a new `key` belongs in: the requested one, else the section
+    /// named after its prefix (`date.timezone` → `[Date]`), else `[PHP]`.
+    fn target_section(&self, section: Option<&str>, key: &str) -> String {
+        if let Some(section) = section {
+            return section.to_string();
+        }
+        key.split_once('.')
+            .map(|(prefix, _)| prefix)
+            .filter(|prefix| self.has_section(prefix))
+            .and_then(|prefix| {
+                self.lines.iter().find_map(|l| match l {
+                    Line::Section { name, .. } if name.eq_ignore_ascii_case(prefix) => Some(name.clone()),
+                    _ => None,
+                })
+            })
+            .unwrap_or_else(|| GLOBAL_SECTION.to_string())
+    }
+
+    /// Where a new line for `section` goes: after the last non-blank line of
+    /// that section. Sections that do not exist yet are appended.
+    fn insert_position(&mut self, section: &str) -> usize {
+        let sections = self.sections();
+        let in_section: Vec<usize> = (0..self.lines.len())
+            .filter(|i| sections[*i].eq_ignore_ascii_case(section))
+            .collect();
+
+        if in_section.is_empty() {
+            let headers = self.lines.iter().any(|l| matches!(l, Line::Section { .. }));
+            if section.eq_ignore_ascii_case(GLOBAL_SECTION) && headers {
+                // Global directives go before the first header
+                return 0;
+            }
+            if headers {
+                self.lines.push(Line::Other(String::new()));
+                self.lines.push(Line::Section {
+                    name: section.to_string(),
+                    raw: format!("[{}]", section),
+                });
+            }
+            return self.lines.len();
+        }
+
+        let last_content = in_section.iter().rev().find(|i| match &self.lines[**i] {
+            Line::Other(raw) => !raw.trim().is_empty(),
+            _ => true,
+        });
+        last_content.map(|i| i + 1).unwrap_or(in_section[0] + 1)
+    }
+
+    /// Active directives in file order.
+    pub fn entries(&self) -> Vec<IniEntry> {
+        self.sections()
+            .into_iter()
+            .zip(&self.lines)
+            .filter_map(|(section, line)| match line {
+                Line::Directive { key, value, commented: false, .. } => Some(IniEntry {
+                    section,
+                    key: key.clone(),
+                    value: value.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Effective directives keyed by (section, key); multi-value keys are
+    /// keyed by their value too, so each extension is its own entry.
+    fn effective(&self) -> BTreeMap<(String, String), String> {
+        self.entries()
+            .into_iter()
+            .map(|e| {
+                let key = if Self::is_multi_value(&e.key) {
+                    format!("{}={}", e.key, e.value)
+                } else {
+                    e.key
+                };
+                ((e.section, key), e.value)
+            })
+            .collect()
+    }
+
+    /// Directives added, removed or changed going from `self` to `other`.
+    /// Comments and formatting are ignored.
+    pub fn diff(&self, other: &IniDocument) -> Vec<IniChange> {
+        let before = self.effective();
+        let after = other.effective();
+        let mut keys: Vec<&(String, String)> = before.keys().chain(after.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        keys.into_iter()
+            .filter_map(|k| {
+                let (old, new) = (before.get(k), after.get(k));
+                (old != new).then(|| IniChange {
+                    section: k.0.clone(),
+                    key: k.1.split_once('=').map_or(k.1.as_str(), |(key, _)| key).to_string(),
+                    before: old.cloned(),
+                    after: new.cloned(),
+                })
+            })
+            .collect()
+    }
+
+    /// Active and commented-out `key` lines as (value, commented), in file
+    /// order. Used for multi-value keys such as `extension`.
+    pub fn occurrences(&self, key: &str) -> Vec<(String, bool)> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Directive { key: k, value, commented, .. } if k == key => {
+                    Some((value.clone(), *commented))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Value PHP would use: the last active occurrence.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.entries().into_iter().rev().find(|e| e.key == key).map(|e| e.value)
+    }
+
+    /// Set `key` in place: update the last active occurrence and drop earlier
+    /// duplicates, else uncomment a documented default, else add it to the
+    /// end of its section.
+    pub fn set(&mut self, section: Option<&str>, key: &str, value: &str) {
+        let multi = Self::is_multi_value(key);
+        let match_value = multi.then_some(value);
+
+        let active = self.find(section, key, match_value, false);
+        if let Some((last, duplicates)) = active.split_last() {
+            self.lines[*last] = Self::directive_line(key, value);
+            for i in duplicates.iter().rev() {
+                self.lines.remove(*i);
+            }
+            return;
+        }
+
+        if let Some(default) = self.find(section, key, match_value, true).first() {
+            self.lines[*default] = Self::directive_line(key, value);
+            return;
+        }
+
+        let target = self.target_section(section, key);
+        let position = self.insert_position(&target);
+        self.lines.insert(position, Self::directive_line(key, value));
+    }
+
+    /// Re-enable a commented default as it is documented. Returns its value.
+    pub fn uncomment(&mut self, section: Option<&str>, key: &str) -> Option<String> {
+        if let Some(active) = self.find(section, key, None, false).last() {
+            if let Line::Directive { value, .. } = &self.lines[*active] {
+                return Some(value.clone());
+            }
+        }
+        let index = *self.find(section, key, None, true).first()?;
+        let Line::Directive { value, .. } = &self.lines[index] else {
+            return None;
+        };
+        let value = value.clone();
+        self.lines[index] = Self::directive_line(key, &value);
+        Some(value)
+    }
+
+    /// Remove `key` (one `value` of a multi-value key) so PHP falls back to
+    /// its default. The last occurrence is commented out unless a commented
+    /// copy already documents it; duplicates are dropped. Returns whether
+    /// anything changed.
+    pub fn remove(&mut self, section: Option<&str>, key: &str, value: Option<&str>) -> bool {
+        let active = self.find(section, key, value, false);
+        let Some((last, duplicates)) = active.split_last() else {
+            return false;
+        };
+        if self.find(section, key, value, true).is_empty() {
+            if let Line::Directive { key, value, .. } = &self.lines[*last] {
+                self.lines[*last] = Line::Directive {
+                    key: key.clone(),
+                    value: value.clone(),
+                    commented: true,
+                    raw: format!(";{} = {}", key, value),
+                };
+            }
+        } else {
+            self.lines.remove(*last);
+        }
+        for i in duplicates.iter().rev() {
+            self.lines.remove(*i);
+        }
+        true
+    }
+}
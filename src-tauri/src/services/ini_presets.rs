@@ -0,0 +1,179 @@
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::ini_document::IniDocument;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// (name, description, directives)
+type BundledPreset = (&'static str, &'static str, &'static [(&'static str, &'static str)]);
+
+/// Where php.ini-development and php.ini-production disagree, plus the
+/// opcache behaviour production servers are usually run with.
+const BUNDLED: [BundledPreset; 2] = [
+    (
+        "development",
+        "Show every error and keep assertions on (php.ini-development)",
+        &[
+            ("display_errors", "On"),
+            ("display_startup_errors", "On"),
+            ("error_reporting", "E_ALL"),
+            ("log_errors", "On"),
+            ("zend.assertions", "1"),
+            ("mysqlnd.collect_memory_statistics", "On"),
+            ("opcache.validate_timestamps", "1"),
+        ],
+    ),
+    (
+        "production",
+        "Hide errors from output, log them, compile out assertions (php.ini-production)",
+        &[
+            ("display_errors", "Off"),
+            ("display_startup_errors", "Off"),
+            ("error_reporting", "E_ALL & ~E_DEPRECATED"),
+            ("log_errors", "On"),
+            ("zend.assertions", "-1"),
+            ("mysqlnd.collect_memory_statistics", "Off"),
+            ("opcache.validate_timestamps", "0"),
+        ],
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IniPreset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Shipped with the app; cannot be changed or deleted
+    #[serde(default)]
+    pub builtin: bool,
+    pub directives: BTreeMap<String, String>,
+}
+
+impl IniPreset {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let valid_name = !self.name.is_empty()
+            && self.name.len() <= 48
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' '));
+        if !valid_name {
+            return Err(AppError::Config(format!(
+                "Invalid preset name: '{}' (letters, digits, spaces, '-' and '_')",
+                self.name
+            )));
+        }
+        if self.directives.is_empty() {
+            return Err(AppError::Config("A preset needs at least one directive".to_string()));
+        }
+        for (key, value) in &self.directives {
+            if !IniDocument::is_valid_key(key) {
+                return Err(AppError::Config(format!("Invalid php.ini directive: '{}'", key)));
+            }
+            if key == "extension" || key == "zend_extension" {
+                return Err(AppError::Config(
+                    "Presets cannot load extensions; toggle them on the Extensions tab".to_string(),
+                ));
+            }
+            if value.contains(['\n', '\r']) {
+                return Err(AppError::Config(format!(
+                    "Value of '{}' must not contain line breaks",
+                    key
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Named sets of php.ini values: the bundled development/production pair
+/// and user-defined presets stored in the config dir.
+pub struct IniPresets;
+
+impl IniPresets {
+    fn get_path() -> PathBuf {
+        paths::get_config_dir().join("php-presets.json")
+    }
+
+    fn bundled() -> Vec<IniPreset> {
+        BUNDLED
+            .iter()
+            .map(|(name, description, directives)| IniPreset {
+                name: name.to_string(),
+                description: description.to_string(),
+                builtin: true,
+                directives: directives
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn load_user() -> BTreeMap<String, IniPreset> {
+        std::fs::read_to_string(Self::get_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_user(presets: &BTreeMap<String, IniPreset>) -> Result<(), AppError> {
+        let path = Self::get_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(presets)?)?;
+        Ok(())
+    }
+
+    fn is_bundled(name: &str) -> bool {
+        BUNDLED.iter().any(|(n, _, _)| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Bundled presets first, then user presets by name.
+    pub fn list() -> Vec<IniPreset> {
+        let mut presets = Self::bundled();
+        presets.extend(Self::load_user().into_values());
+        presets
+    }
+
+    pub fn get(name: &str) -> Result<IniPreset, AppError> {
+        Self::list()
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| AppError::NotFound(format!("php.ini preset '{}' not found", name)))
+    }
+
+    /// Create or replace a user preset.
+    pub fn save(mut preset: IniPreset) -> Result<IniPreset, AppError> {
+        if Self::is_bundled(&preset.name) {
+            return Err(AppError::Config(format!(
+                "'{}' is a bundled preset and cannot be changed",
+                preset.name
+            )));
+        }
+        preset.builtin = false;
+        preset.validate()?;
+
+        let mut presets = Self::load_user();
+        presets.insert(preset.name.clone(), preset.clone());
+        Self::save_user(&presets)?;
+        Ok(preset)
+    }
+
+    pub fn delete(name: &str) -> Result<(), AppError> {
+        if Self::is_bundled(name) {
+            return Err(AppError::Config(format!(
+                "'{}' is a bundled preset and cannot be deleted",
+                name
+            )));
+        }
+        let mut presets = Self::load_user();
+        if presets.remove(name).is_none() {
+            return Err(AppError::NotFound(format!("php.ini preset '{}' not found", name)));
+        }
+        Self::save_user(&presets)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::config::paths;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Snapshots kept per PHP version; older ones are pruned on capture.
+const MAX_SNAPSHOTS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IniSnapshot {
+    /// File stem: `<timestamp>_<reason>`
+    pub id: String,
+    pub version: String,
+    pub created_at: String,
+    /// What was about to change when the snapshot was taken
+    pub reason: String,
+    pub size: u64,
+}
+
+/// Copies of php.ini taken before every write, so any change made through
+/// the app can be diffed and undone.
+pub struct IniSnapshots;
+
+impl IniSnapshots {
+    fn get_dir(version: &str) -> PathBuf {
+        paths::get_data_dir()
+            .join("data")
+            .join("ini-snapshots")
+            .join(version)
+    }
+
+    /// Ids end up in file names; anything but a plain stem is rejected.
+    fn get_path(version: &str, id: &str) -> Result<PathBuf, AppError> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !id.starts_with('.');
+        if !valid {
+            return Err(AppError::Config(format!("Invalid snapshot id: '{}'", id)));
+        }
+        let path = Self::get_dir(version).join(format!("{}.ini", id));
+        if !path.exists() {
+            return Err(AppError::NotFound(format!(
+                "Snapshot {} not found for PHP {}",
+                id, version
+            )));
+        }
+        Ok(path)
+    }
+
+    fn slug(reason: &str) -> String {
+        let slug: String = reason
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '.') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .take(48)
+            .collect();
+        slug.trim_matches('-').to_string()
+    }
+
+    fn to_snapshot(version: &str, path: &Path) -> Option<IniSnapshot> {
+        let id = path.file_stem()?.to_string_lossy().to_string();
+        let metadata = std::fs::metadata(path).ok()?;
+        let created_at = metadata
+            .modified()
+            .ok()
+            .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339())
+            .unwrap_or_default();
+        let reason = id.split_once('_').map(|(_, r)| r).unwrap_or_default().to_string();
+        Some(IniSnapshot {
+            id,
+            version: version.to_string(),
+            created_at,
+            reason,
+            size: metadata.len(),
+        })
+    }
+
+    /// Copy `ini_path` before it is modified. Skipped when the file is
+    /// identical to the newest snapshot, which keeps rapid edits of the
+    /// same value from flooding the history.
+    pub fn capture(version: &str, ini_path: &Path, reason: &str) -> Result<Option<IniSnapshot>, AppError> {
+        if !ini_path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(ini_path)?;
+        if let Some(latest) = Self::list(version)?.first() {
+            if Self::read(version, &latest.id)? == content {
+                return Ok(None);
+            }
+        }
+
+        let dir = Self::get_dir(version);
+        std::fs::create_dir_all(&dir)?;
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S-%3f");
+        let path = dir.join(format!("{}_{}.ini", stamp, Self::slug(reason)));
+        std::fs::write(&path, &content)?;
+        log::info!("Saved php.ini snapshot for PHP {}: {}", version, path.display());
+
+        Self::prune(version);
+        Ok(Self::to_snapshot(version, &path))
+    }
+
+    /// Newest first.
+    pub fn list(version: &str) -> Result<Vec<IniSnapshot>, AppError> {
+        let dir = Self::get_dir(version);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut snapshots: Vec<IniSnapshot> = std::fs::read_dir(&dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ini"))
+            .filter_map(|path| Self::to_snapshot(version, &path))
+            .collect();
+        // Ids start with a sortable timestamp
+        snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(snapshots)
+    }
+
+    pub fn read(version: &str, id: &str) -> Result<String, AppError> {
+        Ok(std::fs::read_to_string(Self::get_path(version, id)?)?)
+    }
+
+    pub fn delete(version: &str, id: &str) -> Result<(), AppError> {
+        std::fs::remove_file(Self::get_path(version, id)?)?;
+        Ok(())
+    }
+
+    fn prune(version: &str) {
+        let Ok(snapshots) = Self::list(version) else {
+            return;
+        };
+        for snapshot in snapshots.iter().skip(MAX_SNAPSHOTS) {
+            let _ = Self::delete(version, &snapshot.id);
+        }
+    }
+}
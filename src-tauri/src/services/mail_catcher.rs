@@ -0,0 +1,644 @@
+use crate::config::app_config::AppConfig;
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::mime_message::MimeMessage;
+use crate::services::php_manager::PhpManager;
+use crate::state::AppState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Messages larger than this are refused with 552.
+const MAX_MESSAGE_BYTES: usize = 25 * 1024 * 1024;
+
+/// Longest SMTP command line accepted (RFC 5321 allows 512).
+const MAX_COMMAND_BYTES: usize = 4096;
+
+const PREVIEW_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailCatcherInfo {
+    pub running: bool,
+    pub port: u16,
+    pub message_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailAttachment {
+    pub index: usize,
+    pub filename: String,
+    pub content_type: String,
+    pub size: u64,
+    /// File name under the message's `attachments/` folder
+    pub file: String,
+}
+
+/// A received message as stored in `<mail dir>/<id>/message.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailMessage {
+    pub id: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    /// `Date` header as sent
+    pub date: Option<String>,
+    pub received_at: String,
+    pub size: u64,
+    /// SMTP envelope, which includes Bcc recipients
+    pub envelope_from: String,
+    pub envelope_to: Vec<String>,
+    pub text: Option<String>,
+    pub html: Option<String>,
+    pub headers: Vec<MailHeader>,
+    pub attachments: Vec<MailAttachment>,
+}
+
+/// List entry (and `mail-received` event payload).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailSummary {
+    pub id: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject: String,
+    pub received_at: String,
+    pub size: u64,
+    pub attachments: usize,
+    pub preview: String,
+}
+
+impl From<&MailMessage> for MailSummary {
+    fn from(message: &MailMessage) -> Self {
+        let preview: String = message
+            .text
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(PREVIEW_CHARS)
+            .collect();
+        MailSummary {
+            id: message.id.clone(),
+            from: message.from.clone(),
+            to: message.to.clone(),
+            subject: message.subject.clone(),
+            received_at: message.received_at.clone(),
+            size: message.size,
+            attachments: message.attachments.len(),
+            preview,
+        }
+    }
+}
+
+/// A local SMTP server that keeps every message instead of delivering it.
+/// PHP's mail() reaches it through `sendmail_path` (a small PHP script that
+/// speaks SMTP) or, on Windows, the `SMTP`/`smtp_port` directives.
+pub struct MailCatcher;
+
+impl MailCatcher {
+    pub fn port() -> u16 {
+        AppConfig::load().mail_catcher.port
+    }
+
+    fn get_message_dir(id: &str) -> Result<PathBuf, AppError> {
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(AppError::Config(format!("Invalid message id '{}'", id)));
+        }
+        let dir = paths::get_mail_dir().join(id);
+        if !dir.join("message.json").exists() {
+            return Err(AppError::NotFound(format!("Message '{}' not found", id)));
+        }
+        Ok(dir)
+    }
+
+    pub fn is_running(state: &AppState) -> bool {
+        state
+            .mail_catcher_cancel
+            .lock()
+            .map(|c| c.as_ref().is_some_and(|t| !t.is_cancelled()))
+            .unwrap_or(false)
+    }
+
+    pub fn get_info(state: &AppState) -> MailCatcherInfo {
+        MailCatcherInfo {
+            running: Self::is_running(state),
+            port: Self::port(),
+            message_count: Self::message_dirs().len(),
+        }
+    }
+
+    // ── Server ──────────────────────────────────────────────────────
+
+    /// Listen on 127.0.0.1 at the configured port. Binding happens here so a
+    /// port in use is reported to the caller.
+    pub fn start(app: &AppHandle) -> Result<(), AppError> {
+        let state = app.state::<AppState>();
+        let mut cancel = state
+            .mail_catcher_cancel
+            .lock()
+            .map_err(|e| AppError::Service(e.to_string()))?;
+        if cancel.as_ref().is_some_and(|t| !t.is_cancelled()) {
+            return Ok(());
+        }
+
+        let port = Self::port();
+        let listener = std::net::TcpListener::bind(("127.0.0.1", port)).map_err(|e| {
+            AppError::Service(format!("Mail catcher cannot listen on port {}: {}", port, e))
+        })?;
+        listener.set_nonblocking(true)?;
+        std::fs::create_dir_all(paths::get_mail_dir())?;
+
+        let token = CancellationToken::new();
+        *cancel = Some(token.clone());
+        drop(cancel);
+
+        let app_handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let listener = match tokio::net::TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    log::error!("Mail catcher could not listen: {}", e);
+                    token.cancel();
+                    return;
+                }
+            };
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            let app = app_handle.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = Self::handle_session(&app, stream).await {
+                                    log::warn!("Mail catcher session failed: {}", e);
+                                }
+                            });
+                        }
+                        Err(e) => log::warn!("Mail catcher could not accept a connection: {}", e),
+                    },
+                }
+            }
+            log::info!("Mail catcher stopped");
+        });
+
+        // Keep php.ini pointing at the current port
+        for version in PhpManager::list_versions().iter().filter(|v| v.installed) {
+            if let Err(e) = Self::configure_php(&version.version) {
+                log::warn!("Could not configure mail() for PHP {}: {}", version.version, e);
+            }
+        }
+
+        log::info!("Mail catcher listening on 127.0.0.1:{}", port);
+        Ok(())
+    }
+
+    pub fn stop(state: &AppState) {
+        if let Ok(mut cancel) = state.mail_catcher_cancel.lock() {
+            if let Some(token) = cancel.take() {
+                token.cancel();
+            }
+        }
+    }
+
+    /// Follow a settings change: stop when disabled, move to a new port, or
+    /// start when newly enabled.
+    pub fn apply_settings(app: &AppHandle, previous_port: u16) -> Result<(), AppError> {
+        let settings = AppConfig::load().mail_catcher;
+        let state = app.state::<AppState>();
+        let running = Self::is_running(&state);
+        if !settings.enabled {
+            if running {
+                Self::stop(&state);
+            }
+            return Ok(());
+        }
+        if running && settings.port == previous_port {
+            return Ok(());
+        }
+        Self::stop(&state);
+        // Give the old accept loop a moment to release the port
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        Self::start(app)
+    }
+
+    async fn reply(writer: &mut (impl AsyncWriteExt + Unpin), line: &str) -> std::io::Result<()> {
+        writer.write_all(format!("{}\r\n", line).as_bytes()).await
+    }
+
+    /// `<user@host> SIZE=123` -> `user@host`
+    fn path_argument(arg: &str) -> String {
+        let arg = arg.trim();
+        match (arg.find('<'), arg.find('>')) {
+            (Some(start), Some(end)) if start < end => arg[start + 1..end].to_string(),
+            _ => arg.split_whitespace().next().unwrap_or_default().to_string(),
+        }
+    }
+
+    async fn handle_session(app: &AppHandle, stream: TcpStream) -> std::io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        Self::reply(&mut writer, "220 lokcaldev ESMTP mail catcher").await?;
+
+        let mut from: Option<String> = None;
+        let mut recipients: Vec<String> = Vec::new();
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line).await? == 0 {
+                break;
+            }
+            if line.len() > MAX_COMMAND_BYTES {
+                Self::reply(&mut writer, "500 5.5.2 Line too long").await?;
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line);
+            let text = text.trim_end();
+            let (verb, arg) = text.split_once(' ').unwrap_or((text, ""));
+
+            match verb.to_ascii_uppercase().as_str() {
+                "EHLO" => {
+                    writer
+                        .write_all(
+                            format!(
+                                "250-lokcaldev\r\n250-SIZE {}\r\n250-8BITMIME\r\n250-SMTPUTF8\r\n250 AUTH PLAIN LOGIN\r\n",
+                                MAX_MESSAGE_BYTES
+                            )
+                            .as_bytes(),
+                        )
+                        .await?
+                }
+                "HELO" => Self::reply(&mut writer, "250 lokcaldev").await?,
+                "MAIL" => {
+                    from = Some(Self::path_argument(arg.split_once(':').map_or(arg, |(_, a)| a)));
+                    recipients.clear();
+                    Self::reply(&mut writer, "250 2.1.0 OK").await?;
+                }
+                "RCPT" => {
+                    if from.is_none() {
+                        Self::reply(&mut writer, "503 5.5.1 MAIL first").await?;
+                    } else {
+                        recipients.push(Self::path_argument(arg.split_once(':').map_or(arg, |(_, a)| a)));
+                        Self::reply(&mut writer, "250 2.1.5 OK").await?;
+                    }
+                }
+                "DATA" => {
+                    if recipients.is_empty() {
+                        Self::reply(&mut writer, "503 5.5.1 RCPT first").await?;
+                        continue;
+                    }
+                    Self::reply(&mut writer, "354 End data with <CR><LF>.<CR><LF>").await?;
+                    let Some(data) = Self::read_data(&mut reader).await? else {
+                        Self::reply(&mut writer, "552 5.3.4 Message too big").await?;
+                        from = None;
+                        recipients.clear();
+                        continue;
+                    };
+                    let envelope_from = from.take().unwrap_or_default();
+                    let envelope_to = std::mem::take(&mut recipients);
+                    match Self::store(&data, envelope_from, envelope_to) {
+                        Ok(message) => {
+                            Self::reply(&mut writer, &format!("250 2.0.0 OK queued as {}", message.id)).await?;
+                            log::info!("Caught mail '{}' from {}", message.subject, message.from);
+                            let _ = app.emit("mail-received", MailSummary::from(&message));
+                        }
+                        Err(e) => {
+                            log::warn!("Could not store caught mail: {}", e);
+                            Self::reply(&mut writer, "451 4.3.0 Could not store message").await?;
+                        }
+                    }
+                }
+                "AUTH" => {
+                    // Any credentials are accepted; apps often insist on sending some
+                    let mut parts = arg.split_whitespace();
+                    let mechanism = parts.next().unwrap_or_default().to_ascii_uppercase();
+                    let initial = parts.next();
+                    let prompts: &[&str] = match (mechanism.as_str(), initial) {
+                        ("PLAIN", Some(_)) => &[],
+                        ("PLAIN", None) => &["334 "],
+                        ("LOGIN", Some(_)) => &["334 UGFzc3dvcmQ6"],
+                        ("LOGIN", None) => &["334 VXNlcm5hbWU6", "334 UGFzc3dvcmQ6"],
+                        _ => {
+                            Self::reply(&mut writer, "504 5.5.4 Unrecognized authentication type").await?;
+                            continue;
+                        }
+                    };
+                    for prompt in prompts {
+                        Self::reply(&mut writer, prompt).await?;
+                        line.clear();
+                        if reader.read_until(b'\n', &mut line).await? == 0 {
+                            return Ok(());
+                        }
+                    }
+                    Self::reply(&mut writer, "235 2.7.0 Authentication successful").await?;
+                }
+                "RSET" => {
+                    from = None;
+                    recipients.clear();
+                    Self::reply(&mut writer, "250 2.0.0 OK").await?;
+                }
+                "NOOP" => Self::reply(&mut writer, "250 2.0.0 OK").await?,
+                "VRFY" => Self::reply(&mut writer, "252 2.1.5 Cannot verify, will accept").await?,
+                "STARTTLS" => Self::reply(&mut writer, "454 4.7.0 TLS not available").await?,
+                "QUIT" => {
+                    Self::reply(&mut writer, "221 2.0.0 Bye").await?;
+                    break;
+                }
+                _ => Self::reply(&mut writer, "502 5.5.2 Command not recognized").await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Message lines up to the terminating `.`, dot-unstuffed. `None` when
+    /// the message is too big (the rest is still read and dropped).
+    async fn read_data(
+        reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>,
+    ) -> std::io::Result<Option<Vec<u8>>> {
+        let mut data = Vec::new();
+        let mut too_big = false;
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line).await? == 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "connection closed during DATA",
+                ));
+            }
+            if line == b".\r\n" || line == b".\n" {
+                break;
+            }
+            if too_big {
+                continue;
+            }
+            let content = line.strip_prefix(b".").unwrap_or(&line);
+            data.extend_from_slice(content);
+            if data.len() > MAX_MESSAGE_BYTES {
+                too_big = true;
+                data = Vec::new();
+            }
+        }
+        Ok(if too_big { None } else { Some(data) })
+    }
+
+    // ── Storage ─────────────────────────────────────────────────────
+
+    /// Keep a safe file name for an attachment.
+    fn sanitize_filename(name: &str) -> String {
+        let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+        let cleaned: String = name
+            .chars()
+            .map(|c| if c.is_control() || "<>:\"|?*".contains(c) { '_' } else { c })
+            .collect();
+        let cleaned = cleaned.trim_matches(['.', ' ']).to_string();
+        if cleaned.is_empty() {
+            "attachment".to_string()
+        } else {
+            cleaned
+        }
+    }
+
+    fn store(raw: &[u8], envelope_from: String, envelope_to: Vec<String>) -> Result<MailMessage, AppError> {
+        let parsed = MimeMessage::parse(raw);
+        let id = format!(
+            "{}-{}",
+            chrono::Utc::now().format("%Y%m%d%H%M%S%3f"),
+            &Uuid::new_v4().simple().to_string()[..8]
+        );
+        let dir = paths::get_mail_dir().join(&id);
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("message.eml"), raw)?;
+
+        let mut attachments = Vec::new();
+        if !parsed.attachments.is_empty() {
+            let attachments_dir = dir.join("attachments");
+            std::fs::create_dir_all(&attachments_dir)?;
+            for (index, attachment) in parsed.attachments.iter().enumerate() {
+                let file = format!("{}-{}", index + 1, Self::sanitize_filename(&attachment.filename));
+                std::fs::write(attachments_dir.join(&file), &attachment.content)?;
+                attachments.push(MailAttachment {
+                    index,
+                    filename: attachment.filename.clone(),
+                    content_type: attachment.content_type.clone(),
+                    size: attachment.content.len() as u64,
+                    file,
+                });
+            }
+        }
+
+        let message = MailMessage {
+            id,
+            from: parsed.header("From").unwrap_or(&envelope_from).to_string(),
+            to: parsed.addresses("To"),
+            cc: parsed.addresses("Cc"),
+            subject: parsed.header("Subject").unwrap_or("(no subject)").to_string(),
+            date: parsed.header("Date").map(|d| d.to_string()),
+            received_at: chrono::Utc::now().to_rfc3339(),
+            size: raw.len() as u64,
+            envelope_from,
+            envelope_to,
+            text: parsed.text.clone(),
+            html: parsed.html.clone(),
+            headers: parsed
+                .headers
+                .iter()
+                .map(|(name, value)| MailHeader {
+                    name: name.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+            attachments,
+        };
+        std::fs::write(dir.join("message.json"), serde_json::to_string_pretty(&message)?)?;
+        Ok(message)
+    }
+
+    /// Message folders, newest first (ids start with the receive time).
+    fn message_dirs() -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = std::fs::read_dir(paths::get_mail_dir())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.join("message.json").exists())
+                    .collect()
+            })
+            .unwrap_or_default();
+        dirs.sort();
+        dirs.reverse();
+        dirs
+    }
+
+    fn load(dir: &std::path::Path) -> Result<MailMessage, AppError> {
+        let content = std::fs::read_to_string(dir.join("message.json"))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn list() -> Vec<MailSummary> {
+        Self::message_dirs()
+            .iter()
+            .filter_map(|dir| Self::load(dir).ok())
+            .map(|message| MailSummary::from(&message))
+            .collect()
+    }
+
+    pub fn get(id: &str) -> Result<MailMessage, AppError> {
+        Self::load(&Self::get_message_dir(id)?)
+    }
+
+    /// The message source as received.
+    pub fn get_raw(id: &str) -> Result<String, AppError> {
+        let raw = std::fs::read(Self::get_message_dir(id)?.join("message.eml"))?;
+        Ok(String::from_utf8_lossy(&raw).to_string())
+    }
+
+    pub fn get_attachment_path(id: &str, index: usize) -> Result<PathBuf, AppError> {
+        let dir = Self::get_message_dir(id)?;
+        let message = Self::load(&dir)?;
+        let attachment = message
+            .attachments
+            .iter()
+            .find(|a| a.index == index)
+            .ok_or_else(|| AppError::NotFound(format!("Message '{}' has no attachment {}", id, index)))?;
+        Ok(dir.join("attachments").join(&attachment.file))
+    }
+
+    pub fn delete(id: &str) -> Result<(), AppError> {
+        std::fs::remove_dir_all(Self::get_message_dir(id)?)?;
+        Ok(())
+    }
+
+    pub fn clear() -> Result<usize, AppError> {
+        let dirs = Self::message_dirs();
+        for dir in &dirs {
+            std::fs::remove_dir_all(dir)?;
+        }
+        log::info!("Deleted {} caught messages", dirs.len());
+        Ok(dirs.len())
+    }
+
+    // ── PHP ─────────────────────────────────────────────────────────
+
+    fn get_sendmail_script_path() -> PathBuf {
+        paths::get_config_dir().join("sendmail.php")
+    }
+
+    /// sendmail replacement for mail(): reads the message from stdin and
+    /// hands it to the mail catcher over SMTP. Recipients come from the
+    /// headers (`-t`), the sender from `-f` or the From header.
+    #[cfg(not(target_os = "windows"))]
+    fn write_sendmail_script(port: u16) -> Result<PathBuf, AppError> {
+        let script = format!(
+            r#"<?php
+// Generated by LokcalDev: delivers mail() messages to the built-in mail catcher.
+$message = stream_get_contents(STDIN);
+$parts = preg_split("/\r?\n\r?\n/", $message, 2);
+$headers = preg_replace("/\r?\n[ \t]+/", " ", $parts[0]);
+
+$from = null;
+foreach ($argv as $i => $arg) {{
+    if (strpos($arg, '-f') === 0) {{
+        $from = strlen($arg) > 2 ? substr($arg, 2) : ($argv[$i + 1] ?? null);
+    }}
+}}
+$recipients = [];
+foreach (preg_split("/\r?\n/", $headers) as $line) {{
+    if (preg_match('/^(to|cc|bcc):(.*)$/i', $line, $m)) {{
+        preg_match_all('/[^\s<>,"]+@[^\s<>,"]+/', $m[2], $found);
+        $recipients = array_merge($recipients, $found[0]);
+    }} elseif ($from === null && preg_match('/^from:(.*)$/i', $line, $m) && preg_match('/[^\s<>,"]+@[^\s<>,"]+/', $m[1], $found)) {{
+        $from = $found[0];
+    }}
+}}
+$recipients = $recipients ?: ['undisclosed-recipients@localhost'];
+
+$smtp = @fsockopen('127.0.0.1', {port}, $errno, $errstr, 5);
+if (!$smtp) {{
+    fwrite(STDERR, "LokcalDev mail catcher is not running on port {port}: $errstr\n");
+    exit(1);
+}}
+function lokcaldev_smtp($smtp, $command, $expect) {{
+    if ($command !== null) {{
+        fwrite($smtp, $command . "\r\n");
+    }}
+    $reply = '';
+    while (($line = fgets($smtp, 1024)) !== false) {{
+        $reply .= $line;
+        if (strlen($line) < 4 || $line[3] === ' ') {{
+            break;
+        }}
+    }}
+    if ((int) substr($reply, 0, 3) !== $expect) {{
+        fwrite(STDERR, "LokcalDev mail catcher: " . $reply);
+        exit(1);
+    }}
+}}
+lokcaldev_smtp($smtp, null, 220);
+lokcaldev_smtp($smtp, 'HELO localhost', 250);
+lokcaldev_smtp($smtp, 'MAIL FROM:<' . ($from ?? 'php@localhost') . '>', 250);
+foreach ($recipients as $recipient) {{
+    lokcaldev_smtp($smtp, "RCPT TO:<$recipient>", 250);
+}}
+lokcaldev_smtp($smtp, 'DATA', 354);
+$data = preg_replace("/\r?\n/", "\r\n", rtrim($message, "\r\n"));
+$data = preg_replace('/^\./m', '..', $data);
+fwrite($smtp, $data . "\r\n.\r\n");
+lokcaldev_smtp($smtp, null, 250);
+lokcaldev_smtp($smtp, 'QUIT', 221);
+"#,
+            port = port
+        );
+        let path = Self::get_sendmail_script_path();
+        std::fs::create_dir_all(paths::get_config_dir())?;
+        std::fs::write(&path, script)?;
+        Ok(path)
+    }
+
+    /// php.ini directives that send mail() to the mail catcher.
+    #[cfg(not(target_os = "windows"))]
+    fn php_ini_directives(version: &str, port: u16) -> Result<BTreeMap<String, String>, AppError> {
+        let quote = |path: PathBuf| format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"));
+        let script = Self::write_sendmail_script(port)?;
+        // -n: the script needs no extensions, and must not pick up this php.ini
+        let command = format!("{} -n {}", quote(PhpManager::get_php_binary(version)), quote(script));
+        Ok(BTreeMap::from([("sendmail_path".to_string(), format!("\"{}\"", command))]))
+    }
+
+    /// mail() on Windows talks SMTP itself.
+    #[cfg(target_os = "windows")]
+    fn php_ini_directives(_version: &str, port: u16) -> Result<BTreeMap<String, String>, AppError> {
+        Ok(BTreeMap::from([
+            ("SMTP".to_string(), "127.0.0.1".to_string()),
+            ("smtp_port".to_string(), port.to_string()),
+        ]))
+    }
+
+    /// Point a version's mail() at the mail catcher. php.ini is only written
+    /// when something changed.
+    pub fn configure_php(version: &str) -> Result<(), AppError> {
+        let directives = Self::php_ini_directives(version, Self::port())?;
+        if PhpManager::ensure_ini_directives(version, &directives, "mail catcher")? {
+            log::info!("Pointed mail() of PHP {} at the mail catcher", version);
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
 use crate::config::paths;
 use crate::error::AppError;
+#[cfg(not(target_os = "macos"))]
+use crate::services::archive_extractor::ArchiveExtractor;
 use crate::services::download_manager::DownloadManager;
+use crate::services::process_log::ProcessLog;
+#[cfg(not(target_os = "macos"))]
+use crate::services::service_catalogue::ServiceCatalogue;
 use crate::services::utils;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -45,7 +50,7 @@ impl MariaDbManager {
     }
 
     #[cfg(target_os = "macos")]
-    fn get_mysql_binary() -> PathBuf {
+    pub(crate) fn get_mysql_binary() -> PathBuf {
         Self::get_mariadb_prefix().join("bin").join("mysql")
     }
 
@@ -67,7 +72,7 @@ impl MariaDbManager {
     }
 
     #[cfg(target_os = "windows")]
-    fn get_mysql_binary() -> PathBuf {
+    pub(crate) fn get_mysql_binary() -> PathBuf {
         Self::get_base_dir().join("bin").join("mysql.exe")
     }
 
@@ -89,13 +94,13 @@ impl MariaDbManager {
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    fn get_mysql_binary() -> PathBuf {
+    pub(crate) fn get_mysql_binary() -> PathBuf {
         Self::get_base_dir().join("bin").join("mysql")
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     fn get_install_db_binary() -> PathBuf {
-        Self::get_base_dir().join("scripts").join("mysql_install_db")
+        Self::get_base_dir().join("scripts").join("mariadb-install-db")
     }
 
     // ── Common paths ────────────────────────────────────────────────
@@ -113,11 +118,11 @@ impl MariaDbManager {
         paths::get_data_dir().join("data").join("mariadb.sock")
     }
 
-    fn get_log_path() -> PathBuf {
+    pub(crate) fn get_log_path() -> PathBuf {
         paths::get_logs_dir().join("mariadb.log")
     }
 
-    fn get_connection_args() -> Vec<String> {
+    pub(crate) fn get_connection_args() -> Vec<String> {
         #[cfg(target_os = "windows")]
         {
             vec![
@@ -219,29 +224,96 @@ impl MariaDbManager {
             return Err(AppError::Service("MariaDB is already installed".to_string()));
         }
 
-        let version = "11.4.10";
+        let version = ServiceCatalogue::latest("mariadb")?;
         let url = format!(
             "https://archive.mariadb.org/mariadb-{}/winx64-packages/mariadb-{}-winx64.zip",
             version, version
         );
         let archive_path = base_dir.join("mariadb.zip");
 
-        DownloadManager::download_file(app, "mariadb", &url, &archive_path).await?;
-        DownloadManager::extract_zip(app, "mariadb", &archive_path, &base_dir)?;
+        DownloadManager::download_executable(app, "mariadb", &url, &archive_path).await?;
+        ArchiveExtractor::extract(app, "mariadb", &archive_path, &base_dir, 1)?;
 
         let _ = std::fs::remove_file(&archive_path);
 
-        // Flatten: extracted zip creates mariadb-11.4.10-winx64/ subdirectory
-        utils::flatten_extracted_dir(&base_dir, "mariadb-")?;
-
+        Self::emit_progress(app, "completed", "MariaDB installed");
         Ok(Self::get_info())
     }
 
     // ── Install (fallback) ──────────────────────────────────────────
 
+    // ── Install (Linux — bintar download) ───────────────────────────
+
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    pub async fn install(_app: &AppHandle) -> Result<MariaDbInfo, AppError> {
-        Err(AppError::Service("MariaDB install not supported on this platform yet".to_string()))
+    pub async fn install(app: &AppHandle) -> Result<MariaDbInfo, AppError> {
+        let base_dir = Self::get_base_dir();
+        if Self::get_mysqld_binary().exists() {
+            return Err(AppError::Service("MariaDB is already installed".to_string()));
+        }
+
+        let version = ServiceCatalogue::latest("mariadb")?;
+        let arch = std::env::consts::ARCH;
+        let url = format!(
+            "https://archive.mariadb.org/mariadb-{}/bintar-linux-systemd-{}/mariadb-{}-linux-systemd-{}.tar.gz",
+            version, arch, version, arch
+        );
+        let archive_path = base_dir.join("mariadb.tar.gz");
+
+        DownloadManager::download_executable(app, "mariadb", &url, &archive_path).await?;
+        ArchiveExtractor::extract(app, "mariadb", &archive_path, &base_dir, 1)?;
+
+        let _ = std::fs::remove_file(&archive_path);
+
+        if !Self::get_mysqld_binary().exists() {
+            let msg = "MariaDB archive did not contain a mysqld binary".to_string();
+            Self::emit_progress(app, "failed", &msg);
+            return Err(AppError::Download(msg));
+        }
+
+        // Bintar ships without a data directory — create the system tables now
+        if !Self::get_info().initialized {
+            Self::emit_progress(app, "extracting", "Initializing MariaDB data directory...");
+            if let Err(e) = Self::initialize_db() {
+                Self::emit_progress(app, "failed", &e.to_string());
+                return Err(e);
+            }
+        }
+
+        Self::emit_progress(app, "completed", "MariaDB installed");
+        Ok(Self::get_info())
+    }
+
+    // ── Upgrade to the latest patch release ─────────────────────────
+
+    #[cfg(target_os = "macos")]
+    pub async fn upgrade(app: &AppHandle) -> Result<MariaDbInfo, AppError> {
+        if !Self::get_mysqld_binary().exists() {
+            return Err(AppError::NotFound("MariaDB is not installed".to_string()));
+        }
+
+        Self::emit_progress(app, "extracting", "Upgrading MariaDB via Homebrew...");
+        if let Err(e) = utils::brew_upgrade("mariadb") {
+            Self::emit_progress(app, "failed", &e.to_string());
+            return Err(e);
+        }
+
+        Self::emit_progress(app, "completed", "MariaDB upgraded");
+        Ok(Self::get_info())
+    }
+
+    /// Swap in the catalogue's latest binaries. The data directory lives outside the install dir
+    /// and is untouched; the old tree is restored if the install fails.
+    #[cfg(not(target_os = "macos"))]
+    pub async fn upgrade(app: &AppHandle) -> Result<MariaDbInfo, AppError> {
+        if !Self::get_mysqld_binary().exists() {
+            return Err(AppError::NotFound("MariaDB is not installed".to_string()));
+        }
+
+        let (info, previous) = utils::reinstall_dir(&Self::get_base_dir(), Self::install(app)).await?;
+        let _ = std::fs::remove_dir_all(&previous);
+
+        log::info!("Upgraded MariaDB to {}", info.version.as_deref().unwrap_or("unknown"));
+        Ok(info)
     }
 
     // ── get_info ────────────────────────────────────────────────────
@@ -336,6 +408,13 @@ impl MariaDbManager {
         let pid_path = Self::get_pid_path();
         let log_path = Self::get_log_path();
 
+        // Another server on the port would pass the readiness check below
+        if Self::is_accepting() {
+            return Err(AppError::Process(
+                "Cannot start MariaDB: port 3306 is already in use".to_string(),
+            ));
+        }
+
         let mut cmd = Command::new(&mysqld);
         cmd.arg("--no-defaults")
             .arg(format!("--basedir={}", utils::to_forward_slash(&base_dir)))
@@ -344,9 +423,8 @@ impl MariaDbManager {
             .arg(format!("--log-error={}", utils::to_forward_slash(&log_path)))
             .arg("--port=3306")
             .arg("--bind-address=127.0.0.1")
-            .arg("--skip-grant-tables")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null());
+            .arg("--skip-grant-tables");
+        ProcessLog::prepare(&mut cmd);
 
         #[cfg(not(target_os = "windows"))]
         {
@@ -359,16 +437,31 @@ impl MariaDbManager {
             cmd.arg("--skip-named-pipe");
         }
 
-        let child = cmd.spawn()
+        let mut child = cmd.spawn()
             .map_err(|e| AppError::Process(format!("Failed to start MariaDB: {}", e)))?;
 
         let pid = child.id();
+        if let Err(e) = ProcessLog::capture("mariadb", &mut child) {
+            log::warn!("Could not capture output of MariaDB: {}", e);
+        }
         let _ = std::fs::write(&pid_path, pid.to_string());
 
+        // Fail if mysqld exits before it starts listening (port in use,
+        // unreadable data dir, ...)
+        if let Some(status) = ProcessLog::wait_for_startup(&mut child, Self::is_accepting) {
+            let _ = std::fs::remove_file(&pid_path);
+            return Err(ProcessLog::startup_error("mariadb", "MariaDB", status, Some(&log_path)));
+        }
+
         log::info!("Started MariaDB (PID: {})", pid);
         Ok(child)
     }
 
+    fn is_accepting() -> bool {
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 3306));
+        std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_millis(200)).is_ok()
+    }
+
     // ── stop ────────────────────────────────────────────────────────
 
     pub fn stop() -> Result<(), AppError> {
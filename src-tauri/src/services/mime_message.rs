@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+
+/// Nesting depth of multiparts followed before the rest is kept as-is.
+const MAX_DEPTH: usize = 8;
+
+/// A decoded non-text part (or a text part sent as a file).
+#[derive(Debug, Clone)]
+pub struct MimeAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+/// The parts of an RFC 5322 message a mail viewer needs: decoded headers,
+/// the first plain-text and HTML bodies, and attachments.
+#[derive(Debug, Clone, Default)]
+pub struct MimeMessage {
+    /// Unfolded headers in order, with RFC 2047 words decoded
+    pub headers: Vec<(String, String)>,
+    pub text: Option<String>,
+    pub html: Option<String>,
+    pub attachments: Vec<MimeAttachment>,
+}
+
+impl MimeMessage {
+    pub fn parse(raw: &[u8]) -> Self {
+        let (headers, body) = split_headers(raw);
+        let mut message = MimeMessage {
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.clone(), decode_words(value)))
+                .collect(),
+            ..Default::default()
+        };
+        message.walk(&headers, body, 0);
+        message
+    }
+
+    /// Decoded value of the first header called `name`.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Addresses of an address-list header (`To`, `Cc`, ...), as written.
+    pub fn addresses(&self, name: &str) -> Vec<String> {
+        self.headers
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            .flat_map(|(_, v)| split_addresses(v))
+            .collect()
+    }
+
+    fn walk(&mut self, headers: &[(String, String)], body: &[u8], depth: usize) {
+        let (mime, params) = content_type(headers);
+
+        if mime.starts_with("multipart/") && depth < MAX_DEPTH {
+            if let Some(boundary) = params.get("boundary") {
+                for part in split_multipart(body, boundary) {
+                    let (part_headers, part_body) = split_headers(part);
+                    self.walk(&part_headers, part_body, depth + 1);
+                }
+                return;
+            }
+        }
+
+        let encoding = find_header(headers, "content-transfer-encoding").unwrap_or_default();
+        let content = decode_body(body, &encoding);
+        let (disposition, disposition_params) = find_header(headers, "content-disposition")
+            .map(|v| parse_parameters(&v))
+            .unwrap_or_default();
+        let filename = disposition_params
+            .get("filename")
+            .or_else(|| params.get("name"))
+            .map(|f| decode_words(f));
+        let is_attachment = disposition == "attachment" || filename.is_some();
+
+        if !is_attachment && mime == "text/plain" && self.text.is_none() {
+            self.text = Some(decode_charset(&content, params.get("charset").map(String::as_str)));
+        } else if !is_attachment && mime == "text/html" && self.html.is_none() {
+            self.html = Some(decode_charset(&content, params.get("charset").map(String::as_str)));
+        } else if !content.is_empty() {
+            let filename = filename.unwrap_or_else(|| {
+                let index = self.attachments.len() + 1;
+                match mime.as_str() {
+                    "message/rfc822" => format!("message-{}.eml", index),
+                    "text/plain" => format!("part-{}.txt", index),
+                    "text/html" => format!("part-{}.html", index),
+                    _ => format!("part-{}", index),
+                }
+            });
+            self.attachments.push(MimeAttachment {
+                filename,
+                content_type: mime,
+                content,
+            });
+        }
+    }
+}
+
+fn line_end(data: &[u8], from: usize) -> usize {
+    data[from..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(data.len(), |i| from + i + 1)
+}
+
+fn trim_newline(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Unfolded raw headers and the body after the first empty line.
+fn split_headers(raw: &[u8]) -> (Vec<(String, String)>, &[u8]) {
+    let mut lines: Vec<String> = Vec::new();
+    let mut pos = 0;
+    while pos < raw.len() {
+        let end = line_end(raw, pos);
+        let line = trim_newline(&raw[pos..end]);
+        pos = end;
+        if line.is_empty() {
+            break;
+        }
+        let text = String::from_utf8_lossy(line);
+        if text.starts_with([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push(' ');
+                last.push_str(text.trim());
+                continue;
+            }
+        }
+        lines.push(text.to_string());
+    }
+
+    let headers = lines
+        .iter()
+        .filter_map(|l| l.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    (headers, &raw[pos..])
+}
+
+fn find_header(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.clone())
+}
+
+/// Lower-cased MIME type and parameters; `text/plain` when missing.
+fn content_type(headers: &[(String, String)]) -> (String, HashMap<String, String>) {
+    match find_header(headers, "content-type") {
+        Some(value) => parse_parameters(&value),
+        None => ("text/plain".to_string(), HashMap::new()),
+    }
+}
+
+/// `value; key=value; key="quoted; value"` with RFC 2231 `key*=utf-8''%xx`.
+fn parse_parameters(header: &str) -> (String, HashMap<String, String>) {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in header.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ';' if !quoted => pieces.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    pieces.push(current);
+
+    let value = pieces[0].trim().to_ascii_lowercase();
+    let mut params = HashMap::new();
+    for piece in &pieces[1..] {
+        let Some((key, raw)) = piece.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let raw = raw.trim().trim_matches('"');
+        match key.strip_suffix('*') {
+            Some(key) => {
+                // charset'language'percent-encoded
+                let encoded = raw.splitn(3, '\'').nth(2).unwrap_or(raw);
+                params.insert(key.to_string(), percent_decode(encoded));
+            }
+            None => {
+                params.insert(key, raw.to_string());
+            }
+        }
+    }
+    (value, params)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = hex_byte(bytes[i + 1], bytes[i + 2]) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Bodies of the parts between `--boundary` lines.
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut pos = 0;
+    while pos < body.len() {
+        let end = line_end(body, pos);
+        let line = trim_newline(&body[pos..end]);
+        if line.starts_with(delimiter.as_bytes()) {
+            if let Some(s) = start {
+                // The newline before a delimiter belongs to the delimiter
+                parts.push(trim_newline(&body[s..pos]));
+            }
+            if line[delimiter.len()..].starts_with(b"--") {
+                return parts;
+            }
+            start = Some(end);
+        }
+        pos = end;
+    }
+    // Unterminated multipart: keep what arrived
+    if let Some(s) = start {
+        parts.push(&body[s..]);
+    }
+    parts
+}
+
+fn decode_body(body: &[u8], encoding: &str) -> Vec<u8> {
+    match encoding.trim().to_ascii_lowercase().as_str() {
+        "base64" => base64_decode(body),
+        "quoted-printable" => quoted_printable_decode(body, false),
+        _ => body.to_vec(),
+    }
+}
+
+fn base64_value(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some((c - b'A') as u32),
+        b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+        b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    }
+}
+
+/// Lenient: line breaks and other stray characters are skipped.
+fn base64_decode(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &c in input {
+        if c == b'=' {
+            break;
+        }
+        let Some(value) = base64_value(c) else {
+            continue;
+        };
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    out
+}
+
+fn hex_byte(high: u8, low: u8) -> Option<u8> {
+    let digit = |c: u8| (c as char).to_digit(16);
+    Some((digit(high)? * 16 + digit(low)?) as u8)
+}
+
+/// `=XX` escapes and `=` soft line breaks; `header` also maps `_` to a
+/// space (RFC 2047 "Q" encoding).
+fn quoted_printable_decode(input: &[u8], header: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'=' if input[i + 1..].starts_with(b"\r\n") => i += 3,
+            b'=' if input[i + 1..].starts_with(b"\n") => i += 2,
+            b'=' if i + 2 < input.len() => match hex_byte(input[i + 1], input[i + 2]) {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 3;
+                }
+                None => {
+                    out.push(b'=');
+                    i += 1;
+                }
+            },
+            b'_' if header => {
+                out.push(b' ');
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// UTF-8 for anything but the Latin-1 family, which maps byte for byte.
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    match charset.map(|c| c.to_ascii_lowercase()) {
+        Some(c) if c == "iso-8859-1" || c == "latin1" || c == "windows-1252" || c == "us-ascii" => {
+            bytes.iter().map(|&b| b as char).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+/// Decode RFC 2047 `=?charset?B|Q?text?=` words. Whitespace between two
+/// encoded words is dropped, as the RFC requires.
+fn decode_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut last_was_encoded = false;
+    while let Some(start) = rest.find("=?") {
+        let word = &rest[start + 2..];
+        let decoded = word.split_once('?').and_then(|(charset, word)| {
+            let (encoding, word) = word.split_once('?')?;
+            let (text, after) = word.split_once("?=")?;
+            let bytes = match encoding.to_ascii_uppercase().as_str() {
+                "B" => base64_decode(text.as_bytes()),
+                "Q" => quoted_printable_decode(text.as_bytes(), true),
+                _ => return None,
+            };
+            let charset = charset.split('*').next().unwrap_or(charset);
+            Some((decode_charset(&bytes, Some(charset)), after))
+        });
+        match decoded {
+            Some((text, after)) => {
+                let between = &rest[..start];
+                if !(last_was_encoded && between.trim().is_empty()) {
+                    out.push_str(between);
+                }
+                out.push_str(&text);
+                rest = after;
+                last_was_encoded = true;
+            }
+            None => {
+                out.push_str(&rest[..start + 2]);
+                rest = &rest[start + 2..];
+                last_was_encoded = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split an address list on commas outside quotes and angle brackets.
+fn split_addresses(value: &str) -> Vec<String> {
+    let mut addresses = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut angle = false;
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '<' if !quoted => angle = true,
+            '>' if !quoted => angle = false,
+            ',' if !quoted && !angle => {
+                addresses.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    addresses.push(current);
+    addresses
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
@@ -1,6 +1,24 @@
 pub mod utils;
+pub mod archive_extractor;
+pub mod artifact_cache;
+pub mod cli_shims;
+pub mod checksum_manifest;
+pub mod composer_manager;
 pub mod download_manager;
+pub mod extension_installer;
+pub mod fastcgi_client;
+pub mod fpm_monitor;
+pub mod ini_document;
+pub mod ini_presets;
+pub mod ini_snapshots;
+pub mod mail_catcher;
+pub mod mime_message;
+pub mod php_catalogue;
 pub mod php_manager;
+pub mod port_registry;
+pub mod process_log;
+pub mod service_catalogue;
+pub mod service_supervisor;
 pub mod nginx_manager;
 pub mod nginx_config;
 pub mod site_manager;
@@ -9,4 +27,6 @@ pub mod ssl_manager;
 pub mod dns_manager;
 pub mod phpmyadmin_manager;
 pub mod template_manager;
+pub mod upgrade_manager;
+pub mod xdebug_manager;
 pub mod ai_service;
@@ -1,14 +1,21 @@
 use crate::config::paths;
 use crate::error::AppError;
+use crate::services::site_manager::SiteIniOverrides;
 use crate::services::utils;
+use std::collections::BTreeMap;
 
 pub struct NginxConfigGenerator;
 
 impl NginxConfigGenerator {
+    /// `fastcgi_pass` is either `127.0.0.1:<port>` or `unix:<socket path>`;
+    /// `request_timeout` is the pool's `request_terminate_timeout`; without one
+    /// (0) nginx keeps its default `fastcgi_read_timeout`.
     pub fn generate_site_config(
         domain: &str,
         document_root: &str,
-        php_port: u16,
+        fastcgi_pass: &str,
+        request_timeout: u32,
+        ini_overrides: &SiteIniOverrides,
         ssl: bool,
         ssl_cert: Option<&str>,
         ssl_key: Option<&str>,
@@ -22,6 +29,13 @@ impl NginxConfigGenerator {
         let fastcgi_params = utils::to_forward_slash(&fastcgi_params_path);
         let log_dir_str = utils::to_forward_slash(&log_dir);
         let doc_root = document_root.replace('\\', "/");
+        let read_timeout = |indent: &str| {
+            if request_timeout > 0 {
+                format!("{}fastcgi_read_timeout {}s;\n", indent, request_timeout)
+            } else {
+                String::new()
+            }
+        };
 
         let mut config = String::new();
 
@@ -73,10 +87,10 @@ impl NginxConfigGenerator {
 
     location ~ \.php$ {{
         try_files $uri =404;
-        fastcgi_pass 127.0.0.1:{};
-        fastcgi_index index.php;
+        fastcgi_pass "{}";
+{}        fastcgi_index index.php;
         include "{}";
-    }}
+{}    }}
 
     location ~ /\.ht {{
         deny all;
@@ -87,8 +101,10 @@ impl NginxConfigGenerator {
             domain,
             log_dir_str,
             domain,
-            php_port,
+            fastcgi_pass,
+            read_timeout("        "),
             fastcgi_params,
+            Self::php_ini_params(ini_overrides),
         ));
 
         // Add phpMyAdmin location if installed
@@ -107,13 +123,13 @@ impl NginxConfigGenerator {
 
         location ~ \.php$ {{
             root "{}";
-            fastcgi_pass 127.0.0.1:{};
-            fastcgi_index index.php;
+            fastcgi_pass "{}";
+{}            fastcgi_index index.php;
             include "{}";
         }}
     }}
 "#,
-                pma_root, pma_root, php_port, fastcgi_params,
+                pma_root, pma_root, fastcgi_pass, read_timeout("            "), fastcgi_params,
             ));
         }
 
@@ -122,6 +138,33 @@ impl NginxConfigGenerator {
         config
     }
 
+    /// `fastcgi_param PHP_VALUE` / `PHP_ADMIN_VALUE` lines; nginx turns the
+    /// `\n` escapes into the newlines PHP-FPM splits directives on.
+    fn php_ini_params(ini_overrides: &SiteIniOverrides) -> String {
+        let encode = |values: &BTreeMap<String, String>| {
+            values
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v.replace('\\', "\\\\")))
+                .collect::<Vec<_>>()
+                .join("\\n")
+        };
+
+        let mut params = String::new();
+        if !ini_overrides.values.is_empty() {
+            params.push_str(&format!(
+                "        fastcgi_param PHP_VALUE \"{}\";\n",
+                encode(&ini_overrides.values)
+            ));
+        }
+        if !ini_overrides.admin_values.is_empty() {
+            params.push_str(&format!(
+                "        fastcgi_param PHP_ADMIN_VALUE \"{}\";\n",
+                encode(&ini_overrides.admin_values)
+            ));
+        }
+        params
+    }
+
     pub fn write_site_config(domain: &str, config_content: &str) -> Result<(), AppError> {
         let config_dir = paths::get_nginx_config_dir().join("sites-enabled");
         std::fs::create_dir_all(&config_dir)?;
@@ -1,9 +1,15 @@
+use crate::config::app_config::AppConfig;
 use crate::config::paths;
 use crate::error::AppError;
+#[cfg(not(target_os = "macos"))]
+use crate::services::archive_extractor::ArchiveExtractor;
 use crate::services::download_manager::DownloadManager;
+#[cfg(not(target_os = "macos"))]
+use crate::services::service_catalogue::ServiceCatalogue;
+use crate::services::php_manager::PhpManager;
 use crate::services::utils;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use tauri::{AppHandle, Emitter};
 
@@ -96,6 +102,24 @@ impl NginxManager {
         }
     }
 
+    /// Build an nginx command bound to our config file.
+    /// On Linux the prebuilt binary has a compile-time prefix pointing at
+    /// system paths, so the prefix and startup error log are overridden too.
+    fn nginx_command(binary: &Path) -> Command {
+        let mut cmd = Command::new(binary);
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            cmd.arg("-p")
+                .arg(paths::get_nginx_dir())
+                .arg("-e")
+                .arg(Self::get_log_dir().join("nginx-error.log"));
+        }
+
+        cmd.arg("-c").arg(Self::get_config_path());
+        cmd
+    }
+
     fn detect_version() -> Option<String> {
         let binary = Self::get_nginx_binary();
         if !binary.exists() {
@@ -167,29 +191,72 @@ impl NginxManager {
             return Err(AppError::Service("Nginx is already installed".to_string()));
         }
 
-        let version = "1.28.2";
+        let version = ServiceCatalogue::latest("nginx")?;
         let url = format!("https://nginx.org/download/nginx-{}.zip", version);
         let archive_path = nginx_dir.join("nginx.zip");
 
-        DownloadManager::download_file(app, "nginx", &url, &archive_path).await?;
-        DownloadManager::extract_zip(app, "nginx", &archive_path, &nginx_dir)?;
+        DownloadManager::download_executable(app, "nginx", &url, &archive_path).await?;
+        ArchiveExtractor::extract(app, "nginx", &archive_path, &nginx_dir, 1)?;
 
         let _ = std::fs::remove_file(&archive_path);
 
-        // Flatten: extracted zip creates nginx-1.28.2/ subdirectory
-        utils::flatten_extracted_dir(&nginx_dir, "nginx-")?;
-
         // Generate default config
         Self::ensure_config()?;
 
+        Self::emit_progress(app, "completed", "Nginx installed");
         Ok(Self::get_info())
     }
 
     // ── Install (fallback) ──────────────────────────────────────────
 
+    // ── Install (Linux — static binary tarball) ─────────────────────
+
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    pub async fn install(_app: &AppHandle) -> Result<NginxInfo, AppError> {
-        Err(AppError::Service("Nginx install not supported on this platform yet".to_string()))
+    pub async fn install(app: &AppHandle) -> Result<NginxInfo, AppError> {
+        let nginx_dir = paths::get_nginx_dir();
+        if Self::get_nginx_binary().exists() {
+            return Err(AppError::Service("Nginx is already installed".to_string()));
+        }
+
+        let version = ServiceCatalogue::latest("nginx")?;
+        let arch = std::env::consts::ARCH;
+        let url = format!(
+            "https://github.com/jirutka/nginx-binaries/releases/download/v{}/nginx-{}-{}-linux.tar.gz",
+            version, version, arch
+        );
+        let archive_path = nginx_dir.join("nginx.tar.gz");
+
+        DownloadManager::download_executable(app, "nginx", &url, &archive_path).await?;
+        ArchiveExtractor::extract(app, "nginx", &archive_path, &nginx_dir, 0)?;
+
+        let _ = std::fs::remove_file(&archive_path);
+
+        // The static build is a single binary — move it into sbin/
+        let binary = Self::get_nginx_binary();
+        let loose_binary = nginx_dir.join("nginx");
+        if !binary.exists() && loose_binary.is_file() {
+            if let Some(parent) = binary.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::rename(&loose_binary, &binary)?;
+        }
+
+        if !binary.exists() {
+            let msg = "Nginx archive did not contain an nginx binary".to_string();
+            Self::emit_progress(app, "failed", &msg);
+            return Err(AppError::Download(msg));
+        }
+
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        // Generate default config
+        Self::ensure_config()?;
+
+        Self::emit_progress(app, "completed", "Nginx installed");
+        Ok(Self::get_info())
     }
 
     // ── read configured port ────────────────────────────────────────
@@ -206,6 +273,40 @@ impl NginxManager {
         (8080, 8443)
     }
 
+    // ── Upgrade to the latest patch release ─────────────────────────
+
+    #[cfg(target_os = "macos")]
+    pub async fn upgrade(app: &AppHandle) -> Result<NginxInfo, AppError> {
+        if !Self::get_nginx_binary().exists() {
+            return Err(AppError::NotFound("Nginx is not installed".to_string()));
+        }
+
+        Self::emit_progress(app, "extracting", "Upgrading Nginx via Homebrew...");
+        if let Err(e) = utils::brew_upgrade("nginx") {
+            Self::emit_progress(app, "failed", &e.to_string());
+            return Err(e);
+        }
+        Self::ensure_config()?;
+
+        Self::emit_progress(app, "completed", "Nginx upgraded");
+        Ok(Self::get_info())
+    }
+
+    /// Swap in the catalogue's latest binaries. Configuration lives in the config dir
+    /// and is untouched; the old tree is restored if the install fails.
+    #[cfg(not(target_os = "macos"))]
+    pub async fn upgrade(app: &AppHandle) -> Result<NginxInfo, AppError> {
+        if !Self::get_nginx_binary().exists() {
+            return Err(AppError::NotFound("Nginx is not installed".to_string()));
+        }
+
+        let (info, previous) = utils::reinstall_dir(&paths::get_nginx_dir(), Self::install(app)).await?;
+        let _ = std::fs::remove_dir_all(&previous);
+
+        log::info!("Upgraded Nginx to {}", info.version.as_deref().unwrap_or("unknown"));
+        Ok(info)
+    }
+
     // ── get_info ────────────────────────────────────────────────────
 
     pub fn get_info() -> NginxInfo {
@@ -262,11 +363,7 @@ impl NginxManager {
             }
         }
 
-        let config = Self::get_config_path();
-
-        let output = Command::new(&binary)
-            .arg("-c")
-            .arg(&config)
+        let output = Self::nginx_command(&binary)
             .output()
             .map_err(|e| AppError::Process(format!("Failed to start Nginx: {}", e)))?;
 
@@ -295,9 +392,7 @@ impl NginxManager {
         let config = Self::get_config_path();
 
         if binary.exists() && config.exists() {
-            let _ = Command::new(&binary)
-                .arg("-c")
-                .arg(&config)
+            let _ = Self::nginx_command(&binary)
                 .arg("-s")
                 .arg("stop")
                 .output();
@@ -319,11 +414,7 @@ impl NginxManager {
         if !binary.exists() {
             return Err(AppError::NotFound("Nginx is not installed".to_string()));
         }
-        let config = Self::get_config_path();
-
-        let output = Command::new(&binary)
-            .arg("-c")
-            .arg(&config)
+        let output = Self::nginx_command(&binary)
             .arg("-s")
             .arg("reload")
             .output()
@@ -340,6 +431,21 @@ impl NginxManager {
         Ok(())
     }
 
+    // ── refresh_php_upstreams ───────────────────────────────────────
+
+    /// Rewrite nginx.conf and all site configs after a PHP-FPM address or
+    /// request timeout changed, reloading nginx if it is running.
+    pub fn refresh_php_upstreams() -> Result<(), AppError> {
+        use crate::services::site_manager::SiteManager;
+
+        Self::ensure_config()?;
+        SiteManager::regenerate_all_configs()?;
+        if Self::get_info().running {
+            Self::reload()?;
+        }
+        Ok(())
+    }
+
     // ── test_config ─────────────────────────────────────────────────
 
     pub fn test_config() -> Result<String, AppError> {
@@ -347,10 +453,7 @@ impl NginxManager {
         if !binary.exists() {
             return Err(AppError::NotFound("Nginx is not installed".to_string()));
         }
-        let config = Self::get_config_path();
-        let output = Command::new(&binary)
-            .arg("-c")
-            .arg(&config)
+        let output = Self::nginx_command(&binary)
             .arg("-t")
             .output()
             .map_err(|e| AppError::Process(format!("Failed to test config: {}", e)))?;
@@ -363,47 +466,29 @@ impl NginxManager {
         }
     }
 
-    // ── detect active PHP-FPM port ────────────────────────────────
-
-    /// Find the port of the first running PHP-FPM, or the first installed PHP version.
-    /// Falls back to 9081 (PHP 8.1) if nothing is found.
-    fn detect_php_fpm_port() -> u16 {
-        let versions = ["8.1", "8.2", "8.3", "8.4"];
-
-        // First, try to find a running PHP-FPM
-        for v in &versions {
-            let pid_path = paths::get_data_dir()
-                .join("data")
-                .join(format!("php-fpm-{}.pid", v));
-            let (alive, _) = utils::read_pid_file(&pid_path);
-            if alive {
-                return utils::php_version_to_port(v);
-            }
-        }
+    // ── detect active PHP-FPM upstream ──────────────────────────────
+
+    /// FastCGI address of the first running PHP-FPM, or the first installed PHP version.
+    /// Falls back to the default PHP version if nothing is found.
+    fn detect_php_fpm_upstream() -> String {
+        let versions = PhpManager::list_versions();
 
-        // Fallback: first installed PHP version (check Homebrew on macOS)
-        #[cfg(target_os = "macos")]
+        // First, try to find a running PHP-FPM, then the first installed version
+        if let Some(php) = versions
+            .iter()
+            .find(|v| v.running)
+            .or_else(|| versions.iter().find(|v| v.installed))
         {
-            let brew_prefix = utils::get_brew_prefix();
-            for v in &versions {
-                let php_bin = brew_prefix
-                    .join("opt")
-                    .join(format!("php@{}", v))
-                    .join("bin")
-                    .join("php");
-                if php_bin.exists() {
-                    return utils::php_version_to_port(v);
-                }
-            }
+            return PhpManager::fastcgi_address(&php.version);
         }
 
         // Default fallback
-        9081
+        PhpManager::fastcgi_address(&AppConfig::load().default_php_version)
     }
 
     // ── phpMyAdmin location block ──────────────────────────────────
 
-    fn get_phpmyadmin_location_block(php_port: u16) -> String {
+    fn get_phpmyadmin_location_block(php_upstream: &str) -> String {
         let pma_dir = paths::get_phpmyadmin_dir();
         if !pma_dir.join("index.php").exists() {
             return String::new();
@@ -423,12 +508,12 @@ impl NginxManager {
 
             location ~ \.php$ {{
                 root "{}";
-                fastcgi_pass 127.0.0.1:{};
+                fastcgi_pass "{}";
                 fastcgi_index index.php;
                 include "{}";
             }}
         }}"#,
-            pma_root, pma_root, php_port, fastcgi_params,
+            pma_root, pma_root, php_upstream, fastcgi_params,
         )
     }
 
@@ -443,17 +528,17 @@ impl NginxManager {
 
         let config_path = Self::get_config_path();
         // Regenerate config if missing, has stale user directive,
-        // PHP port changed, or phpMyAdmin installation state changed
+        // PHP upstream changed, or phpMyAdmin installation state changed
         let pma_installed = paths::get_phpmyadmin_dir().join("index.php").exists();
-        let active_php_port = Self::detect_php_fpm_port();
+        let php_upstream = Self::detect_php_fpm_upstream();
         let needs_config = if config_path.exists() {
             let existing = std::fs::read_to_string(&config_path).unwrap_or_default();
             let has_pma = existing.contains("^~ /phpmyadmin");
-            let current_port_str = format!("fastcgi_pass 127.0.0.1:{}", active_php_port);
+            let current_upstream_str = format!("fastcgi_pass \"{}\"", php_upstream);
             !existing.contains("fastcgi_pass")
                 || existing.contains("\nuser ")
                 || pma_installed != has_pma
-                || !existing.contains(&current_port_str)
+                || !existing.contains(&current_upstream_str)
         } else {
             true
         };
@@ -463,8 +548,7 @@ impl NginxManager {
             let www_dir = paths::get_data_dir().join("www");
             std::fs::create_dir_all(&www_dir)?;
 
-            // Detect active PHP-FPM port and configured listen port
-            let php_port = Self::detect_php_fpm_port();
+            // Configured listen port
             let (listen_port, _ssl_port) = Self::read_configured_ports();
 
             let content = format!(
@@ -505,7 +589,7 @@ http {{
 
         location ~ \.php$ {{
             try_files $uri =404;
-            fastcgi_pass 127.0.0.1:{php_port};
+            fastcgi_pass "{php_upstream}";
             fastcgi_index index.php;
             include "{fastcgi_params}";
         }}
@@ -517,8 +601,8 @@ http {{
 }}
 "#,
                 listen_port = listen_port,
-                php_port = php_port,
-                phpmyadmin_location = Self::get_phpmyadmin_location_block(php_port),
+                php_upstream = php_upstream,
+                phpmyadmin_location = Self::get_phpmyadmin_location_block(&php_upstream),
                 pid = utils::to_forward_slash(&pid_path),
                 error_log = utils::to_forward_slash(&log_dir.join("nginx-error.log")),
                 mime_types = utils::to_forward_slash(&config_dir.join("mime.types")),
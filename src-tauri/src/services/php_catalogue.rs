@@ -0,0 +1,163 @@
+use crate::config::paths;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Release index shipped with the app; a copy under the config dir takes precedence.
+const BUNDLED_INDEX: &str = include_str!("../../resources/php-releases.json");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhpRelease {
+    /// Minor version, e.g. "8.3"
+    pub version: String,
+    /// Latest known patch release, e.g. "8.3.16"
+    pub latest: String,
+    /// "active", "security" or "eol"
+    pub status: String,
+    /// Release date of `latest`, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub released: Option<String>,
+    /// Visual Studio toolchain of the Windows builds ("vc15", "vs16", "vs17")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows_toolchain: Option<String>,
+    /// Expected SHA-256 of this version's downloads, keyed by file name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sha256: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ReleaseIndex {
+    releases: Vec<PhpRelease>,
+}
+
+/// Response of `https://www.php.net/releases/index.php?json&version=X.Y`
+#[derive(Debug, Deserialize)]
+struct PhpNetRelease {
+    version: String,
+    #[serde(default)]
+    date: Option<String>,
+}
+
+pub struct PhpCatalogue;
+
+impl PhpCatalogue {
+    fn get_local_path() -> PathBuf {
+        paths::get_config_dir().join("php-releases.json")
+    }
+
+    fn parse(content: &str) -> Option<Vec<PhpRelease>> {
+        let index: ReleaseIndex = serde_json::from_str(content).ok()?;
+        if index.releases.is_empty() {
+            return None;
+        }
+        Some(index.releases)
+    }
+
+    /// All known PHP releases, oldest first.
+    pub fn releases() -> Vec<PhpRelease> {
+        let local = std::fs::read_to_string(Self::get_local_path())
+            .ok()
+            .and_then(|content| {
+                let parsed = Self::parse(&content);
+                if parsed.is_none() {
+                    log::warn!("Ignoring invalid {}", Self::get_local_path().display());
+                }
+                parsed
+            });
+
+        let mut releases = local
+            .or_else(|| Self::parse(BUNDLED_INDEX))
+            .unwrap_or_default();
+        releases.sort_by_key(|r| Self::sort_key(&r.version));
+        releases
+    }
+
+    /// Minor versions in the catalogue, e.g. ["7.4", "8.0", ...].
+    pub fn versions() -> Vec<String> {
+        Self::releases().into_iter().map(|r| r.version).collect()
+    }
+
+    pub fn find(version: &str) -> Result<PhpRelease, AppError> {
+        Self::releases()
+            .into_iter()
+            .find(|r| r.version == version)
+            .ok_or_else(|| AppError::NotFound(format!("PHP {} is not in the release catalogue", version)))
+    }
+
+    /// Shipped digest of a PHP download, by archive file name.
+    pub fn sha256(file_name: &str) -> Option<String> {
+        Self::releases()
+            .into_iter()
+            .find_map(|r| r.sha256.get(file_name).cloned())
+    }
+
+    fn sort_key(version: &str) -> (u32, u32, u32) {
+        let mut parts = version.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+        (
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+        )
+    }
+
+    /// Whether patch release `a` is newer than `b` (e.g. "8.3.16" > "8.3.9").
+    pub fn is_newer(a: &str, b: &str) -> bool {
+        Self::sort_key(a) > Self::sort_key(b)
+    }
+
+    /// Refresh patch levels from php.net and save the result as the local
+    /// catalogue. A new minor version announced upstream is appended.
+    pub async fn refresh() -> Result<Vec<PhpRelease>, AppError> {
+        let client = reqwest::Client::builder()
+            .user_agent("LokcalDev/0.1")
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| AppError::Download(e.to_string()))?;
+
+        let mut releases = Self::releases();
+        for release in releases.iter_mut() {
+            match Self::fetch_latest(&client, &release.version).await {
+                Ok(Some(upstream)) if Self::is_newer(&upstream.version, &release.latest) => {
+                    log::info!("PHP {}: {} -> {}", release.version, release.latest, upstream.version);
+                    release.latest = upstream.version;
+                    release.released = upstream.date;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Could not refresh PHP {}: {}", release.version, e),
+            }
+        }
+
+        // `version=8` resolves to the newest 8.x release, which reveals new minors
+        if let Ok(Some(upstream)) = Self::fetch_latest(&client, "8").await {
+            let minor = upstream.version.splitn(3, '.').take(2).collect::<Vec<_>>().join(".");
+            if !releases.iter().any(|r| r.version == minor) {
+                let windows_toolchain = releases.last().and_then(|r| r.windows_toolchain.clone());
+                releases.push(PhpRelease {
+                    version: minor,
+                    latest: upstream.version,
+                    status: "active".to_string(),
+                    released: upstream.date,
+                    windows_toolchain,
+                    sha256: BTreeMap::new(),
+                });
+            }
+        }
+
+        let content = serde_json::to_string_pretty(&ReleaseIndex { releases: releases.clone() })?;
+        std::fs::create_dir_all(paths::get_config_dir())?;
+        std::fs::write(Self::get_local_path(), content)?;
+        Ok(releases)
+    }
+
+    async fn fetch_latest(client: &reqwest::Client, version: &str) -> Result<Option<PhpNetRelease>, reqwest::Error> {
+        let url = format!("https://www.php.net/releases/index.php?json&version={}", version);
+        let response = client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        // Unknown versions return `{"error": ...}`, which fails to parse here
+        Ok(response.json::<PhpNetRelease>().await.ok())
+    }
+}
@@ -1,38 +1,78 @@
+use crate::config::app_config::AppConfig;
 use crate::config::paths;
 use crate::error::AppError;
+#[cfg(not(target_os = "macos"))]
+use crate::services::archive_extractor::ArchiveExtractor;
 use crate::services::download_manager::DownloadManager;
+use crate::services::ini_document::{IniChange, IniDocument};
+use crate::services::ini_presets::IniPresets;
+use crate::services::ini_snapshots::{IniSnapshot, IniSnapshots};
+use crate::services::mail_catcher::MailCatcher;
+use crate::services::nginx_manager::NginxManager;
+use crate::services::php_catalogue::{PhpCatalogue, PhpRelease};
+use crate::services::port_registry::PortRegistry;
+use crate::services::process_log::ProcessLog;
+use crate::services::site_manager::{Site, SiteManager};
 use crate::services::utils;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+#[cfg(target_os = "macos")]
+use std::process::Stdio;
 use tauri::{AppHandle, Emitter};
 
-const PHP_VERSIONS: &[&str] = &["8.1", "8.2", "8.3", "8.4"];
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PhpVersion {
     pub version: String,
     pub installed: bool,
     pub running: bool,
     pub port: u16,
+    /// Unix socket FPM listens on instead of `port`, if enabled
+    pub socket: Option<String>,
     pub pid: Option<u32>,
     pub path: Option<String>,
+    /// Installed patch release, e.g. "8.3.16"
+    pub full_version: Option<String>,
+    /// Latest patch release in the catalogue
+    pub latest: String,
+    /// Upstream support status: "active", "security" or "eol"
+    pub status: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PhpIniDirective {
     pub key: String,
     pub value: String,
     pub section: String,
+    /// Value reported by `php -i`, when PHP could be queried
+    pub effective_value: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PhpExtension {
     pub name: String,
     pub enabled: bool,
+    /// Compiled into the binary; cannot be toggled
     pub builtin: bool,
+    /// Loaded with `zend_extension=` (opcache, xdebug)
+    pub zend: bool,
+    /// Ini file with the extension's `extension=` line, active or commented
+    pub ini_file: Option<String>,
+    /// Module in extension_dir
+    pub file: Option<String>,
 }
 
+/// Extensions that must be loaded with `zend_extension=`.
+const ZEND_EXTENSIONS: [&str; 2] = ["opcache", "xdebug"];
+
+/// `pm.status_path` of every pool. Only reachable over FastCGI; nginx
+/// never forwards it.
+pub const FPM_STATUS_PATH: &str = "/fpm-status";
+
 pub struct PhpManager;
 
 impl PhpManager {
@@ -46,7 +86,7 @@ impl PhpManager {
     }
 
     #[cfg(target_os = "macos")]
-    fn get_php_binary(version: &str) -> PathBuf {
+    pub(crate) fn get_php_binary(version: &str) -> PathBuf {
         Self::get_php_prefix(version).join("bin").join("php")
     }
 
@@ -56,7 +96,7 @@ impl PhpManager {
     }
 
     #[cfg(target_os = "macos")]
-    fn get_php_ini_path(version: &str) -> PathBuf {
+    pub(crate) fn get_php_ini_path(version: &str) -> PathBuf {
         utils::get_brew_prefix()
             .join("etc")
             .join("php")
@@ -78,7 +118,7 @@ impl PhpManager {
     }
 
     #[cfg(target_os = "windows")]
-    fn get_php_binary(version: &str) -> PathBuf {
+    pub(crate) fn get_php_binary(version: &str) -> PathBuf {
         Self::get_version_dir(version).join("php.exe")
     }
 
@@ -88,7 +128,7 @@ impl PhpManager {
     }
 
     #[cfg(target_os = "windows")]
-    fn get_php_ini_path(version: &str) -> PathBuf {
+    pub(crate) fn get_php_ini_path(version: &str) -> PathBuf {
         Self::get_version_dir(version).join("php.ini")
     }
 
@@ -100,7 +140,7 @@ impl PhpManager {
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    fn get_php_binary(version: &str) -> PathBuf {
+    pub(crate) fn get_php_binary(version: &str) -> PathBuf {
         Self::get_version_dir(version).join("bin").join("php")
     }
 
@@ -110,7 +150,7 @@ impl PhpManager {
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    fn get_php_ini_path(version: &str) -> PathBuf {
+    pub(crate) fn get_php_ini_path(version: &str) -> PathBuf {
         Self::get_version_dir(version).join("lib").join("php.ini")
     }
 
@@ -122,24 +162,60 @@ impl PhpManager {
             .join(format!("php-fpm-{}.pid", version))
     }
 
-    fn get_fpm_log_path(version: &str) -> PathBuf {
+    pub(crate) fn get_fpm_log_path(version: &str) -> PathBuf {
         paths::get_logs_dir().join(format!("php-fpm-{}.log", version))
     }
 
-    // --- Download URLs (Windows only) ---
+    pub fn get_fpm_slowlog_path(version: &str) -> PathBuf {
+        paths::get_logs_dir().join(format!("php-fpm-{}-slow.log", version))
+    }
+
+    pub fn get_site_slowlog_path(site: &Site) -> PathBuf {
+        paths::get_logs_dir().join(format!("php-fpm-{}-slow.log", site.domain))
+    }
+
+    /// Directory holding the per-site pool files of a version's FPM master.
+    fn get_site_pool_dir(version: &str) -> PathBuf {
+        paths::get_data_dir()
+            .join("config")
+            .join(format!("php-fpm-{}.d", version))
+    }
+
+    /// Socket of a site's dedicated pool. Kept short: Unix socket paths are
+    /// limited to ~104 bytes on macOS.
+    pub fn get_site_pool_socket(site_id: &str) -> PathBuf {
+        let short_id: String = site_id.chars().take(8).collect();
+        paths::get_run_dir().join(format!("site-{}.sock", short_id))
+    }
+
+    // --- Download URLs (Windows / Linux) ---
 
     #[cfg(target_os = "windows")]
-    fn get_download_url(version: &str) -> String {
-        let full_version = match version {
-            "8.1" => "8.1.31",
-            "8.2" => "8.2.28",
-            "8.3" => "8.3.16",
-            "8.4" => "8.4.4",
-            _ => "8.3.16",
-        };
+    fn get_download_url(release: &PhpRelease) -> String {
+        // Only supported branches stay in releases/; everything else moves to archives/
+        let dir = if release.status == "eol" { "releases/archives" } else { "releases" };
         format!(
-            "https://windows.php.net/downloads/releases/php-{}-nts-Win32-vs17-x64.zip",
-            full_version
+            "https://windows.php.net/downloads/{}/php-{}-nts-Win32-{}-x64.zip",
+            dir,
+            release.latest,
+            release.windows_toolchain.as_deref().unwrap_or("vs17")
+        )
+    }
+
+    /// Static PHP builds ship `php` and `php-fpm` as separate single-binary tarballs.
+    /// Returns (cli_url, fpm_url).
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn get_download_urls(release: &PhpRelease) -> (String, String) {
+        let arch = if cfg!(target_arch = "aarch64") { "aarch64" } else { "x86_64" };
+        (
+            format!(
+                "https://dl.static-php.dev/static-php-cli/common/php-{}-cli-linux-{}.tar.gz",
+                release.latest, arch
+            ),
+            format!(
+                "https://dl.static-php.dev/static-php-cli/common/php-{}-fpm-linux-{}.tar.gz",
+                release.latest, arch
+            ),
         )
     }
 
@@ -155,42 +231,51 @@ impl PhpManager {
     // --- Core functions ---
 
     pub fn list_versions() -> Vec<PhpVersion> {
-        PHP_VERSIONS
+        PhpCatalogue::releases()
             .iter()
-            .map(|v| {
-                let installed = Self::get_php_binary(v).exists();
-                let pid_file = Self::get_fpm_pid_path(v);
-                let (running, pid) = if installed {
-                    utils::read_pid_file(&pid_file)
-                } else {
-                    (false, None)
-                };
-
-                let path = if installed {
-                    Self::get_php_binary(v)
-                        .parent()
-                        .and_then(|p| p.parent())
-                        .map(|p| p.to_string_lossy().to_string())
-                } else {
-                    None
-                };
-
-                PhpVersion {
-                    version: v.to_string(),
-                    installed,
-                    running,
-                    port: utils::php_version_to_port(v),
-                    pid,
-                    path,
-                }
-            })
+            .map(Self::version_info)
             .collect()
     }
 
+    fn version_info(release: &PhpRelease) -> PhpVersion {
+        let v = release.version.as_str();
+        let installed = Self::get_php_binary(v).exists();
+        let pid_file = Self::get_fpm_pid_path(v);
+        let (running, pid) = if installed {
+            utils::read_pid_file(&pid_file)
+        } else {
+            (false, None)
+        };
+
+        let path = if installed {
+            Self::get_php_binary(v)
+                .parent()
+                .and_then(|p| p.parent())
+                .map(|p| p.to_string_lossy().to_string())
+        } else {
+            None
+        };
+
+        PhpVersion {
+            version: v.to_string(),
+            installed,
+            running,
+            port: PortRegistry::php_fpm_port(v),
+            socket: Self::uses_unix_socket()
+                .then(|| Self::get_fpm_socket_path(v).to_string_lossy().to_string()),
+            pid,
+            path,
+            full_version: if installed { Self::installed_patch(v) } else { None },
+            latest: release.latest.clone(),
+            status: release.status.clone(),
+        }
+    }
+
     // --- macOS: Install via Homebrew ---
 
     #[cfg(target_os = "macos")]
     pub async fn install_version(app: &AppHandle, version: &str) -> Result<PhpVersion, AppError> {
+        let release = PhpCatalogue::find(version)?;
         if Self::is_installed_via_brew(version) {
             return Err(AppError::Service(format!(
                 "PHP {} is already installed",
@@ -249,27 +334,18 @@ impl PhpManager {
         }
 
         // Setup custom FPM pool config with our port
-        Self::ensure_fpm_pool_config(version)?;
+        Self::ensure_fpm_pool_config(version, PortRegistry::assign_php_fpm_port(version)?)?;
 
         Self::emit_progress(app, &dl_id, "completed", &format!("PHP {} installed", version));
 
-        Ok(PhpVersion {
-            version: version.to_string(),
-            installed: true,
-            running: false,
-            port: utils::php_version_to_port(version),
-            pid: None,
-            path: Self::get_php_binary(version)
-                .parent()
-                .and_then(|p| p.parent())
-                .map(|p| p.to_string_lossy().to_string()),
-        })
+        Ok(Self::version_info(&release))
     }
 
     // --- Windows: Install via direct download ---
 
     #[cfg(target_os = "windows")]
     pub async fn install_version(app: &AppHandle, version: &str) -> Result<PhpVersion, AppError> {
+        let release = PhpCatalogue::find(version)?;
         let version_dir = Self::get_version_dir(version);
         if version_dir.exists() {
             return Err(AppError::Service(format!(
@@ -278,30 +354,155 @@ impl PhpManager {
             )));
         }
 
-        let url = Self::get_download_url(version);
+        let url = Self::get_download_url(&release);
         let archive_path = paths::get_php_dir().join(format!("php-{}.zip", version));
 
-        DownloadManager::download_file(app, &format!("php-{}", version), &url, &archive_path).await?;
-        DownloadManager::extract_zip(app, &format!("php-{}", version), &archive_path, &version_dir)?;
+        DownloadManager::download_executable(app, &format!("php-{}", version), &url, &archive_path).await?;
+        ArchiveExtractor::extract(app, &format!("php-{}", version), &archive_path, &version_dir, 0)?;
 
         let _ = std::fs::remove_file(&archive_path);
         Self::ensure_php_ini_default(version)?;
+        let _ = std::fs::write(Self::get_patch_marker_path(version), &release.latest);
+        PortRegistry::assign_php_fpm_port(version)?;
 
-        Ok(PhpVersion {
-            version: version.to_string(),
-            installed: true,
-            running: false,
-            port: utils::php_version_to_port(version),
-            pid: None,
-            path: Some(version_dir.to_string_lossy().to_string()),
-        })
+        Self::emit_progress(app, &format!("php-{}", version), "completed", &format!("PHP {} installed", version));
+
+        Ok(Self::version_info(&release))
     }
 
     // --- Fallback for other platforms ---
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    pub async fn install_version(_app: &AppHandle, _version: &str) -> Result<PhpVersion, AppError> {
-        Err(AppError::Service("PHP install not supported on this platform yet".to_string()))
+    pub async fn install_version(app: &AppHandle, version: &str) -> Result<PhpVersion, AppError> {
+        let release = PhpCatalogue::find(version)?;
+        if Self::get_php_binary(version).exists() {
+            return Err(AppError::Service(format!(
+                "PHP {} is already installed",
+                version
+            )));
+        }
+
+        let dl_id = format!("php-{}", version);
+        let version_dir = Self::get_version_dir(version);
+        let (cli_url, fpm_url) = Self::get_download_urls(&release);
+        let cli_archive = paths::get_php_dir().join(format!("php-{}-cli.tar.gz", version));
+        let fpm_archive = paths::get_php_dir().join(format!("php-{}-fpm.tar.gz", version));
+
+        // CLI binary → <version>/bin/php
+        DownloadManager::download_executable(app, &dl_id, &cli_url, &cli_archive).await?;
+        ArchiveExtractor::extract(app, &dl_id, &cli_archive, &version_dir.join("bin"), 0)?;
+        let _ = std::fs::remove_file(&cli_archive);
+
+        // FPM binary → <version>/sbin/php-fpm
+        DownloadManager::download_executable(app, &dl_id, &fpm_url, &fpm_archive).await?;
+        ArchiveExtractor::extract(app, &dl_id, &fpm_archive, &version_dir.join("sbin"), 0)?;
+        let _ = std::fs::remove_file(&fpm_archive);
+
+        let php_bin = Self::get_php_binary(version);
+        let fpm_bin = Self::get_php_fpm_binary(version);
+        if !php_bin.exists() || !fpm_bin.exists() {
+            let _ = std::fs::remove_dir_all(&version_dir);
+            let msg = format!("PHP {} archive did not contain php/php-fpm binaries", version);
+            Self::emit_progress(app, &dl_id, "failed", &msg);
+            return Err(AppError::Download(msg));
+        }
+
+        // Set executable permission
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&php_bin, std::fs::Permissions::from_mode(0o755))?;
+            std::fs::set_permissions(&fpm_bin, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        Self::ensure_php_ini_default(version)?;
+        let _ = std::fs::write(Self::get_patch_marker_path(version), &release.latest);
+        Self::ensure_fpm_pool_config(version, PortRegistry::assign_php_fpm_port(version)?)?;
+
+        Self::emit_progress(app, &dl_id, "completed", &format!("PHP {} installed", version));
+
+        Ok(Self::version_info(&release))
+    }
+
+    // --- Installed patch level ---
+
+    #[cfg(target_os = "macos")]
+    pub fn installed_patch(version: &str) -> Option<String> {
+        utils::brew_installed_version(&format!("php@{}", version))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn get_patch_marker_path(version: &str) -> PathBuf {
+        Self::get_version_dir(version).join(".version")
+    }
+
+    /// Patch release recorded at install time. Installs that predate the
+    /// marker are probed once with `php -r` and the result recorded.
+    #[cfg(not(target_os = "macos"))]
+    pub fn installed_patch(version: &str) -> Option<String> {
+        let marker = Self::get_patch_marker_path(version);
+        if let Ok(content) = std::fs::read_to_string(&marker) {
+            return Some(content.trim().to_string());
+        }
+        let output = Command::new(Self::get_php_binary(version))
+            .args(["-n", "-r", "echo PHP_VERSION;"])
+            .output()
+            .ok()?;
+        let patch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || patch.is_empty() {
+            return None;
+        }
+        let _ = std::fs::write(&marker, &patch);
+        Some(patch)
+    }
+
+    // --- Upgrade to the latest patch release ---
+
+    #[cfg(target_os = "macos")]
+    pub async fn upgrade_version(app: &AppHandle, version: &str) -> Result<PhpVersion, AppError> {
+        let release = PhpCatalogue::find(version)?;
+        if !Self::is_installed_via_brew(version) {
+            return Err(AppError::NotFound(format!("PHP {} is not installed", version)));
+        }
+
+        let dl_id = format!("php-{}", version);
+        Self::emit_progress(app, &dl_id, "extracting", &format!("Upgrading PHP {} via Homebrew...", version));
+        // Homebrew keeps a modified php.ini and writes the new default next to it
+        if let Err(e) = utils::brew_upgrade(&format!("shivammathur/php/php@{}", version)) {
+            Self::emit_progress(app, &dl_id, "failed", &e.to_string());
+            return Err(e);
+        }
+        Self::ensure_fpm_pool_config(version, PortRegistry::php_fpm_port(version))?;
+
+        Self::emit_progress(app, &dl_id, "completed", &format!("PHP {} upgraded", version));
+        Ok(Self::version_info(&release))
+    }
+
+    /// Replace the binaries of an installed version with the catalogue's latest
+    /// patch release. php.ini and any extension files not shipped by the new
+    /// build are carried over; the old tree is restored if the install fails.
+    #[cfg(not(target_os = "macos"))]
+    pub async fn upgrade_version(app: &AppHandle, version: &str) -> Result<PhpVersion, AppError> {
+        let release = PhpCatalogue::find(version)?;
+        if !Self::get_php_binary(version).exists() {
+            return Err(AppError::NotFound(format!("PHP {} is not installed", version)));
+        }
+
+        let version_dir = Self::get_version_dir(version);
+        let (info, previous) = utils::reinstall_dir(&version_dir, Self::install_version(app, version)).await?;
+
+        let ini_path = Self::get_php_ini_path(version);
+        if let Ok(relative) = ini_path.strip_prefix(&version_dir) {
+            let old_ini = previous.join(relative);
+            if old_ini.exists() {
+                std::fs::copy(&old_ini, &ini_path)?;
+            }
+        }
+        utils::copy_missing_files(&previous.join("ext"), &version_dir.join("ext"))?;
+        utils::copy_missing_files(&previous.join("conf.d"), &version_dir.join("conf.d"))?;
+        let _ = std::fs::remove_dir_all(&previous);
+
+        log::info!("Upgraded PHP {} to {}", version, release.latest);
+        Ok(info)
     }
 
     // --- Remove ---
@@ -350,57 +551,184 @@ impl PhpManager {
             )));
         }
 
-        let port = utils::php_version_to_port(version);
+        let port = if Self::uses_unix_socket() {
+            // A live master would pass the health check below; leave it alone
+            if Self::is_fpm_accepting(version) {
+                return Err(AppError::Process(format!(
+                    "Cannot start PHP-FPM {}: {} is already in use",
+                    version,
+                    Self::fastcgi_address(version)
+                )));
+            }
+            // Nothing answers, so the socket was left behind by a crashed
+            // master and would block the bind
+            std::fs::create_dir_all(paths::get_run_dir())?;
+            let _ = std::fs::remove_file(Self::get_fpm_socket_path(version));
+            PortRegistry::php_fpm_port(version)
+        } else {
+            // Moves off a port that something else already listens on
+            let (port, moved) = PortRegistry::reserve_php_fpm_port(version)?;
+            if moved {
+                // Sites still point at the old port
+                if let Err(e) = NginxManager::refresh_php_upstreams() {
+                    log::warn!("Could not update nginx configs for PHP {}: {}", version, e);
+                }
+            }
+            port
+        };
+
+        let service_id = format!("php-fpm-{}", version);
+
+        if let Err(e) = MailCatcher::configure_php(version) {
+            log::warn!("Could not point mail() of PHP {} at the mail catcher: {}", version, e);
+        }
 
         #[cfg(target_os = "windows")]
-        let child = {
+        let mut child = {
             // Windows: php-cgi.exe in FastCGI mode bound to a port
-            Command::new(&fpm_bin)
-                .arg("-b")
-                .arg(format!("127.0.0.1:{}", port))
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()
+            let mut cmd = Command::new(&fpm_bin);
+            cmd.arg("-b").arg(format!("127.0.0.1:{}", port));
+            ProcessLog::prepare(&mut cmd);
+            cmd.spawn()
                 .map_err(|e| {
                     AppError::Process(format!("Failed to start PHP-CGI {}: {}", version, e))
                 })?
         };
 
         #[cfg(not(target_os = "windows"))]
-        let child = {
+        let mut child = {
             // Unix: php-fpm with custom config
-            Self::ensure_fpm_pool_config(version)?;
+            Self::ensure_fpm_pool_config(version, port)?;
             let lokcaldev_conf = paths::get_data_dir()
                 .join("config")
                 .join(format!("php-fpm-{}.conf", version));
 
-            Command::new(&fpm_bin)
-                .arg("--fpm-config")
+            let mut cmd = Command::new(&fpm_bin);
+            cmd.arg("--fpm-config")
                 .arg(&lokcaldev_conf)
-                .arg("--nodaemonize")
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()
+                .arg("--nodaemonize");
+            ProcessLog::prepare(&mut cmd);
+
+            // Static builds have no compiled-in ini path — point them at ours
+            #[cfg(not(target_os = "macos"))]
+            {
+                cmd.arg("-c").arg(Self::get_php_ini_path(version));
+            }
+
+            cmd.spawn()
                 .map_err(|e| {
                     AppError::Process(format!("Failed to start PHP-FPM {}: {}", version, e))
                 })?
         };
 
         let pid = child.id();
+        if let Err(e) = ProcessLog::capture(&service_id, &mut child) {
+            log::warn!("Could not capture output of PHP-FPM {}: {}", version, e);
+        }
 
         // Write PID file
         let pid_path = Self::get_fpm_pid_path(version);
         let _ = std::fs::write(&pid_path, pid.to_string());
 
+        // Health check: give the pool a moment to start accepting requests,
+        // failing if it exits instead (bad ini, missing extension, port in use)
+        if let Some(status) = ProcessLog::wait_for_startup(&mut child, || Self::is_fpm_accepting(version)) {
+            let _ = std::fs::remove_file(&pid_path);
+            return Err(ProcessLog::startup_error(
+                &service_id,
+                &format!("PHP-FPM {}", version),
+                status,
+                Some(&Self::get_fpm_log_path(version)),
+            ));
+        }
+        if !Self::is_fpm_accepting(version) {
+            log::warn!(
+                "PHP-FPM {} is not accepting connections on {} yet",
+                version,
+                Self::fastcgi_address(version)
+            );
+        }
+
         log::info!(
-            "Started PHP-FPM {} on port {} (PID: {})",
+            "Started PHP-FPM {} on {} (PID: {})",
             version,
-            port,
+            Self::fastcgi_address(version),
             pid
         );
         Ok((child, pid))
     }
 
+    // --- FastCGI transport ---
+
+    pub fn is_fpm_running(version: &str) -> bool {
+        utils::read_pid_file(&Self::get_fpm_pid_path(version)).0
+    }
+
+    /// Whether FPM listens on a Unix socket instead of a TCP port
+    /// (`php_fpm_unix_sockets`; php-cgi on Windows always uses TCP).
+    pub fn uses_unix_socket() -> bool {
+        cfg!(unix) && AppConfig::load().php_fpm_unix_sockets
+    }
+
+    pub fn get_fpm_socket_path(version: &str) -> PathBuf {
+        paths::get_run_dir().join(format!("php-fpm-{}.sock", version))
+    }
+
+    /// Address nginx passes PHP requests of `version` to, as used by `fastcgi_pass`.
+    pub fn fastcgi_address(version: &str) -> String {
+        if Self::uses_unix_socket() {
+            format!("unix:{}", utils::to_forward_slash(&Self::get_fpm_socket_path(version)))
+        } else {
+            format!("127.0.0.1:{}", PortRegistry::php_fpm_port(version))
+        }
+    }
+
+    /// TCP port of the FPM service, `None` when it listens on a socket.
+    pub fn service_port(version: &str) -> Option<u16> {
+        if Self::uses_unix_socket() {
+            None
+        } else {
+            Some(PortRegistry::php_fpm_port(version))
+        }
+    }
+
+    /// Whether the FPM of `version` accepts connections on its listen address.
+    pub fn is_fpm_accepting(version: &str) -> bool {
+        #[cfg(unix)]
+        if Self::uses_unix_socket() {
+            return std::os::unix::net::UnixStream::connect(Self::get_fpm_socket_path(version)).is_ok();
+        }
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], PortRegistry::php_fpm_port(version)));
+        std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_millis(300)).is_ok()
+    }
+
+    /// Gracefully reload a running PHP-FPM master so it rereads its pools.
+    #[cfg(unix)]
+    pub fn reload_fpm(version: &str) -> Result<(), AppError> {
+        let (running, pid) = utils::read_pid_file(&Self::get_fpm_pid_path(version));
+        if let (true, Some(pid)) = (running, pid) {
+            let output = Command::new("kill")
+                .args(["-USR2", &pid.to_string()])
+                .output()
+                .map_err(|e| AppError::Process(format!("Failed to reload PHP-FPM {}: {}", version, e)))?;
+            if !output.status.success() {
+                return Err(AppError::Process(format!(
+                    "Failed to reload PHP-FPM {}: {}",
+                    version,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+            log::info!("Reloaded PHP-FPM {}", version);
+        }
+        Ok(())
+    }
+
+    /// php-cgi has no pools or reload signal; changes apply on restart.
+    #[cfg(not(unix))]
+    pub fn reload_fpm(_version: &str) -> Result<(), AppError> {
+        Ok(())
+    }
+
     pub fn stop_fpm(version: &str) -> Result<(), AppError> {
         let pid_path = Self::get_fpm_pid_path(version);
         if pid_path.exists() {
@@ -417,40 +745,124 @@ impl PhpManager {
 
     // --- FPM config: LokcalDev's own config in data dir ---
 
-    fn ensure_fpm_pool_config(version: &str) -> Result<(), AppError> {
+    fn ensure_fpm_pool_config(version: &str, port: u16) -> Result<(), AppError> {
         let config_dir = paths::get_data_dir().join("config");
         std::fs::create_dir_all(&config_dir)?;
 
         let conf_path = config_dir.join(format!("php-fpm-{}.conf", version));
-        let port = utils::php_version_to_port(version);
         let pid_path = Self::get_fpm_pid_path(version);
         let log_path = Self::get_fpm_log_path(version);
 
-        // Always regenerate to ensure correct port
-        let content = format!(
+        let include = if Self::write_site_pools(version)? {
+            format!("include = \"{}/*.conf\"\n", Self::get_site_pool_dir(version).display())
+        } else {
+            String::new()
+        };
+
+        // Always regenerated from settings.toml (listen address, pm settings, site pools)
+        let settings = AppConfig::load().fpm_settings(version);
+        let listen = if Self::uses_unix_socket() {
+            format!("\"{}\"\nlisten.mode = 0660", Self::get_fpm_socket_path(version).display())
+        } else {
+            format!("127.0.0.1:{}", port)
+        };
+        let mut content = format!(
             "[global]\n\
              pid = {}\n\
              error_log = {}\n\
-             log_level = notice\n\
+             log_level = {}\n\
              daemonize = no\n\
+             {}\
              \n\
              [www]\n\
-             listen = 127.0.0.1:{}\n\
-             pm = dynamic\n\
-             pm.max_children = 5\n\
-             pm.start_servers = 2\n\
-             pm.min_spare_servers = 1\n\
-             pm.max_spare_servers = 3\n",
+             listen = {}\n\
+             pm = {}\n\
+             pm.max_children = {}\n",
             pid_path.display(),
             log_path.display(),
-            port,
+            settings.log_level,
+            include,
+            listen,
+            settings.pm,
+            settings.max_children,
         );
+        match settings.pm.as_str() {
+            "dynamic" => content.push_str(&format!(
+                "pm.start_servers = {}\npm.min_spare_servers = {}\npm.max_spare_servers = {}\n",
+                settings.start_servers, settings.min_spare_servers, settings.max_spare_servers,
+            )),
+            "ondemand" => content.push_str(&format!(
+                "pm.process_idle_timeout = {}s\n",
+                settings.process_idle_timeout,
+            )),
+            _ => {}
+        }
+        content.push_str(&format!(
+            "pm.max_requests = {}\nrequest_terminate_timeout = {}s\npm.status_path = {}\n",
+            settings.max_requests, settings.request_terminate_timeout, FPM_STATUS_PATH,
+        ));
+        if settings.slowlog_timeout > 0 {
+            content.push_str(&format!(
+                "request_slowlog_timeout = {}s\nslowlog = \"{}\"\n",
+                settings.slowlog_timeout,
+                Self::get_fpm_slowlog_path(version).display(),
+            ));
+        }
         std::fs::write(&conf_path, content)?;
 
         Ok(())
     }
 
-    // --- php.ini fallback for Windows ---
+    /// Write one pool file per active site on `version` that has its own pool.
+    /// Returns whether any were written.
+    fn write_site_pools(version: &str) -> Result<bool, AppError> {
+        let pool_dir = Self::get_site_pool_dir(version);
+        if pool_dir.exists() {
+            std::fs::remove_dir_all(&pool_dir)?;
+        }
+
+        let sites: Vec<Site> = SiteManager::list()?
+            .into_iter()
+            .filter(|s| s.php_version == version && SiteManager::has_own_pool(s))
+            .collect();
+        if sites.is_empty() {
+            return Ok(false);
+        }
+
+        std::fs::create_dir_all(&pool_dir)?;
+        std::fs::create_dir_all(paths::get_run_dir())?;
+        for site in &sites {
+            let Some(pool) = &site.pool else { continue };
+            let pool_name = format!("site-{}", site.id.chars().take(8).collect::<String>());
+            let socket = Self::get_site_pool_socket(&site.id);
+            let slowlog = Self::get_site_slowlog_path(site);
+            let content = pool.to_fpm_config(
+                &pool_name,
+                &socket.to_string_lossy(),
+                &slowlog.to_string_lossy(),
+            );
+            std::fs::write(pool_dir.join(format!("{}.conf", pool_name)), content)?;
+        }
+        Ok(true)
+    }
+
+    /// Regenerate the FPM config of `version` after its settings or a site
+    /// pool changed, and reload the master if it is running.
+    #[cfg(unix)]
+    pub fn apply_pool_config(version: &str) -> Result<(), AppError> {
+        if !Self::get_php_fpm_binary(version).exists() {
+            return Ok(());
+        }
+        Self::ensure_fpm_pool_config(version, PortRegistry::php_fpm_port(version))?;
+        Self::reload_fpm(version)
+    }
+
+    #[cfg(not(unix))]
+    pub fn apply_pool_config(_version: &str) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    // --- php.ini fallback for Windows / Linux ---
 
     #[allow(dead_code)]
     fn ensure_php_ini_default(version: &str) -> Result<(), AppError> {
@@ -484,116 +896,381 @@ impl PhpManager {
 
     // --- INI read/write ---
 
+    fn load_ini(version: &str) -> Result<(PathBuf, IniDocument), AppError> {
+        let ini_path = Self::get_php_ini_path(version);
+        if !ini_path.exists() {
+            return Err(AppError::NotFound(format!(
+                "php.ini not found for PHP {}",
+                version
+            )));
+        }
+        let doc = IniDocument::load(&ini_path)?;
+        Ok((ini_path, doc))
+    }
+
+    /// Write php.ini, snapshotting the previous content first.
+    fn save_ini(version: &str, ini_path: &Path, doc: &IniDocument, reason: &str) -> Result<(), AppError> {
+        IniSnapshots::capture(version, ini_path, reason)?;
+        doc.save(ini_path)
+    }
+
     pub fn get_ini(version: &str) -> Result<Vec<PhpIniDirective>, AppError> {
         let ini_path = Self::get_php_ini_path(version);
         if !ini_path.exists() {
             return Ok(Vec::new());
         }
 
-        let content = std::fs::read_to_string(&ini_path)?;
-        let mut directives = Vec::new();
-        let mut current_section = "PHP".to_string();
+        let doc = IniDocument::load(&ini_path)?;
+        let effective = Self::get_effective_ini(version).unwrap_or_default();
+        Ok(doc
+            .entries()
+            .into_iter()
+            .map(|entry| PhpIniDirective {
+                effective_value: effective.get(&entry.key).cloned(),
+                key: entry.key,
+                value: entry.value,
+                section: entry.section,
+            })
+            .collect())
+    }
 
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with(';') {
-                continue;
-            }
-            if trimmed.starts_with('[') && trimmed.ends_with(']') {
-                current_section = trimmed[1..trimmed.len() - 1].to_string();
-                continue;
-            }
-            if let Some((key, value)) = trimmed.split_once('=') {
-                directives.push(PhpIniDirective {
-                    key: key.trim().to_string(),
-                    value: value.trim().to_string(),
-                    section: current_section.clone(),
-                });
-            }
+    /// Set a directive in place (see `IniDocument::set`). Without a section
+    /// the directive's existing or conventional section is used.
+    pub fn set_ini_directive(version: &str, key: &str, value: &str, section: Option<&str>) -> Result<(), AppError> {
+        Self::validate_ini_key(key, section)?;
+        Self::validate_ini_value(key, value)?;
+        let (ini_path, mut doc) = Self::load_ini(version)?;
+        doc.set(section, key, value);
+        Self::save_ini(version, &ini_path, &doc, &format!("set {}", key))
+    }
+
+    /// Comment a directive out so PHP falls back to its built-in default.
+    pub fn remove_ini_directive(version: &str, key: &str, section: Option<&str>) -> Result<(), AppError> {
+        Self::validate_ini_key(key, section)?;
+        let (ini_path, mut doc) = Self::load_ini(version)?;
+        if !doc.remove(section, key, None) {
+            return Err(AppError::NotFound(format!("{} is not set in php.ini", key)));
         }
+        Self::save_ini(version, &ini_path, &doc, &format!("remove {}", key))
+    }
 
-        Ok(directives)
+    /// Enable the commented default of a directive. Returns its value.
+    pub fn uncomment_ini_directive(version: &str, key: &str, section: Option<&str>) -> Result<String, AppError> {
+        Self::validate_ini_key(key, section)?;
+        let (ini_path, mut doc) = Self::load_ini(version)?;
+        let value = doc
+            .uncomment(section, key)
+            .ok_or_else(|| AppError::NotFound(format!("No default for {} in php.ini", key)))?;
+        Self::save_ini(version, &ini_path, &doc, &format!("uncomment {}", key))?;
+        Ok(value)
     }
 
-    pub fn set_ini_directive(version: &str, key: &str, value: &str) -> Result<(), AppError> {
-        let ini_path = Self::get_php_ini_path(version);
-        if !ini_path.exists() {
-            return Err(AppError::NotFound(format!(
-                "php.ini not found for PHP {}",
-                version
+    /// Reject names that would not round-trip through php.ini, so a key or
+    /// section cannot smuggle in extra lines.
+    fn validate_ini_key(key: &str, section: Option<&str>) -> Result<(), AppError> {
+        if !IniDocument::is_valid_key(key) {
+            return Err(AppError::Config(format!("Invalid php.ini directive: '{}'", key)));
+        }
+        if let Some(section) = section {
+            if section.is_empty() || section.contains(['\n', '\r', '[', ']']) {
+                return Err(AppError::Config(format!("Invalid php.ini section: '{}'", section)));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_ini_value(key: &str, value: &str) -> Result<(), AppError> {
+        if value.contains(['\n', '\r']) {
+            return Err(AppError::Config(format!(
+                "Value of '{}' must not contain line breaks",
+                key
             )));
         }
+        Ok(())
+    }
 
-        let content = std::fs::read_to_string(&ini_path)?;
-        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
-        let mut found = false;
+    // --- Presets & snapshots ---
 
-        for line in &mut lines {
-            let trimmed = line.trim();
-            if trimmed.starts_with(';') || trimmed.is_empty() || trimmed.starts_with('[') {
-                continue;
-            }
-            if let Some((k, _)) = trimmed.split_once('=') {
-                if k.trim() == key {
-                    *line = format!("{} = {}", key, value);
-                    found = true;
-                    break;
-                }
-            }
+    /// Set several directives with a single snapshot.
+    pub fn set_ini_directives(
+        version: &str,
+        directives: &BTreeMap<String, String>,
+        reason: &str,
+    ) -> Result<(), AppError> {
+        for (key, value) in directives {
+            Self::validate_ini_key(key, None)?;
+            Self::validate_ini_value(key, value)?;
         }
+        let (ini_path, mut doc) = Self::load_ini(version)?;
+        for (key, value) in directives {
+            doc.set(None, key, value);
+        }
+        Self::save_ini(version, &ini_path, &doc, reason)
+    }
 
-        if !found {
-            lines.push(format!("{} = {}", key, value));
+    /// Like `set_ini_directives`, but leaves php.ini (and its snapshots)
+    /// alone when every directive already has its value. Returns whether
+    /// anything was written.
+    pub fn ensure_ini_directives(
+        version: &str,
+        directives: &BTreeMap<String, String>,
+        reason: &str,
+    ) -> Result<bool, AppError> {
+        let (ini_path, mut doc) = Self::load_ini(version)?;
+        let changed: Vec<(&String, &String)> = directives
+            .iter()
+            .filter(|(key, value)| doc.get(key).as_deref() != Some(value.as_str()))
+            .collect();
+        if changed.is_empty() {
+            return Ok(false);
+        }
+        for (key, value) in changed {
+            doc.set(None, key, value);
         }
+        Self::save_ini(version, &ini_path, &doc, reason)?;
+        Ok(true)
+    }
+
+    pub fn apply_ini_preset(version: &str, name: &str) -> Result<(), AppError> {
+        let preset = IniPresets::get(name)?;
+        Self::set_ini_directives(version, &preset.directives, &format!("preset {}", preset.name))?;
+        log::info!("Applied php.ini preset '{}' to PHP {}", preset.name, version);
+        Ok(())
+    }
+
+    pub fn list_ini_snapshots(version: &str) -> Result<Vec<IniSnapshot>, AppError> {
+        IniSnapshots::list(version)
+    }
+
+    /// Snapshot content, or the live php.ini for `None`.
+    fn ini_snapshot_document(version: &str, id: Option<&str>) -> Result<IniDocument, AppError> {
+        match id {
+            Some(id) => Ok(IniDocument::parse(&IniSnapshots::read(version, id)?)),
+            None => Ok(Self::load_ini(version)?.1),
+        }
+    }
 
-        std::fs::write(&ini_path, lines.join("\n") + "\n")?;
+    /// Directive changes from snapshot `from` to snapshot `to` (`None`: the
+    /// current php.ini).
+    pub fn diff_ini_snapshots(version: &str, from: &str, to: Option<&str>) -> Result<Vec<IniChange>, AppError> {
+        let before = Self::ini_snapshot_document(version, Some(from))?;
+        let after = Self::ini_snapshot_document(version, to)?;
+        Ok(before.diff(&after))
+    }
+
+    /// Put a snapshot back. The current php.ini is snapshotted first, so a
+    /// restore can itself be undone.
+    pub fn restore_ini_snapshot(version: &str, id: &str) -> Result<(), AppError> {
+        let content = IniSnapshots::read(version, id)?;
+        let ini_path = Self::get_php_ini_path(version);
+        IniSnapshots::capture(version, &ini_path, &format!("restore {}", id))?;
+        std::fs::write(&ini_path, content)?;
+        log::info!("Restored php.ini snapshot {} for PHP {}", id, version);
         Ok(())
     }
 
+    pub fn delete_ini_snapshot(version: &str, id: &str) -> Result<(), AppError> {
+        IniSnapshots::delete(version, id)
+    }
+
+    /// CLI invocation of a version that reads our php.ini.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn php_command(version: &str) -> Command {
+        Command::new(Self::get_php_binary(version))
+    }
+
+    /// Static builds have no compiled-in ini path — point them at ours.
+    #[cfg(not(target_os = "macos"))]
+    pub(crate) fn php_command(version: &str) -> Command {
+        let mut cmd = Command::new(Self::get_php_binary(version));
+        cmd.arg("-c").arg(Self::get_php_ini_path(version));
+        cmd
+    }
+
+    /// Directive values as PHP resolves them (`php -i`: "key => local => master").
+    pub fn get_effective_ini(version: &str) -> Result<HashMap<String, String>, AppError> {
+        let output = Self::php_command(version)
+            .arg("-i")
+            .output()
+            .map_err(|e| AppError::Process(format!("Failed to run php -i: {}", e)))?;
+        if !output.status.success() {
+            return Err(AppError::Process(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.split(" => ").collect();
+                match parts.as_slice() {
+                    [key, local, _master] => Some((key.trim().to_string(), local.trim().to_string())),
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    pub fn get_effective_ini_value(version: &str, key: &str) -> Result<Option<String>, AppError> {
+        Ok(Self::get_effective_ini(version)?.remove(key))
+    }
+
     // --- Extensions ---
 
-    pub fn list_extensions(version: &str) -> Result<Vec<PhpExtension>, AppError> {
-        let php_bin = Self::get_php_binary(version);
-        if !php_bin.exists() {
-            return Err(AppError::NotFound(format!(
-                "PHP {} is not installed",
-                version
-            )));
+    /// `extension_dir` plus the php.ini and conf.d files PHP actually reads.
+    fn ini_layout(version: &str) -> Result<(PathBuf, Vec<PathBuf>), AppError> {
+        let output = Self::php_command(version)
+            .arg("-r")
+            .arg("echo ini_get('extension_dir'), PHP_EOL, php_ini_loaded_file(), PHP_EOL, php_ini_scanned_files();")
+            .output()
+            .map_err(|e| AppError::Process(format!("Failed to query PHP {}: {}", version, e)))?;
+        if !output.status.success() {
+            return Err(AppError::Process(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        let mut extension_dir = PathBuf::from(lines.next().unwrap_or_default().trim());
+        // A relative extension_dir (Windows' "ext") is relative to the binary
+        if extension_dir.is_relative() {
+            if let Some(bin_dir) = Self::get_php_binary(version).parent() {
+                extension_dir = bin_dir.join(extension_dir);
+            }
+        }
+
+        let ini_files = lines
+            .flat_map(|line| line.split(','))
+            .map(|f| f.trim())
+            .filter(|f| !f.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        Ok((extension_dir, ini_files))
+    }
+
+    pub(crate) fn extension_dir(version: &str) -> Result<PathBuf, AppError> {
+        Ok(Self::ini_layout(version)?.0)
+    }
+
+    /// Whether `php -m` lists the extension.
+    pub fn is_extension_loaded(version: &str, extension: &str) -> Result<bool, AppError> {
+        Ok(Self::loaded_modules(version)?.contains_key(&Self::extension_name(extension)))
+    }
+
+    /// Module name from an `extension=` value or a file in extension_dir:
+    /// `"/path/php_xdebug.dll"` and `xdebug.so` both become `xdebug`.
+    fn extension_name(value: &str) -> String {
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+        let file = value.rsplit(['/', '\\']).next().unwrap_or(value);
+        let name = file
+            .strip_suffix(".so")
+            .or_else(|| file.strip_suffix(".dll"))
+            .unwrap_or(file);
+        name.strip_prefix("php_").unwrap_or(name).to_lowercase()
+    }
+
+    /// Names `php -m` prints that differ from the extension file name.
+    fn module_name(module: &str) -> String {
+        match module {
+            "Zend OPcache" => "opcache".to_string(),
+            other => other.to_lowercase(),
         }
+    }
 
-        let output = Command::new(&php_bin)
+    /// Extensions loaded by `php -m` (module name → zend).
+    fn loaded_modules(version: &str) -> Result<HashMap<String, bool>, AppError> {
+        let output = Self::php_command(version)
             .arg("-m")
             .output()
             .map_err(|e| AppError::Process(format!("Failed to list extensions: {}", e)))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut extensions = Vec::new();
+        let mut modules = HashMap::new();
         let mut in_zend = false;
-
         for line in stdout.lines() {
             let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
+            match trimmed {
+                "" => {}
+                "[PHP Modules]" => in_zend = false,
+                "[Zend Modules]" => in_zend = true,
+                module => {
+                    let zend = modules.entry(Self::module_name(module)).or_insert(false);
+                    *zend |= in_zend;
+                }
             }
-            if trimmed == "[PHP Modules]" {
-                in_zend = false;
-                continue;
+        }
+        Ok(modules)
+    }
+
+    /// Every extension PHP knows about: loaded modules, files in
+    /// extension_dir and `extension=` / `zend_extension=` lines (active or
+    /// commented) in php.ini and the scanned conf.d files.
+    pub fn list_extensions(version: &str) -> Result<Vec<PhpExtension>, AppError> {
+        let php_bin = Self::get_php_binary(version);
+        if !php_bin.exists() {
+            return Err(AppError::NotFound(format!(
+                "PHP {} is not installed",
+                version
+            )));
+        }
+
+        let loaded = Self::loaded_modules(version)?;
+        let (extension_dir, ini_files) = Self::ini_layout(version)?;
+        let mut extensions: BTreeMap<String, PhpExtension> = BTreeMap::new();
+
+        if let Ok(entries) = std::fs::read_dir(&extension_dir) {
+            for path in entries.flatten().map(|e| e.path()) {
+                let is_module = path
+                    .extension()
+                    .is_some_and(|ext| ext == "so" || ext == "dll");
+                if !is_module {
+                    continue;
+                }
+                let name = Self::extension_name(&path.file_name().unwrap_or_default().to_string_lossy());
+                extensions.entry(name.clone()).or_insert_with(|| PhpExtension {
+                    name,
+                    file: Some(path.to_string_lossy().to_string()),
+                    ..PhpExtension::default()
+                });
             }
-            if trimmed == "[Zend Modules]" {
-                in_zend = true;
+        }
+
+        for ini_file in &ini_files {
+            let Ok(doc) = IniDocument::load(ini_file) else {
                 continue;
+            };
+            for key in ["extension", "zend_extension"] {
+                for (value, commented) in doc.occurrences(key) {
+                    let name = Self::extension_name(&value);
+                    let ext = extensions.entry(name.clone()).or_insert_with(|| PhpExtension {
+                        name,
+                        ..PhpExtension::default()
+                    });
+                    ext.zend |= key == "zend_extension";
+                    // An active line wins over a commented one for ini_file
+                    if !commented || ext.ini_file.is_none() {
+                        ext.ini_file = Some(ini_file.to_string_lossy().to_string());
+                    }
+                }
             }
-            extensions.push(PhpExtension {
-                name: trimmed.to_string(),
-                enabled: true,
-                builtin: in_zend,
+        }
+
+        for (name, zend) in &loaded {
+            let ext = extensions.entry(name.clone()).or_insert_with(|| PhpExtension {
+                name: name.clone(),
+                // Loaded without a file or ini line: compiled in
+                builtin: true,
+                ..PhpExtension::default()
             });
+            ext.enabled = true;
+            ext.zend |= *zend;
         }
 
-        extensions.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(extensions)
+        Ok(extensions.into_values().collect())
     }
 
+    /// Enable or disable an extension in whichever ini file controls it
+    /// (php.ini or a conf.d file), using the `extension=` or
+    /// `zend_extension=` form already present. Extensions not mentioned
+    /// anywhere are added to php.ini.
     pub fn toggle_extension(
         version: &str,
         extension: &str,
@@ -607,34 +1284,77 @@ impl PhpManager {
             )));
         }
 
-        let content = std::fs::read_to_string(&ini_path)?;
-        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
-        let ext_line = format!("extension={}", extension);
-        let disabled_line = format!(";extension={}", extension);
+        let name = Self::extension_name(extension);
+        let ini_files = match Self::ini_layout(version) {
+            Ok((_, files)) if !files.is_empty() => files,
+            _ => vec![ini_path.clone()],
+        };
+        let reason = format!("{} {}", if enable { "enable" } else { "disable" }, name);
 
         let mut found = false;
-        for line in &mut lines {
-            let trimmed = line.trim();
-            if trimmed == ext_line || trimmed == disabled_line {
-                *line = if enable {
-                    ext_line.clone()
+        for ini_file in &ini_files {
+            let Ok(mut doc) = IniDocument::load(ini_file) else {
+                continue;
+            };
+            let mut changed = false;
+            for key in ["extension", "zend_extension"] {
+                let matching: Vec<(String, bool)> = doc
+                    .occurrences(key)
+                    .into_iter()
+                    .filter(|(value, _)| Self::extension_name(value) == name)
+                    .collect();
+                found |= !matching.is_empty();
+
+                if enable {
+                    // Uncommenting one line is enough; set() prefers it
+                    if let Some((value, _)) = matching.first() {
+                        if !matching.iter().any(|(_, commented)| !commented) {
+                            doc.set(None, key, value);
+                            changed = true;
+                        }
+                    }
                 } else {
-                    disabled_line.clone()
-                };
-                found = true;
+                    for (value, _) in matching.iter().filter(|(_, commented)| !commented) {
+                        changed |= doc.remove(None, key, Some(value));
+                    }
+                }
+            }
+            if changed {
+                if ini_file == &ini_path {
+                    Self::save_ini(version, ini_file, &doc, &reason)?;
+                } else {
+                    doc.save(ini_file)?;
+                }
+                log::info!(
+                    "{} PHP {} extension {} in {}",
+                    if enable { "Enabled" } else { "Disabled" },
+                    version,
+                    name,
+                    ini_file.display()
+                );
+            }
+            // Stop at the first file that mentions it when enabling
+            if found && enable {
                 break;
             }
         }
 
+        if !found && !enable {
+            return Err(AppError::Config(format!(
+                "{} is not loaded by any ini file of PHP {}",
+                name, version
+            )));
+        }
         if !found {
-            if enable {
-                lines.push(ext_line);
+            let key = if ZEND_EXTENSIONS.contains(&name.as_str()) {
+                "zend_extension"
             } else {
-                lines.push(disabled_line);
-            }
+                "extension"
+            };
+            let mut doc = IniDocument::load(&ini_path)?;
+            doc.set(None, key, &name);
+            Self::save_ini(version, &ini_path, &doc, &reason)?;
         }
-
-        std::fs::write(&ini_path, lines.join("\n") + "\n")?;
         Ok(())
     }
 }
@@ -1,10 +1,10 @@
 use crate::config::paths;
 use crate::error::AppError;
+use crate::services::archive_extractor::ArchiveExtractor;
 use crate::services::download_manager::DownloadManager;
 use crate::services::nginx_config::NginxConfigGenerator;
 use crate::services::nginx_manager::NginxManager;
 use crate::services::site_manager::SiteManager;
-use crate::services::utils;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
@@ -81,13 +81,10 @@ impl PhpMyAdminManager {
         DownloadManager::download_file(app, "phpmyadmin", &url, &archive_path).await?;
 
         Self::emit_progress(app, "extracting", "Extracting phpMyAdmin...");
-        DownloadManager::extract_zip(app, "phpmyadmin", &archive_path, &pma_dir)?;
+        ArchiveExtractor::extract(app, "phpmyadmin", &archive_path, &pma_dir, 1)?;
 
         let _ = std::fs::remove_file(&archive_path);
 
-        // Flatten: zip creates phpMyAdmin-5.2.2-all-languages/ subdirectory
-        utils::flatten_extracted_dir(&pma_dir, "phpMyAdmin-")?;
-
         // Write config
         Self::create_config()?;
 
@@ -108,7 +105,7 @@ impl PhpMyAdminManager {
                 if !site.active {
                     continue;
                 }
-                let php_port = utils::php_version_to_port(&site.php_version);
+                let fastcgi_pass = SiteManager::fastcgi_pass(site);
                 let (ssl_cert, ssl_key) = if site.ssl {
                     (
                         Some(ssl_dir.join(format!("{}.pem", site.domain)).to_string_lossy().to_string()),
@@ -121,7 +118,9 @@ impl PhpMyAdminManager {
                 let config = NginxConfigGenerator::generate_site_config(
                     &site.domain,
                     &site.document_root,
-                    php_port,
+                    &fastcgi_pass,
+                    SiteManager::fastcgi_read_timeout(site),
+                    &site.fastcgi_ini(),
                     site.ssl,
                     ssl_cert.as_deref(),
                     ssl_key.as_deref(),
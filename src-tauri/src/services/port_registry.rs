@@ -0,0 +1,178 @@
+use crate::config::app_config::AppConfig;
+use crate::config::paths;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Ports scanned past a version's preferred port before giving up.
+const MAX_PORT_SEARCH: u16 = 100;
+
+/// Versions without a fixed slot (anything but 8.1+) are placed from here on.
+const OVERFLOW_OFFSET: u16 = 10;
+
+/// Serializes read-modify-write of the assignments file.
+static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PortAssignments {
+    /// `php_fpm_base_port` the assignments were derived from
+    base_port: u16,
+    /// PHP minor version → FastCGI port
+    php_fpm: BTreeMap<String, u16>,
+    /// Ports assigned under an earlier base port. A running FPM still
+    /// listens there, so they stay in use until the version is restarted.
+    #[serde(default)]
+    outdated: BTreeMap<String, u16>,
+}
+
+/// Persisted PHP-FPM port assignments.
+///
+/// Ports are derived from `AppConfig.php_fpm_base_port` (8.1 = base, 8.2 =
+/// base + 1, ...) and moved to the next free port when something else is
+/// already listening, so nginx configs and pool configs always agree. When
+/// the base port changes, a version keeps its old port until its FPM is
+/// started again.
+pub struct PortRegistry;
+
+impl PortRegistry {
+    fn get_path() -> PathBuf {
+        paths::get_data_dir().join("data").join("ports.json")
+    }
+
+    /// Saved assignments. After a base port change the existing ones are
+    /// marked outdated rather than dropped.
+    fn load() -> PortAssignments {
+        let base_port = AppConfig::load().php_fpm_base_port;
+        let mut saved: PortAssignments = std::fs::read_to_string(Self::get_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        if saved.base_port != base_port {
+            let current = std::mem::take(&mut saved.php_fpm);
+            saved.outdated.extend(current);
+            saved.base_port = base_port;
+        }
+        saved
+    }
+
+    fn save(assignments: &PortAssignments) -> Result<(), AppError> {
+        let path = Self::get_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(assignments)?)?;
+        Ok(())
+    }
+
+    /// Fixed offset from the base port: 8.1 → 0, 8.2 → 1, ... 8.9 → 8.
+    fn preferred_offset(version: &str) -> Option<u16> {
+        let mut parts = version.split('.').map(|p| p.parse::<u16>().ok());
+        match (parts.next().flatten(), parts.next().flatten()) {
+            (Some(8), Some(minor)) if (1..OVERFLOW_OFFSET).contains(&minor) => Some(minor - 1),
+            _ => None,
+        }
+    }
+
+    fn is_port_free(port: u16) -> bool {
+        TcpListener::bind(("127.0.0.1", port)).is_ok()
+    }
+
+    /// First port from `start` that no other version holds and, when
+    /// `check_free` is set, nothing is listening on.
+    fn next_port(assignments: &PortAssignments, version: &str, start: u16, check_free: bool) -> Option<u16> {
+        (0..MAX_PORT_SEARCH)
+            .filter_map(|i| start.checked_add(i))
+            .find(|port| {
+                !assignments
+                    .php_fpm
+                    .iter()
+                    .chain(assignments.outdated.iter())
+                    .any(|(v, p)| v != version && p == port)
+                    && (!check_free || Self::is_port_free(*port))
+            })
+    }
+
+    fn assign(assignments: &mut PortAssignments, version: &str) -> u16 {
+        if let Some(port) = assignments.php_fpm.get(version) {
+            return *port;
+        }
+        let base = assignments.base_port;
+        let start = base.saturating_add(Self::preferred_offset(version).unwrap_or(OVERFLOW_OFFSET));
+        let port = Self::next_port(assignments, version, start, false).unwrap_or(start);
+        assignments.php_fpm.insert(version.to_string(), port);
+        port
+    }
+
+    /// FastCGI port of a PHP version. Read-only: a version without an
+    /// assignment gets the port it would be given, but nothing is reserved.
+    pub fn php_fpm_port(version: &str) -> u16 {
+        let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut assignments = Self::load();
+        if let Some(port) = assignments.outdated.get(version) {
+            return *port;
+        }
+        Self::assign(&mut assignments, version)
+    }
+
+    /// Like `php_fpm_port`, but saves a new assignment. Used when a version
+    /// is installed.
+    pub fn assign_php_fpm_port(version: &str) -> Result<u16, AppError> {
+        let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut assignments = Self::load();
+        if let Some(port) = assignments.outdated.get(version) {
+            return Ok(*port);
+        }
+        let known = assignments.php_fpm.contains_key(version);
+        let port = Self::assign(&mut assignments, version);
+        if !known {
+            Self::save(&assignments)?;
+        }
+        Ok(port)
+    }
+
+    /// Port PHP-FPM should bind to right now. If the assigned port is taken by
+    /// another program, or the version still had a port from an earlier base
+    /// port, the second value is `true` so callers can rewrite the nginx configs.
+    pub fn reserve_php_fpm_port(version: &str) -> Result<(u16, bool), AppError> {
+        let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut assignments = Self::load();
+        let previous = assignments.outdated.remove(version);
+        let port = Self::assign(&mut assignments, version);
+        let rebased = previous.is_some_and(|p| p != port);
+
+        // A just-stopped FPM (restart) may take a moment to release the socket
+        for attempt in 0..10 {
+            if Self::is_port_free(port) {
+                Self::save(&assignments)?;
+                return Ok((port, rebased));
+            }
+            if attempt < 9 {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+
+        let new_port = Self::next_port(&assignments, version, port.saturating_add(1), true).ok_or_else(|| {
+            AppError::Service(format!(
+                "No free port for PHP-FPM {} between {} and {}",
+                version,
+                port,
+                port.saturating_add(MAX_PORT_SEARCH)
+            ))
+        })?;
+        log::warn!(
+            "Port {} is already in use, moving PHP-FPM {} to port {}",
+            port,
+            version,
+            new_port
+        );
+        assignments.php_fpm.insert(version.to_string(), new_port);
+        Self::save(&assignments)?;
+        Ok((new_port, true))
+    }
+}
@@ -0,0 +1,167 @@
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::utils;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A log is rotated once it grows past this size.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated copies kept next to the current log (`.log.1` is the newest).
+const KEEP_ROTATED: usize = 3;
+
+/// Lines of output included in startup errors.
+const ERROR_TAIL_LINES: usize = 20;
+
+/// A process that exits within this long after spawning failed to start.
+pub const STARTUP_GRACE: Duration = Duration::from_secs(3);
+
+struct LogWriter {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl LogWriter {
+    fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            written,
+        })
+    }
+
+    fn write_line(&mut self, stream: &str, line: &str) {
+        if self.written >= MAX_LOG_BYTES {
+            ProcessLog::rotate(&self.path);
+            match Self::open(&self.path) {
+                Ok(reopened) => *self = reopened,
+                Err(e) => log::warn!("Could not reopen {}: {}", self.path.display(), e),
+            }
+        }
+        let entry = format!(
+            "[{}] [{}] {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            stream,
+            line
+        );
+        if self.file.write_all(entry.as_bytes()).is_ok() {
+            self.written += entry.len() as u64;
+        }
+    }
+}
+
+/// stdout/stderr of the processes LokcalDev keeps running, written to
+/// `<logs dir>/<service id>-output.log` and rotated by size.
+pub struct ProcessLog;
+
+impl ProcessLog {
+    pub fn get_path(service_id: &str) -> PathBuf {
+        paths::get_logs_dir().join(format!("{}-output.log", service_id))
+    }
+
+    fn rotated_path(path: &Path, index: usize) -> PathBuf {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// `x.log` -> `x.log.1` -> `x.log.2` ..., dropping the oldest.
+    fn rotate(path: &Path) {
+        let _ = std::fs::remove_file(Self::rotated_path(path, KEEP_ROTATED));
+        for index in (1..KEEP_ROTATED).rev() {
+            let from = Self::rotated_path(path, index);
+            if from.exists() {
+                let _ = std::fs::rename(&from, Self::rotated_path(path, index + 1));
+            }
+        }
+        if let Err(e) = std::fs::rename(path, Self::rotated_path(path, 1)) {
+            log::warn!("Could not rotate {}: {}", path.display(), e);
+        }
+    }
+
+    /// Pipe the command's output so `capture` can log it.
+    pub fn prepare(cmd: &mut Command) {
+        cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+
+    /// Append the child's output to its service log until it exits.
+    pub fn capture(service_id: &str, child: &mut Child) -> Result<(), AppError> {
+        let path = Self::get_path(service_id);
+        std::fs::create_dir_all(paths::get_logs_dir())?;
+        if std::fs::metadata(&path).map(|m| m.len() >= MAX_LOG_BYTES).unwrap_or(false) {
+            Self::rotate(&path);
+        }
+
+        let mut writer = LogWriter::open(&path)?;
+        writer.write_line("lokcaldev", &format!("started {} (PID {})", service_id, child.id()));
+        let writer = Arc::new(Mutex::new(writer));
+
+        if let Some(stdout) = child.stdout.take() {
+            Self::pump(stdout, "stdout", Arc::clone(&writer));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            Self::pump(stderr, "stderr", writer);
+        }
+        Ok(())
+    }
+
+    fn pump(stream: impl Read + Send + 'static, name: &'static str, writer: Arc<Mutex<LogWriter>>) {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                if let Ok(mut writer) = writer.lock() {
+                    writer.write_line(name, &line);
+                }
+            }
+        });
+    }
+
+    /// Wait until `ready` reports the process is serving, the grace period
+    /// ends, or the process exits (returned as its status).
+    pub fn wait_for_startup(child: &mut Child, ready: impl Fn() -> bool) -> Option<ExitStatus> {
+        let deadline = Instant::now() + STARTUP_GRACE;
+        loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Some(status);
+            }
+            if ready() || Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Last lines of the captured output followed by the service's own log.
+    /// Each source keeps at least half of `count` when it has that many lines.
+    pub fn recent_lines(service_id: &str, service_log: Option<&Path>, count: usize) -> Vec<String> {
+        let output = utils::tail_lines(&Self::get_path(service_id), count);
+        let Some(service_log) = service_log else {
+            return output;
+        };
+        let log_lines = utils::tail_lines(service_log, count);
+
+        let from_log = log_lines.len().min(count - output.len().min(count - count / 2));
+        let from_output = output.len().min(count - from_log);
+        let mut lines = output[output.len() - from_output..].to_vec();
+        lines.extend_from_slice(&log_lines[log_lines.len() - from_log..]);
+        lines
+    }
+
+    /// Error for a process that exited right after it was started.
+    pub fn startup_error(service_id: &str, name: &str, status: ExitStatus, service_log: Option<&Path>) -> AppError {
+        // Give the pump threads a moment to flush the last lines
+        std::thread::sleep(Duration::from_millis(100));
+        let tail = Self::recent_lines(service_id, service_log, ERROR_TAIL_LINES);
+        AppError::Process(if tail.is_empty() {
+            format!("{} exited during startup ({})", name, status)
+        } else {
+            format!("{} exited during startup ({}):\n{}", name, status, tail.join("\n"))
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use crate::config::paths;
+use crate::error::AppError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Nginx/MariaDB release index shipped with the app; a copy under the config
+/// dir takes precedence (same scheme as the PHP catalogue).
+const BUNDLED_INDEX: &str = include_str!("../../resources/service-releases.json");
+
+#[derive(Debug, Clone, Deserialize)]
+struct ServiceRelease {
+    /// Latest patch release offered for download
+    latest: String,
+    /// Expected SHA-256 of downloads, keyed by file name
+    #[serde(default)]
+    sha256: HashMap<String, String>,
+    /// Per-OS override ("windows", "linux", "macos") when a platform's
+    /// prebuilt binaries lag behind
+    #[serde(flatten)]
+    platforms: HashMap<String, String>,
+}
+
+pub struct ServiceCatalogue;
+
+impl ServiceCatalogue {
+    fn get_local_path() -> PathBuf {
+        paths::get_config_dir().join("service-releases.json")
+    }
+
+    fn load() -> HashMap<String, ServiceRelease> {
+        std::fs::read_to_string(Self::get_local_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .or_else(|| serde_json::from_str(BUNDLED_INDEX).ok())
+            .unwrap_or_default()
+    }
+
+    /// Latest patch release of `service` ("nginx", "mariadb") for this platform.
+    pub fn latest(service: &str) -> Result<String, AppError> {
+        let release = Self::load()
+            .remove(service)
+            .ok_or_else(|| AppError::NotFound(format!("{} is not in the release catalogue", service)))?;
+        Ok(release
+            .platforms
+            .get(std::env::consts::OS)
+            .cloned()
+            .unwrap_or(release.latest))
+    }
+
+    /// Shipped digest of a Nginx/MariaDB download, by archive file name.
+    pub fn sha256(file_name: &str) -> Option<String> {
+        Self::load()
+            .into_values()
+            .find_map(|r| r.sha256.get(file_name).cloned())
+    }
+}
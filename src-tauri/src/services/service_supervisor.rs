@@ -0,0 +1,327 @@
+use crate::commands::service_commands;
+use crate::config::app_config::AppConfig;
+use crate::services::mariadb_manager::MariaDbManager;
+use crate::services::php_manager::PhpManager;
+use crate::services::process_log::ProcessLog;
+use crate::state::{AppState, ServiceInfo, ServiceStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A service that stays up this long after a restart is considered healthy
+/// again and gets its full restart budget back.
+const STABLE_AFTER: Duration = Duration::from_secs(300);
+
+const LOG_TAIL_LINES: usize = 20;
+
+/// How a supervised process ended.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceExit {
+    pub service_id: String,
+    pub code: Option<i32>,
+    /// Terminating signal (Unix only)
+    pub signal: Option<i32>,
+    pub exited_at: String,
+    /// Last lines of the service's output and log at the time of the exit
+    pub log_tail: Vec<String>,
+    /// Restarts already attempted since the service was last healthy
+    pub restarts: u32,
+}
+
+/// Payload of the `service-status` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatusEvent {
+    pub service: ServiceInfo,
+    pub message: String,
+    /// Set when the event reports a crash
+    pub exit: Option<ServiceExit>,
+    /// Seconds until the next restart attempt, if one is scheduled
+    pub restart_in: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct RestartState {
+    attempts: u32,
+    last_restart: Option<Instant>,
+    due: Option<Instant>,
+}
+
+/// Supervisor bookkeeping kept in `AppState`.
+#[derive(Debug, Default)]
+pub struct SupervisorState {
+    pub restarts: HashMap<String, RestartState>,
+    /// Most recent unexpected exit per service
+    pub exits: HashMap<String, ServiceExit>,
+    pub stopped: bool,
+}
+
+/// Watches the processes in `AppState.child_processes`. Every stop path calls
+/// `release` before terminating the process, so every exit seen here is a
+/// crash; it is recorded, reported and, per `restart_policy`, restarted.
+pub struct ServiceSupervisor;
+
+impl ServiceSupervisor {
+    pub fn spawn(app: AppHandle) {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let state = app.state::<AppState>();
+            if state.supervisor.lock().map(|s| s.stopped).unwrap_or(true) {
+                break;
+            }
+            Self::collect_exits(&app);
+            Self::run_due_restarts(&app);
+        });
+    }
+
+    /// Stop watching before the app kills its children on exit.
+    pub fn shutdown(state: &AppState) {
+        if let Ok(mut supervisor) = state.supervisor.lock() {
+            supervisor.stopped = true;
+            supervisor.restarts.clear();
+        }
+    }
+
+    /// Take a service the user is stopping out of supervision: forget its
+    /// process handle (without killing it, so the caller can stop it
+    /// gracefully) and drop any pending restart.
+    pub fn release(state: &AppState, service_id: &str) {
+        if let Ok(mut children) = state.child_processes.lock() {
+            children.remove(service_id);
+        }
+        if let Ok(mut supervisor) = state.supervisor.lock() {
+            supervisor.restarts.remove(service_id);
+        }
+    }
+
+    pub fn last_exits(state: &AppState) -> Vec<ServiceExit> {
+        let mut exits: Vec<ServiceExit> = state
+            .supervisor
+            .lock()
+            .map(|s| s.exits.values().cloned().collect())
+            .unwrap_or_default();
+        exits.sort_by(|a, b| b.exited_at.cmp(&a.exited_at));
+        exits
+    }
+
+    fn display_name(service_id: &str) -> String {
+        match service_id.strip_prefix("php-fpm-") {
+            Some(version) => format!("PHP-FPM {}", version),
+            None if service_id == "mariadb" => "MariaDB".to_string(),
+            None => service_id.to_string(),
+        }
+    }
+
+    fn log_path(service_id: &str) -> Option<PathBuf> {
+        match service_id.strip_prefix("php-fpm-") {
+            Some(version) => Some(PhpManager::get_fpm_log_path(version)),
+            None if service_id == "mariadb" => Some(MariaDbManager::get_log_path()),
+            None => None,
+        }
+    }
+
+    #[cfg(unix)]
+    fn signal(status: &ExitStatus) -> Option<i32> {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+
+    #[cfg(not(unix))]
+    fn signal(_status: &ExitStatus) -> Option<i32> {
+        None
+    }
+
+    fn collect_exits(app: &AppHandle) {
+        let state = app.state::<AppState>();
+        let exited: Vec<(String, ExitStatus)> = match state.child_processes.lock() {
+            Ok(mut children) => {
+                let exited: Vec<(String, ExitStatus)> = children
+                    .iter_mut()
+                    .filter_map(|(id, child)| match child.try_wait() {
+                        Ok(Some(status)) => Some((id.clone(), status)),
+                        _ => None,
+                    })
+                    .collect();
+                for (id, _) in &exited {
+                    children.remove(id);
+                }
+                exited
+            }
+            Err(_) => return,
+        };
+
+        let policy = AppConfig::load().restart_policy;
+        for (service_id, status) in exited {
+            let name = Self::display_name(&service_id);
+            log::warn!("{} exited unexpectedly ({})", name, status);
+
+            let (exit, restart_in) = {
+                let Ok(mut supervisor) = state.supervisor.lock() else {
+                    return;
+                };
+                let restart = supervisor.restarts.entry(service_id.clone()).or_default();
+                if restart.last_restart.is_some_and(|t| t.elapsed() >= STABLE_AFTER) {
+                    restart.attempts = 0;
+                }
+                let restart_in = if policy.enabled && restart.attempts < policy.max_restarts {
+                    let delay = policy.backoff(restart.attempts);
+                    restart.due = Some(Instant::now() + delay);
+                    Some(delay.as_secs())
+                } else {
+                    restart.due = None;
+                    None
+                };
+                let exit = ServiceExit {
+                    service_id: service_id.clone(),
+                    code: status.code(),
+                    signal: Self::signal(&status),
+                    exited_at: chrono::Utc::now().to_rfc3339(),
+                    log_tail: ProcessLog::recent_lines(
+                        &service_id,
+                        Self::log_path(&service_id).as_deref(),
+                        LOG_TAIL_LINES,
+                    ),
+                    restarts: restart.attempts,
+                };
+                supervisor.exits.insert(service_id.clone(), exit.clone());
+                (exit, restart_in)
+            };
+
+            let message = match restart_in {
+                Some(secs) => format!(
+                    "{} exited ({}); restarting in {}s (attempt {} of {})",
+                    name,
+                    status,
+                    secs,
+                    exit.restarts + 1,
+                    policy.max_restarts
+                ),
+                None if policy.enabled => format!(
+                    "{} exited ({}); gave up after {} restarts",
+                    name, status, exit.restarts
+                ),
+                None => format!("{} exited ({})", name, status),
+            };
+            let service = Self::mark_failed(&state, &service_id);
+            if policy.notify {
+                Self::notify(app, &format!("{} crashed", name), &message);
+            }
+            Self::emit(app, service, message, Some(exit), restart_in);
+        }
+    }
+
+    fn run_due_restarts(app: &AppHandle) {
+        let state = app.state::<AppState>();
+        let due: Vec<String> = match state.supervisor.lock() {
+            Ok(supervisor) => supervisor
+                .restarts
+                .iter()
+                .filter(|(_, r)| r.due.is_some_and(|due| due <= Instant::now()))
+                .map(|(id, _)| id.clone())
+                .collect(),
+            Err(_) => return,
+        };
+
+        let policy = AppConfig::load().restart_policy;
+        for service_id in due {
+            let name = Self::display_name(&service_id);
+            let already_running = state
+                .child_processes
+                .lock()
+                .map(|c| c.contains_key(&service_id))
+                .unwrap_or(false);
+
+            let result = if already_running {
+                // Started by hand in the meantime
+                None
+            } else {
+                log::info!("Restarting {}", name);
+                Some(service_commands::start_service(app.clone(), app.state::<AppState>(), service_id.clone()))
+            };
+
+            let mut retry_in = None;
+            if let Ok(mut supervisor) = state.supervisor.lock() {
+                let Some(restart) = supervisor.restarts.get_mut(&service_id) else {
+                    // Stopped by the user while restarting
+                    continue;
+                };
+                restart.due = None;
+                if result.is_some() {
+                    restart.attempts += 1;
+                    restart.last_restart = Some(Instant::now());
+                }
+                if matches!(result, Some(Err(_))) && policy.enabled && restart.attempts < policy.max_restarts {
+                    let delay = policy.backoff(restart.attempts);
+                    restart.due = Some(Instant::now() + delay);
+                    retry_in = Some(delay.as_secs());
+                }
+            }
+
+            match result {
+                Some(Ok(service)) => {
+                    log::info!("Restarted {}", name);
+                    Self::emit(app, service, format!("{} restarted", name), None, None);
+                }
+                Some(Err(e)) => {
+                    log::warn!("Failed to restart {}: {}", name, e);
+                    let message = match retry_in {
+                        Some(secs) => format!("Failed to restart {}: {}; retrying in {}s", name, e, secs),
+                        None => format!("Failed to restart {}: {}", name, e),
+                    };
+                    if retry_in.is_none() && policy.notify {
+                        Self::notify(app, &format!("{} is down", name), &message);
+                    }
+                    let service = Self::mark_failed(&state, &service_id);
+                    Self::emit(app, service, message, None, retry_in);
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Flag the service as errored in `AppState.services`.
+    fn mark_failed(state: &AppState, service_id: &str) -> ServiceInfo {
+        let mut services = match state.services.lock() {
+            Ok(services) => services,
+            Err(e) => e.into_inner(),
+        };
+        let service = services.entry(service_id.to_string()).or_insert_with(|| ServiceInfo {
+            id: service_id.to_string(),
+            name: Self::display_name(service_id),
+            status: ServiceStatus::Error,
+            port: None,
+            version: service_id.strip_prefix("php-fpm-").map(|v| v.to_string()),
+            pid: None,
+            installed: true,
+            initialized: true,
+        });
+        service.status = ServiceStatus::Error;
+        service.pid = None;
+        service.clone()
+    }
+
+    fn emit(app: &AppHandle, service: ServiceInfo, message: String, exit: Option<ServiceExit>, restart_in: Option<u64>) {
+        let _ = app.emit(
+            "service-status",
+            ServiceStatusEvent {
+                service,
+                message,
+                exit,
+                restart_in,
+            },
+        );
+    }
+
+    fn notify(app: &AppHandle, title: &str, body: &str) {
+        if let Err(e) = app.notification().builder().title(title).body(body).show() {
+            log::warn!("Could not show notification: {}", e);
+        }
+    }
+}
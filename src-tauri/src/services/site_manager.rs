@@ -1,10 +1,15 @@
+use crate::config::app_config::AppConfig;
 use crate::config::paths;
 use crate::error::AppError;
+use crate::services::cli_shims::CliShims;
 use crate::services::dns_manager::DnsManager;
+use crate::services::ini_document::IniDocument;
 use crate::services::nginx_config::NginxConfigGenerator;
 use crate::services::nginx_manager::NginxManager;
+use crate::services::php_manager::{PhpManager, FPM_STATUS_PATH};
 use crate::services::utils;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +32,193 @@ pub struct Site {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub dns_warning: Option<String>,
+    /// Dedicated PHP-FPM pool; `None` or disabled means the version's shared pool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<SitePool>,
+    /// php.ini values nginx passes to PHP-FPM with every request of the site.
+    #[serde(default, skip_serializing_if = "SiteIniOverrides::is_empty")]
+    pub ini_overrides: SiteIniOverrides,
+    /// Start an Xdebug session on every request, not only triggered ones.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub xdebug: bool,
+}
+
+impl Site {
+    /// `ini_overrides` plus what the debugging toggle adds. start_with_request
+    /// is a PHP_INI_SYSTEM|PERDIR setting, so it has to go through
+    /// `PHP_ADMIN_VALUE`.
+    pub fn fastcgi_ini(&self) -> SiteIniOverrides {
+        let mut ini = self.ini_overrides.clone();
+        if self.xdebug {
+            ini.values.remove("xdebug.start_with_request");
+            ini.admin_values
+                .insert("xdebug.start_with_request".to_string(), "yes".to_string());
+        }
+        ini
+    }
+}
+
+/// Per-site php.ini values sent as FastCGI `PHP_VALUE` / `PHP_ADMIN_VALUE`.
+///
+/// PHP-FPM applies them to the worker handling the request and does not reset
+/// them afterwards, so sites on a shared pool can see each other's values.
+/// Give the site a dedicated pool when that matters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteIniOverrides {
+    /// `PHP_VALUE`: scripts may still change these with `ini_set()`
+    #[serde(default)]
+    pub values: BTreeMap<String, String>,
+    /// `PHP_ADMIN_VALUE`: locked against `ini_set()`
+    #[serde(default)]
+    pub admin_values: BTreeMap<String, String>,
+}
+
+/// Settings of a site's own PHP-FPM pool (macOS/Linux only).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SitePool {
+    pub enabled: bool,
+    #[serde(default = "default_pool_max_children")]
+    pub max_children: u32,
+    /// Requests a worker serves before it is recycled (0 = never)
+    #[serde(default = "default_pool_max_requests")]
+    pub max_requests: u32,
+    /// Log a backtrace of requests slower than this many seconds (0 = off)
+    #[serde(default)]
+    pub slowlog_timeout: u32,
+    /// `env[NAME] = value`
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// `php_admin_value[directive] = value`
+    #[serde(default)]
+    pub admin_values: BTreeMap<String, String>,
+}
+
+fn default_pool_max_children() -> u32 {
+    5
+}
+
+fn default_pool_max_requests() -> u32 {
+    500
+}
+
+impl SitePool {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if cfg!(target_os = "windows") && self.enabled {
+            return Err(AppError::Config(
+                "Per-site PHP-FPM pools are not supported on Windows".to_string(),
+            ));
+        }
+        if !(1..=256).contains(&self.max_children) {
+            return Err(AppError::Config("Pool max children must be between 1 and 256".to_string()));
+        }
+        for (name, value) in &self.env {
+            let valid_name = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid_name {
+                return Err(AppError::Config(format!("Invalid environment variable name: '{}'", name)));
+            }
+            Self::validate_value(name, value)?;
+        }
+        for (directive, value) in &self.admin_values {
+            let valid_directive = !directive.is_empty()
+                && directive.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            if !valid_directive {
+                return Err(AppError::Config(format!("Invalid php.ini directive: '{}'", directive)));
+            }
+            Self::validate_value(directive, value)?;
+        }
+        Ok(())
+    }
+
+    /// Values end up inside double quotes in the FPM and nginx configs; nginx
+    /// would also expand `$` as a variable.
+    pub(crate) fn validate_value(name: &str, value: &str) -> Result<(), AppError> {
+        if value.contains(['\n', '\r', '"', '$']) {
+            return Err(AppError::Config(format!(
+                "Value of '{}' must not contain quotes, '$' or line breaks",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Pool section for php-fpm, listening on `socket`.
+    pub fn to_fpm_config(&self, pool_name: &str, socket: &str, slowlog: &str) -> String {
+        let mut config = format!(
+            "[{}]\n\
+             listen = \"{}\"\n\
+             listen.mode = 0660\n\
+             pm = ondemand\n\
+             pm.max_children = {}\n\
+             pm.process_idle_timeout = 10s\n\
+             pm.max_requests = {}\n\
+             pm.status_path = {}\n",
+            pool_name, socket, self.max_children, self.max_requests, FPM_STATUS_PATH,
+        );
+        if self.slowlog_timeout > 0 {
+            config.push_str(&format!(
+                "request_slowlog_timeout = {}s\nslowlog = \"{}\"\n",
+                self.slowlog_timeout, slowlog,
+            ));
+        }
+        for (name, value) in &self.env {
+            config.push_str(&format!("env[{}] = \"{}\"\n", name, value));
+        }
+        for (directive, value) in &self.admin_values {
+            config.push_str(&format!("php_admin_value[{}] = \"{}\"\n", directive, value));
+        }
+        config
+    }
+}
+
+impl SiteIniOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.admin_values.is_empty()
+    }
+
+    /// Syntax checks, then every directive must be known to `php_version`
+    /// (core or a loaded extension). The latter is skipped when PHP cannot
+    /// be queried, e.g. before the version is installed.
+    pub fn validate(&self, php_version: &str) -> Result<(), AppError> {
+        for (directive, value) in self.values.iter().chain(&self.admin_values) {
+            if !IniDocument::is_valid_key(directive) {
+                return Err(AppError::Config(format!("Invalid php.ini directive: '{}'", directive)));
+            }
+            SitePool::validate_value(directive, value)?;
+        }
+        if let Some(both) = self.values.keys().find(|k| self.admin_values.contains_key(*k)) {
+            return Err(AppError::Config(format!(
+                "'{}' is set both as value and as admin value",
+                both
+            )));
+        }
+
+        match PhpManager::get_effective_ini(php_version) {
+            Ok(known) => {
+                let unknown: Vec<&str> = self
+                    .values
+                    .keys()
+                    .chain(self.admin_values.keys())
+                    .filter(|k| !known.contains_key(*k))
+                    .map(|k| k.as_str())
+                    .collect();
+                if !unknown.is_empty() {
+                    return Err(AppError::Config(format!(
+                        "Unknown php.ini directive(s) for PHP {}: {}",
+                        php_version,
+                        unknown.join(", ")
+                    )));
+                }
+            }
+            Err(e) => log::warn!(
+                "Could not list directives of PHP {}, skipping check: {}",
+                php_version, e
+            ),
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +230,10 @@ pub struct CreateSiteRequest {
     pub php_version: String,
     pub ssl: bool,
     pub template: Option<String>,
+    #[serde(default)]
+    pub pool: Option<SitePool>,
+    #[serde(default)]
+    pub ini_overrides: Option<SiteIniOverrides>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +245,9 @@ pub struct UpdateSiteRequest {
     pub php_version: Option<String>,
     pub ssl: Option<bool>,
     pub active: Option<bool>,
+    pub pool: Option<SitePool>,
+    pub ini_overrides: Option<SiteIniOverrides>,
+    pub xdebug: Option<bool>,
 }
 
 pub struct SiteManager;
@@ -58,6 +257,30 @@ impl SiteManager {
         paths::get_sites_dir().join(format!("{}.toml", id))
     }
 
+    /// Whether the site is served by its own PHP-FPM pool.
+    pub fn has_own_pool(site: &Site) -> bool {
+        !cfg!(target_os = "windows") && site.active && site.pool.as_ref().is_some_and(|p| p.enabled)
+    }
+
+    /// FastCGI address nginx forwards the site's PHP requests to.
+    pub fn fastcgi_pass(site: &Site) -> String {
+        if Self::has_own_pool(site) {
+            format!("unix:{}", utils::to_forward_slash(&PhpManager::get_site_pool_socket(&site.id)))
+        } else {
+            PhpManager::fastcgi_address(&site.php_version)
+        }
+    }
+
+    /// `request_terminate_timeout` of the pool serving the site; dedicated
+    /// pools do not set one.
+    pub fn fastcgi_read_timeout(site: &Site) -> u32 {
+        if Self::has_own_pool(site) {
+            0
+        } else {
+            AppConfig::load().fpm_settings(&site.php_version).request_terminate_timeout
+        }
+    }
+
     pub fn list() -> Result<Vec<Site>, AppError> {
         let sites_dir = paths::get_sites_dir();
         std::fs::create_dir_all(&sites_dir)?;
@@ -91,6 +314,13 @@ impl SiteManager {
         let sites_dir = paths::get_sites_dir();
         std::fs::create_dir_all(&sites_dir)?;
 
+        if let Some(pool) = &req.pool {
+            pool.validate()?;
+        }
+        if let Some(ini_overrides) = &req.ini_overrides {
+            ini_overrides.validate(&req.php_version)?;
+        }
+
         let has_template = req.template.is_some();
         let id = Uuid::new_v4().to_string();
         let mut site = Site {
@@ -105,6 +335,9 @@ impl SiteManager {
             template_status: if has_template { Some("pending".to_string()) } else { None },
             template: req.template,
             dns_warning: None,
+            pool: req.pool,
+            ini_overrides: req.ini_overrides.unwrap_or_default(),
+            xdebug: false,
         };
 
         // Save site config
@@ -137,8 +370,12 @@ impl SiteManager {
             req.document_root.clone()
         };
 
+        if Self::has_own_pool(&site) {
+            PhpManager::apply_pool_config(&site.php_version)?;
+        }
+
         // Generate nginx config
-        let php_port = utils::php_version_to_port(&req.php_version);
+        let fastcgi_pass = Self::fastcgi_pass(&site);
         let ssl_dir = paths::get_ssl_dir();
         let (ssl_cert, ssl_key) = if req.ssl {
             (
@@ -153,7 +390,9 @@ impl SiteManager {
         let nginx_config = NginxConfigGenerator::generate_site_config(
             &req.domain,
             &nginx_root,
-            php_port,
+            &fastcgi_pass,
+            Self::fastcgi_read_timeout(&site),
+            &site.fastcgi_ini(),
             req.ssl,
             ssl_cert.as_deref(),
             ssl_key.as_deref(),
@@ -169,6 +408,7 @@ impl SiteManager {
             site.dns_warning = Some(warning);
         }
 
+        CliShims::refresh();
         log::info!("Created site: {} ({})", site.name, site.domain);
         Ok(site)
     }
@@ -176,6 +416,8 @@ impl SiteManager {
     pub fn update(id: &str, req: UpdateSiteRequest) -> Result<Site, AppError> {
         let mut site = Self::get(id)?;
         let old_domain = site.domain.clone();
+        let old_php_version = site.php_version.clone();
+        let had_own_pool = Self::has_own_pool(&site);
 
         if let Some(name) = req.name {
             site.name = name;
@@ -195,12 +437,41 @@ impl SiteManager {
         if let Some(active) = req.active {
             site.active = active;
         }
+        if let Some(pool) = req.pool {
+            pool.validate()?;
+            site.pool = Some(pool);
+        }
+        let ini_changed = req.ini_overrides.is_some();
+        if let Some(ini_overrides) = req.ini_overrides {
+            site.ini_overrides = ini_overrides;
+        }
+        // Directives known to the old version may not exist in the new one
+        if ini_changed || old_php_version != site.php_version {
+            site.ini_overrides.validate(&site.php_version)?;
+        }
+        if let Some(xdebug) = req.xdebug {
+            if xdebug && !PhpManager::is_extension_loaded(&site.php_version, "xdebug").unwrap_or(false) {
+                return Err(AppError::Config(format!(
+                    "Enable Xdebug for PHP {} before turning on debugging for a site",
+                    site.php_version
+                )));
+            }
+            site.xdebug = xdebug;
+        }
 
         // Save updated site
         let toml_str = toml::to_string_pretty(&site)
             .map_err(|e| AppError::Config(e.to_string()))?;
         std::fs::write(Self::get_site_file(id), &toml_str)?;
 
+        // Rewrite pools of the old and new PHP version
+        if had_own_pool || Self::has_own_pool(&site) {
+            PhpManager::apply_pool_config(&site.php_version)?;
+            if old_php_version != site.php_version {
+                PhpManager::apply_pool_config(&old_php_version)?;
+            }
+        }
+
         // Remove old nginx config if domain changed
         if old_domain != site.domain {
             NginxConfigGenerator::remove_site_config(&old_domain)?;
@@ -210,7 +481,7 @@ impl SiteManager {
         if !site.active {
             NginxConfigGenerator::remove_site_config(&site.domain)?;
         } else {
-            let php_port = utils::php_version_to_port(&site.php_version);
+            let fastcgi_pass = Self::fastcgi_pass(&site);
             let ssl_dir = paths::get_ssl_dir();
             let (ssl_cert, ssl_key) = if site.ssl {
                 (
@@ -225,7 +496,9 @@ impl SiteManager {
             let nginx_config = NginxConfigGenerator::generate_site_config(
                 &site.domain,
                 &site.document_root,
-                php_port,
+                &fastcgi_pass,
+                Self::fastcgi_read_timeout(&site),
+                &site.fastcgi_ini(),
                 site.ssl,
                 ssl_cert.as_deref(),
                 ssl_key.as_deref(),
@@ -235,6 +508,7 @@ impl SiteManager {
             NginxConfigGenerator::write_site_config(&site.domain, &nginx_config)?;
         }
 
+        CliShims::refresh();
         log::info!("Updated site: {} ({})", site.name, site.domain);
         Ok(site)
     }
@@ -261,7 +535,7 @@ impl SiteManager {
                 continue;
             }
 
-            let php_port = utils::php_version_to_port(&site.php_version);
+            let fastcgi_pass = Self::fastcgi_pass(site);
 
             // For Laravel, nginx root should point to {document_root}/public
             let nginx_root = if site.template.as_deref() == Some("laravel") {
@@ -283,7 +557,9 @@ impl SiteManager {
             let config = NginxConfigGenerator::generate_site_config(
                 &site.domain,
                 &nginx_root,
-                php_port,
+                &fastcgi_pass,
+                Self::fastcgi_read_timeout(site),
+                &site.fastcgi_ini(),
                 site.ssl,
                 ssl_cert.as_deref(),
                 ssl_key.as_deref(),
@@ -323,6 +599,14 @@ impl SiteManager {
             std::fs::remove_file(&path)?;
         }
 
+        // Drop its dedicated pool
+        if Self::has_own_pool(&site) {
+            if let Err(e) = PhpManager::apply_pool_config(&site.php_version) {
+                log::warn!("Could not remove PHP-FPM pool of {}: {}", site.domain, e);
+            }
+        }
+
+        CliShims::refresh();
         log::info!("Deleted site: {} ({})", site.name, site.domain);
         Ok(())
     }
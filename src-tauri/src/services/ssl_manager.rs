@@ -69,7 +69,9 @@ impl SslManager {
         }
 
         let url = Self::get_download_url();
-        DownloadManager::download_file(app, "mkcert", &url, &binary).await?;
+        // download_file deletes the binary on checksum mismatch, so only an
+        // accepted file ever reaches the chmod below
+        DownloadManager::download_executable(app, "mkcert", &url, &binary).await?;
 
         // Set executable permission
         #[cfg(unix)]
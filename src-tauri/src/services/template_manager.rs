@@ -1,8 +1,10 @@
 use crate::error::AppError;
+use crate::services::archive_extractor::ArchiveExtractor;
+use crate::services::composer_manager::ComposerManager;
 use crate::services::download_manager::DownloadManager;
+use crate::services::mail_catcher::MailCatcher;
 use crate::services::mariadb_manager::MariaDbManager;
 use crate::services::site_manager::SiteManager;
-use crate::services::utils;
 use tauri::{AppHandle, Emitter};
 
 pub struct TemplateManager;
@@ -76,25 +78,25 @@ impl TemplateManager {
 
         // 2. Extract
         Self::emit_progress(app, site_id, "extracting", "Extracting WordPress...");
-        DownloadManager::extract_zip(app, &download_id, &archive_path, &doc_root)?;
+        ArchiveExtractor::extract(app, &download_id, &archive_path, &doc_root, 1)?;
 
         // 3. Delete archive
         let _ = std::fs::remove_file(&archive_path);
 
-        // 4. Flatten wordpress/ subdirectory
-        utils::flatten_extracted_dir(&doc_root,"wordpress")?;
-
-        // 5. Auto-create MariaDB database (non-fatal)
+        // 4. Auto-create MariaDB database (non-fatal)
         let db_name = Self::sanitize_db_name(&site.name);
         Self::emit_progress(app, site_id, "configuring", "Creating database...");
         if let Err(e) = MariaDbManager::create_database(&db_name) {
             log::warn!("Could not auto-create WordPress database '{}': {}", db_name, e);
         }
 
-        // 6. Generate wp-config.php
+        // 5. Generate wp-config.php
         Self::emit_progress(app, site_id, "configuring", "Generating wp-config.php...");
         Self::generate_wp_config(&doc_root, &db_name)?;
 
+        // 6. Send mail to the mail catcher
+        Self::generate_wp_mail_plugin(&doc_root)?;
+
         log::info!("WordPress installed for site {}", site_id);
         Ok(())
     }
@@ -133,6 +135,31 @@ require_once ABSPATH . 'wp-settings.php';
         Ok(())
     }
 
+    /// Must-use plugin that routes wp_mail() through SMTP to the mail catcher.
+    fn generate_wp_mail_plugin(doc_root: &std::path::Path) -> Result<(), AppError> {
+        let plugin = format!(
+            r#"<?php
+/**
+ * Plugin Name: LokcalDev Mail
+ * Description: Delivers all mail to the LokcalDev mail catcher.
+ */
+add_action( 'phpmailer_init', function ( $phpmailer ) {{
+    $phpmailer->isSMTP();
+    $phpmailer->Host = '127.0.0.1';
+    $phpmailer->Port = {port};
+    $phpmailer->SMTPAuth = false;
+    $phpmailer->SMTPAutoTLS = false;
+}} );
+"#,
+            port = MailCatcher::port(),
+        );
+
+        let mu_plugins = doc_root.join("wp-content").join("mu-plugins");
+        std::fs::create_dir_all(&mu_plugins)?;
+        std::fs::write(mu_plugins.join("lokcaldev-mail.php"), plugin)?;
+        Ok(())
+    }
+
     fn generate_wp_salts() -> String {
         let keys = [
             "AUTH_KEY",
@@ -206,30 +233,71 @@ require_once ABSPATH . 'wp-settings.php';
 
         // 2. Extract
         Self::emit_progress(app, site_id, "extracting", "Extracting Laravel...");
-        DownloadManager::extract_zip(app, &download_id, &archive_path, &doc_root)?;
+        ArchiveExtractor::extract(app, &download_id, &archive_path, &doc_root, 1)?;
 
         // 3. Delete archive
         let _ = std::fs::remove_file(&archive_path);
 
-        // 4. Flatten laravel-master/ subdirectory
-        utils::flatten_extracted_dir(&doc_root,"laravel-")?;
-
-        // 5. Ensure public/ directory exists
+        // 4. Ensure public/ directory exists
         let public_dir = doc_root.join("public");
         std::fs::create_dir_all(&public_dir)?;
 
-        // 6. Copy .env.example → .env if present
+        // 5. Copy .env.example → .env if present
         Self::emit_progress(app, site_id, "configuring", "Configuring Laravel...");
         let env_example = doc_root.join(".env.example");
         let env_file = doc_root.join(".env");
         if env_example.exists() && !env_file.exists() {
             std::fs::copy(&env_example, &env_file)?;
         }
+        if env_file.exists() {
+            Self::configure_laravel_mail(&env_file)?;
+        }
+
+        // 6. Install dependencies; without vendor/ every request fails
+        Self::emit_progress(app, site_id, "configuring", "Installing Composer dependencies...");
+        ComposerManager::ensure_installed(app, &download_id).await?;
+        ComposerManager::execute(app, &site, vec!["install".to_string()]).await?;
+
+        // 7. App key, SQLite database and migrations, as `composer create-project` does
+        Self::emit_progress(app, site_id, "configuring", "Generating app key...");
+        let post_create = vec!["run-script".to_string(), "post-create-project-cmd".to_string()];
+        if let Err(e) = ComposerManager::execute(app, &site, post_create).await {
+            log::warn!("Laravel post-create scripts failed for site {}: {}", site_id, e);
+        }
 
         log::info!("Laravel installed for site {}", site_id);
         Ok(())
     }
 
+    /// Point the .env mail settings at the mail catcher.
+    fn configure_laravel_mail(env_file: &std::path::Path) -> Result<(), AppError> {
+        let port = MailCatcher::port().to_string();
+        let settings = [
+            ("MAIL_MAILER", "smtp"),
+            ("MAIL_HOST", "127.0.0.1"),
+            ("MAIL_PORT", port.as_str()),
+            ("MAIL_USERNAME", "null"),
+            ("MAIL_PASSWORD", "null"),
+        ];
+
+        let content = std::fs::read_to_string(env_file)?;
+        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+        for (key, value) in settings {
+            let entry = format!("{}={}", key, value);
+            let prefix = format!("{}=", key);
+            // Also catches commented-out defaults such as `# MAIL_HOST=`
+            match lines
+                .iter()
+                .position(|l| l.trim_start_matches(['#', ' ']).starts_with(&prefix))
+            {
+                Some(index) => lines[index] = entry,
+                None => lines.push(entry),
+            }
+        }
+        std::fs::write(env_file, lines.join("\n") + "\n")?;
+        Ok(())
+    }
+
     // ── Fat-Free Framework ──────────────────────────────────────────
 
     async fn setup_fatfree(app: &AppHandle, site_id: &str) -> Result<(), AppError> {
@@ -250,20 +318,17 @@ require_once ABSPATH . 'wp-settings.php';
 
         // 2. Extract
         Self::emit_progress(app, site_id, "extracting", "Extracting Fat-Free Framework...");
-        DownloadManager::extract_zip(app, &download_id, &archive_path, &doc_root)?;
+        ArchiveExtractor::extract(app, &download_id, &archive_path, &doc_root, 1)?;
 
         // 3. Delete archive
         let _ = std::fs::remove_file(&archive_path);
 
-        // 4. Flatten fatfree-master/ subdirectory
-        utils::flatten_extracted_dir(&doc_root,"fatfree-")?;
-
-        // 5. Create tmp/ directory (F3 uses it for cache, sessions, etc.)
+        // 4. Create tmp/ directory (F3 uses it for cache, sessions, etc.)
         Self::emit_progress(app, site_id, "configuring", "Configuring Fat-Free Framework...");
         let tmp_dir = doc_root.join("tmp");
         std::fs::create_dir_all(&tmp_dir)?;
 
-        // 6. Create a basic index.php entry point if not present
+        // 5. Create a basic index.php entry point if not present
         let index_php = doc_root.join("index.php");
         if !index_php.exists() {
             let starter = r#"<?php
@@ -0,0 +1,134 @@
+use crate::error::AppError;
+use crate::services::mariadb_manager::MariaDbManager;
+use crate::services::nginx_manager::NginxManager;
+use crate::services::php_catalogue::PhpCatalogue;
+use crate::services::php_manager::PhpManager;
+#[cfg(not(target_os = "macos"))]
+use crate::services::service_catalogue::ServiceCatalogue;
+#[cfg(target_os = "macos")]
+use crate::services::utils;
+use serde::Serialize;
+#[cfg(target_os = "macos")]
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// Installed component compared against the newest patch release available.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableUpgrade {
+    /// "php-8.3", "nginx" or "mariadb" (also the download/job id)
+    pub id: String,
+    pub name: String,
+    /// Service that has to be stopped while the binaries are swapped
+    pub service_id: String,
+    pub installed_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+    pub running: bool,
+}
+
+pub struct UpgradeManager;
+
+impl UpgradeManager {
+    fn entry(
+        id: String,
+        name: String,
+        service_id: String,
+        installed_version: Option<String>,
+        latest_version: Option<String>,
+        running: bool,
+    ) -> AvailableUpgrade {
+        let update_available = match (&installed_version, &latest_version) {
+            (Some(installed), Some(latest)) => PhpCatalogue::is_newer(latest, installed),
+            _ => false,
+        };
+        AvailableUpgrade {
+            id,
+            name,
+            service_id,
+            installed_version,
+            latest_version,
+            update_available,
+            running,
+        }
+    }
+
+    /// Newest version Homebrew offers: the outdated entry, else what is installed.
+    #[cfg(target_os = "macos")]
+    fn brew_latest(outdated: &HashMap<String, String>, formula: &str, installed: &Option<String>) -> Option<String> {
+        outdated.get(formula).cloned().or_else(|| installed.clone())
+    }
+
+    /// Installed PHP, Nginx and MariaDB versions with their newest patch release.
+    pub fn check() -> Vec<AvailableUpgrade> {
+        let mut upgrades = Vec::new();
+
+        #[cfg(target_os = "macos")]
+        let outdated = utils::brew_outdated();
+
+        for php in PhpManager::list_versions().into_iter().filter(|v| v.installed) {
+            #[cfg(target_os = "macos")]
+            let latest = Self::brew_latest(&outdated, &format!("php@{}", php.version), &php.full_version);
+            #[cfg(not(target_os = "macos"))]
+            let latest = Some(php.latest.clone());
+
+            upgrades.push(Self::entry(
+                format!("php-{}", php.version),
+                format!("PHP {}", php.version),
+                format!("php-fpm-{}", php.version),
+                php.full_version,
+                latest,
+                php.running,
+            ));
+        }
+
+        let nginx = NginxManager::get_info();
+        if nginx.installed {
+            #[cfg(target_os = "macos")]
+            let latest = Self::brew_latest(&outdated, "nginx", &nginx.version);
+            #[cfg(not(target_os = "macos"))]
+            let latest = ServiceCatalogue::latest("nginx").ok();
+
+            upgrades.push(Self::entry(
+                "nginx".to_string(),
+                "Nginx".to_string(),
+                "nginx".to_string(),
+                nginx.version,
+                latest,
+                nginx.running,
+            ));
+        }
+
+        let mariadb = MariaDbManager::get_info();
+        if mariadb.installed {
+            #[cfg(target_os = "macos")]
+            let latest = Self::brew_latest(&outdated, "mariadb", &mariadb.version);
+            #[cfg(not(target_os = "macos"))]
+            let latest = ServiceCatalogue::latest("mariadb").ok();
+
+            upgrades.push(Self::entry(
+                "mariadb".to_string(),
+                "MariaDB".to_string(),
+                "mariadb".to_string(),
+                mariadb.version,
+                latest,
+                mariadb.running,
+            ));
+        }
+
+        upgrades
+    }
+
+    /// Upgrade one component in place. The caller stops and restarts its service.
+    pub async fn upgrade(app: &AppHandle, id: &str) -> Result<(), AppError> {
+        match id {
+            "nginx" => NginxManager::upgrade(app).await.map(|_| ()),
+            "mariadb" => MariaDbManager::upgrade(app).await.map(|_| ()),
+            id if id.starts_with("php-") => {
+                let version = id.strip_prefix("php-").unwrap();
+                PhpManager::upgrade_version(app, version).await.map(|_| ())
+            }
+            _ => Err(AppError::NotFound(format!("Unknown component: '{}'", id))),
+        }
+    }
+}
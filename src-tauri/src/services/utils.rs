@@ -77,34 +77,6 @@ pub fn to_forward_slash(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
 
-/// After extracting an archive that creates a subdirectory
-/// (e.g. nginx-1.28.2/), move all contents up to base_dir.
-pub fn flatten_extracted_dir(base_dir: &Path, prefix: &str) -> Result<(), AppError> {
-    let mut extracted_dir = None;
-    for entry in std::fs::read_dir(base_dir)? {
-        let entry = entry?;
-        if entry.file_type()?.is_dir() {
-            let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with(prefix) {
-                extracted_dir = Some(entry.path());
-                break;
-            }
-        }
-    }
-
-    if let Some(sub_dir) = extracted_dir {
-        for entry in std::fs::read_dir(&sub_dir)? {
-            let entry = entry?;
-            let dest = base_dir.join(entry.file_name());
-            if !dest.exists() {
-                std::fs::rename(entry.path(), &dest)?;
-            }
-        }
-        let _ = std::fs::remove_dir_all(&sub_dir);
-    }
-    Ok(())
-}
-
 /// Check that Homebrew is installed (macOS only).
 #[cfg(target_os = "macos")]
 pub fn ensure_homebrew() -> Result<(), AppError> {
@@ -117,6 +89,106 @@ pub fn ensure_homebrew() -> Result<(), AppError> {
     Ok(())
 }
 
+/// Installed version of a Homebrew formula, read from its `opt/` symlink
+/// (`opt/nginx -> ../Cellar/nginx/1.28.2_1` gives "1.28.2").
+#[cfg(target_os = "macos")]
+pub fn brew_installed_version(formula: &str) -> Option<String> {
+    let target = std::fs::read_link(get_brew_prefix().join("opt").join(formula)).ok()?;
+    let dir_name = target.file_name()?.to_string_lossy().to_string();
+    Some(match dir_name.rsplit_once('_') {
+        Some((version, revision)) if revision.chars().all(|c| c.is_ascii_digit()) => version.to_string(),
+        _ => dir_name,
+    })
+}
+
+/// Outdated Homebrew formulae as short name → newest available version.
+#[cfg(target_os = "macos")]
+pub fn brew_outdated() -> std::collections::HashMap<String, String> {
+    let mut outdated = std::collections::HashMap::new();
+    let Ok(output) = Command::new("brew").args(["outdated", "--json=v2"]).output() else {
+        return outdated;
+    };
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    for formula in json["formulae"].as_array().into_iter().flatten() {
+        let (Some(name), Some(current)) = (formula["name"].as_str(), formula["current_version"].as_str()) else {
+            continue;
+        };
+        // Tap formulae are reported by full name, e.g. "shivammathur/php/php@8.3"
+        let short = name.rsplit('/').next().unwrap_or(name);
+        outdated.insert(short.to_string(), current.to_string());
+    }
+    outdated
+}
+
+/// Run `brew upgrade <formula>`.
+#[cfg(target_os = "macos")]
+pub fn brew_upgrade(formula: &str) -> Result<(), AppError> {
+    let output = Command::new("brew")
+        .args(["upgrade", formula])
+        .output()
+        .map_err(|e| AppError::Process(format!("brew upgrade failed: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // brew exits non-zero when there is nothing to upgrade
+        if !stderr.contains("already installed") {
+            return Err(AppError::Process(format!(
+                "brew upgrade {} failed: {}",
+                formula,
+                stderr.chars().take(500).collect::<String>()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Move `dir` aside and run `install` to populate a fresh copy, restoring the
+/// previous tree if the install fails. On success the old tree is left at the
+/// returned backup path so the caller can carry user files over, then delete it.
+#[cfg(not(target_os = "macos"))]
+pub async fn reinstall_dir<T, F>(dir: &Path, install: F) -> Result<(T, PathBuf), AppError>
+where
+    F: std::future::Future<Output = Result<T, AppError>>,
+{
+    let file_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let backup = dir.with_file_name(format!("{}.previous", file_name));
+    if backup.exists() {
+        std::fs::remove_dir_all(&backup)?;
+    }
+    std::fs::rename(dir, &backup)?;
+
+    match install.await {
+        Ok(value) => Ok((value, backup)),
+        Err(e) => {
+            log::warn!("Reinstall of {} failed, restoring previous version: {}", dir.display(), e);
+            let _ = std::fs::remove_dir_all(dir);
+            std::fs::rename(&backup, dir)?;
+            Err(e)
+        }
+    }
+}
+
+/// Copy files under `from` that are missing from `to`, keeping the relative layout.
+#[cfg(not(target_os = "macos"))]
+pub fn copy_missing_files(from: &Path, to: &Path) -> Result<(), AppError> {
+    if !from.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_missing_files(&entry.path(), &dest)?;
+        } else if !dest.exists() {
+            std::fs::create_dir_all(to)?;
+            std::fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
 /// Read a PID file and return the PID if the process is alive.
 pub fn read_pid_file(pid_path: &Path) -> (bool, Option<u32>) {
     if pid_path.exists() {
@@ -130,13 +202,26 @@ pub fn read_pid_file(pid_path: &Path) -> (bool, Option<u32>) {
     (false, None)
 }
 
-/// Map PHP version string to its FPM port.
-pub fn php_version_to_port(version: &str) -> u16 {
-    match version {
-        "8.1" => 9081,
-        "8.2" => 9082,
-        "8.3" => 9083,
-        "8.4" => 9084,
-        _ => 9085,
+/// Last `count` lines of a text file (empty if it cannot be read). Only the
+/// end of the file is read.
+pub fn tail_lines(path: &Path, count: usize) -> Vec<String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if file.seek(SeekFrom::Start(len.saturating_sub(64 * 1024))).is_err() {
+        return Vec::new();
+    }
+    let mut buf = Vec::new();
+    if file.read_to_end(&mut buf).is_err() {
+        return Vec::new();
     }
+    let content = String::from_utf8_lossy(&buf);
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|l| l.to_string())
+        .collect()
 }
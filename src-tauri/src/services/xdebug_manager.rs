@@ -0,0 +1,268 @@
+use crate::config::paths;
+use crate::error::AppError;
+use crate::services::extension_installer::ExtensionInstaller;
+use crate::services::php_manager::PhpManager;
+use crate::services::utils;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+const MODES: [&str; 7] = ["off", "develop", "debug", "profile", "coverage", "trace", "gcstats"];
+const START_WITH_REQUEST: [&str; 4] = ["yes", "trigger", "no", "default"];
+
+/// Xdebug values managed through php.ini. Anything else can still be set
+/// with the ini editor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XdebugConfig {
+    /// `xdebug.mode` entries, e.g. `["debug", "develop"]`
+    pub mode: Vec<String>,
+    pub client_host: String,
+    pub client_port: u16,
+    /// "trigger": only requests carrying XDEBUG_TRIGGER; "yes": every request
+    pub start_with_request: String,
+}
+
+impl Default for XdebugConfig {
+    fn default() -> Self {
+        Self {
+            mode: vec!["debug".to_string()],
+            client_host: "localhost".to_string(),
+            client_port: 9003,
+            start_with_request: "trigger".to_string(),
+        }
+    }
+}
+
+impl XdebugConfig {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.mode.is_empty() {
+            return Err(AppError::Config("Choose at least one Xdebug mode".to_string()));
+        }
+        if let Some(mode) = self.mode.iter().find(|m| !MODES.contains(&m.as_str())) {
+            return Err(AppError::Config(format!(
+                "Invalid Xdebug mode '{}' (expected {})",
+                mode,
+                MODES.join(", ")
+            )));
+        }
+        if self.mode.len() > 1 && self.mode.iter().any(|m| m == "off") {
+            return Err(AppError::Config("Xdebug mode 'off' cannot be combined with other modes".to_string()));
+        }
+        let valid_host = !self.client_host.is_empty()
+            && self
+                .client_host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '_'));
+        if !valid_host {
+            return Err(AppError::Config(format!("Invalid client host: '{}'", self.client_host)));
+        }
+        if self.client_port == 0 {
+            return Err(AppError::Config("Client port must be between 1 and 65535".to_string()));
+        }
+        if !START_WITH_REQUEST.contains(&self.start_with_request.as_str()) {
+            return Err(AppError::Config(format!(
+                "Invalid start_with_request '{}' (expected {})",
+                self.start_with_request,
+                START_WITH_REQUEST.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XdebugStatus {
+    pub version: String,
+    /// Module file present for this PHP version
+    pub installed: bool,
+    /// Loaded by PHP
+    pub enabled: bool,
+    pub config: XdebugConfig,
+    /// Where profiles and traces are written
+    pub output_dir: String,
+}
+
+/// A cachegrind profile or trace file written by Xdebug.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XdebugProfile {
+    pub name: String,
+    /// "profile" or "trace"
+    pub kind: String,
+    pub size: u64,
+    pub modified: String,
+}
+
+/// Xdebug per PHP version. The php.ini keeps it cheap for every site
+/// (`start_with_request = trigger` by default); sites with debugging turned
+/// on get `start_with_request = yes` through their nginx config instead.
+pub struct XdebugManager;
+
+impl XdebugManager {
+    pub fn get_output_dir() -> PathBuf {
+        paths::get_data_dir().join("data").join("xdebug")
+    }
+
+    pub fn get_status(version: &str) -> Result<XdebugStatus, AppError> {
+        let enabled = PhpManager::is_extension_loaded(version, "xdebug")?;
+        let installed = enabled || ExtensionInstaller::is_installed(version, "xdebug")?;
+
+        // Effective values once loaded, php.ini otherwise
+        let values: HashMap<String, String> = PhpManager::get_ini(version)?
+            .into_iter()
+            .filter(|d| d.key.starts_with("xdebug."))
+            .map(|d| (d.key, d.value))
+            .chain(if enabled {
+                PhpManager::get_effective_ini(version)?
+                    .into_iter()
+                    .filter(|(k, _)| k.starts_with("xdebug."))
+                    .collect()
+            } else {
+                Vec::new()
+            })
+            .collect();
+
+        let defaults = XdebugConfig::default();
+        let value = |key: &str| {
+            values
+                .get(key)
+                .map(|v| v.trim_matches('"').to_string())
+                .filter(|v| !v.is_empty() && v != "no value")
+        };
+        let config = XdebugConfig {
+            mode: value("xdebug.mode")
+                .map(|m| m.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+                .unwrap_or(defaults.mode),
+            client_host: value("xdebug.client_host").unwrap_or(defaults.client_host),
+            client_port: value("xdebug.client_port")
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.client_port),
+            start_with_request: value("xdebug.start_with_request").unwrap_or(defaults.start_with_request),
+        };
+
+        Ok(XdebugStatus {
+            version: version.to_string(),
+            installed,
+            enabled,
+            config,
+            output_dir: Self::get_output_dir().to_string_lossy().to_string(),
+        })
+    }
+
+    /// Write `config` to php.ini and load or unload the extension.
+    /// Restarting PHP-FPM is left to the caller.
+    pub fn apply(version: &str, enabled: bool, config: &XdebugConfig) -> Result<XdebugStatus, AppError> {
+        config.validate()?;
+        let loaded = PhpManager::is_extension_loaded(version, "xdebug")?;
+        if enabled && !loaded && !ExtensionInstaller::is_installed(version, "xdebug")? {
+            return Err(AppError::NotFound(format!(
+                "Xdebug is not installed for PHP {}; install it from the Extensions tab first",
+                version
+            )));
+        }
+
+        let output_dir = Self::get_output_dir();
+        std::fs::create_dir_all(&output_dir)?;
+
+        let mut directives = BTreeMap::new();
+        directives.insert("xdebug.mode".to_string(), config.mode.join(","));
+        directives.insert("xdebug.client_host".to_string(), config.client_host.clone());
+        directives.insert("xdebug.client_port".to_string(), config.client_port.to_string());
+        directives.insert("xdebug.start_with_request".to_string(), config.start_with_request.clone());
+        directives.insert(
+            "xdebug.output_dir".to_string(),
+            format!("\"{}\"", utils::to_forward_slash(&output_dir)),
+        );
+        // %H: host of the request, so profiles of different sites are told apart
+        directives.insert("xdebug.profiler_output_name".to_string(), "cachegrind.out.%H.%t.%p".to_string());
+        directives.insert("xdebug.trace_output_name".to_string(), "trace.%H.%t.%p".to_string());
+        PhpManager::set_ini_directives(version, &directives, "xdebug")?;
+
+        if enabled != loaded {
+            PhpManager::toggle_extension(version, "xdebug", enabled)?;
+        }
+        if enabled && !PhpManager::is_extension_loaded(version, "xdebug")? {
+            return Err(AppError::Process(format!(
+                "PHP {} does not load Xdebug (check `php -m` for startup errors)",
+                version
+            )));
+        }
+
+        log::info!(
+            "Xdebug for PHP {} {} (mode {})",
+            version,
+            if enabled { "enabled" } else { "disabled" },
+            config.mode.join(",")
+        );
+        Self::get_status(version)
+    }
+
+    // --- Profiler output ---
+
+    /// Newest first.
+    pub fn list_profiles() -> Result<Vec<XdebugProfile>, AppError> {
+        let dir = Self::get_output_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut profiles: Vec<XdebugProfile> = std::fs::read_dir(&dir)?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let kind = if name.starts_with("cachegrind.out.") {
+                    "profile"
+                } else if name.starts_with("trace.") {
+                    "trace"
+                } else {
+                    return None;
+                };
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339())
+                    .unwrap_or_default();
+                Some(XdebugProfile {
+                    name,
+                    kind: kind.to_string(),
+                    size: metadata.len(),
+                    modified,
+                })
+            })
+            .collect();
+        profiles.sort_by(|a, b| b.modified.cmp(&a.modified));
+        Ok(profiles)
+    }
+
+    /// Path of a profile for opening in an external viewer.
+    pub fn get_profile_path(name: &str) -> Result<PathBuf, AppError> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if !valid {
+            return Err(AppError::Config(format!("Invalid profile name: '{}'", name)));
+        }
+        let path = Self::get_output_dir().join(name);
+        if !path.exists() {
+            return Err(AppError::NotFound(format!("Profile {} not found", name)));
+        }
+        Ok(path)
+    }
+
+    pub fn delete_profile(name: &str) -> Result<(), AppError> {
+        std::fs::remove_file(Self::get_profile_path(name)?)?;
+        Ok(())
+    }
+
+    pub fn clear_profiles() -> Result<usize, AppError> {
+        let profiles = Self::list_profiles()?;
+        for profile in &profiles {
+            let _ = Self::delete_profile(&profile.name);
+        }
+        Ok(profiles.len())
+    }
+}
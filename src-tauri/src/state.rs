@@ -1,7 +1,11 @@
+use crate::config::app_config::AppConfig;
+use crate::services::download_manager::{DownloadJob, DownloadManager};
+use crate::services::service_supervisor::SupervisorState;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Child;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
 use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -29,7 +33,12 @@ pub struct ServiceInfo {
 pub struct AppState {
     pub services: Mutex<HashMap<String, ServiceInfo>>,
     pub child_processes: Mutex<HashMap<String, Child>>,
+    pub supervisor: Mutex<SupervisorState>,
     pub log_tail_cancel: Mutex<Option<CancellationToken>>,
+    pub mail_catcher_cancel: Mutex<Option<CancellationToken>>,
+    pub download_cancel: Mutex<HashMap<String, CancellationToken>>,
+    pub download_jobs: Mutex<Vec<DownloadJob>>,
+    pub download_slots: Arc<Semaphore>,
 }
 
 impl AppState {
@@ -41,6 +50,7 @@ impl AppState {
             ("nginx", "Nginx", Some(8080u16)),
             ("mariadb", "MariaDB", Some(3306u16)),
             ("phpmyadmin", "phpMyAdmin", None),
+            ("mailcatcher", "Mail Catcher", Some(AppConfig::load().mail_catcher.port)),
         ];
 
         for (id, name, port) in default_services {
@@ -62,7 +72,12 @@ impl AppState {
         Self {
             services: Mutex::new(services),
             child_processes: Mutex::new(HashMap::new()),
+            supervisor: Mutex::new(SupervisorState::default()),
             log_tail_cancel: Mutex::new(None),
+            mail_catcher_cancel: Mutex::new(None),
+            download_cancel: Mutex::new(HashMap::new()),
+            download_jobs: Mutex::new(DownloadManager::load_history()),
+            download_slots: Arc::new(Semaphore::new(AppConfig::load().max_concurrent_downloads.max(1))),
         }
     }
 }
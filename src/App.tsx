@@ -8,6 +8,7 @@ import { PhpPage } from "@/pages/PhpPage"
 import { DatabasePage } from "@/pages/DatabasePage"
 import { SslPage } from "@/pages/SslPage"
 import { LogsPage } from "@/pages/LogsPage"
+import { MailPage } from "@/pages/MailPage"
 import { SettingsPage } from "@/pages/SettingsPage"
 import { AiPage } from "@/pages/AiPage"
 
@@ -23,6 +24,7 @@ function App() {
           <Route path="database" element={<DatabasePage />} />
           <Route path="ssl" element={<SslPage />} />
           <Route path="logs" element={<LogsPage />} />
+          <Route path="mail" element={<MailPage />} />
           <Route path="settings" element={<SettingsPage />} />
           <Route path="ai" element={<AiPage />} />
           <Route path="*" element={<Navigate to="/" replace />} />
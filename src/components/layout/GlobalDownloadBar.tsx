@@ -1,15 +1,14 @@
 import { useEffect, useState } from "react"
 import { listen } from "@tauri-apps/api/event"
 import { Progress } from "@/components/ui/progress"
-import { Download, Loader2, CheckCircle2, XCircle } from "lucide-react"
+import { toast } from "sonner"
+import { Download, Loader2, CheckCircle2, XCircle, Clock, Ban, X } from "lucide-react"
+import { Button } from "@/components/ui/button"
 import type { DownloadProgress } from "@/types/php"
 import { formatBytes } from "@/lib/utils"
+import * as tauri from "@/lib/tauri"
 
 const LABELS: Record<string, string> = {
-  "php-8.1": "PHP 8.1",
-  "php-8.2": "PHP 8.2",
-  "php-8.3": "PHP 8.3",
-  "php-8.4": "PHP 8.4",
   nginx: "Nginx",
   mariadb: "MariaDB",
   mkcert: "mkcert",
@@ -23,8 +22,8 @@ export function GlobalDownloadBar() {
       const p = event.payload
       setDownloads((prev) => ({ ...prev, [p.id]: p }))
 
-      // Auto-remove completed/failed downloads after delay
-      if (p.status === "completed" || p.status === "failed") {
+      // Auto-remove finished downloads after delay
+      if (p.status === "completed" || p.status === "failed" || p.status === "cancelled") {
         setTimeout(() => {
           setDownloads((prev) => {
             const next = { ...prev }
@@ -40,16 +39,41 @@ export function GlobalDownloadBar() {
     }
   }, [])
 
+  const cancel = async (id: string) => {
+    try {
+      const cancelled = await tauri.downloadCancel(id)
+      if (!cancelled) toast.info("Nothing left to cancel")
+    } catch (err) {
+      toast.error("Failed to cancel download", { description: String(err) })
+    }
+  }
+
   const active = Object.values(downloads)
   if (active.length === 0) return null
 
   return (
     <div className="border-t bg-muted/30 px-4 py-2 space-y-2">
       {active.map((dl) => {
-        const label = LABELS[dl.id] || dl.id
+        const label = LABELS[dl.id] || (dl.id.startsWith("php-") ? `PHP ${dl.id.slice(4)}` : dl.id)
         const isExtracting = dl.status === "extracting"
         const isCompleted = dl.status === "completed"
         const isFailed = dl.status === "failed"
+        const isCancelled = dl.status === "cancelled"
+        const isQueued = dl.status === "queued"
+        const isFinished = isCompleted || isFailed || isCancelled
+        const statusText = isFailed
+          ? (dl.message || "Download failed")
+          : isCancelled
+            ? (dl.message || "Cancelled")
+            : isQueued
+              ? (dl.message || "Queued")
+              : isCompleted
+                ? (dl.message || "Completed")
+                : isExtracting
+                  ? (dl.message || "Installing...")
+                  : dl.total
+                    ? `${formatBytes(dl.downloaded)} / ${formatBytes(dl.total)}`
+                    : `${formatBytes(dl.downloaded)}`
 
         return (
           <div key={dl.id} className="flex items-center gap-3">
@@ -58,6 +82,10 @@ export function GlobalDownloadBar() {
                 <CheckCircle2 className="h-3.5 w-3.5 text-emerald-500 shrink-0" />
               ) : isFailed ? (
                 <XCircle className="h-3.5 w-3.5 text-destructive shrink-0" />
+              ) : isCancelled ? (
+                <Ban className="h-3.5 w-3.5 text-muted-foreground shrink-0" />
+              ) : isQueued ? (
+                <Clock className="h-3.5 w-3.5 text-muted-foreground shrink-0" />
               ) : isExtracting ? (
                 <Loader2 className="h-3.5 w-3.5 animate-spin text-muted-foreground shrink-0" />
               ) : (
@@ -68,7 +96,7 @@ export function GlobalDownloadBar() {
               </span>
             </div>
 
-            {!isFailed && (
+            {!isFailed && !isCancelled && (
               <Progress
                 value={isExtracting || isCompleted ? 100 : dl.percent}
                 className={`h-1.5 flex-1 ${isExtracting ? "animate-pulse" : ""}`}
@@ -76,22 +104,26 @@ export function GlobalDownloadBar() {
             )}
 
             <span className={`text-[11px] min-w-[100px] text-right ${isFailed ? "text-destructive" : "text-muted-foreground"}`}>
-              {isFailed
-                ? (dl.message || "Download failed")
-                : isCompleted
-                  ? (dl.message || "Completed")
-                  : isExtracting
-                    ? (dl.message || "Installing...")
-                    : dl.total
-                      ? `${formatBytes(dl.downloaded)} / ${formatBytes(dl.total)}`
-                      : `${formatBytes(dl.downloaded)}`}
+              {statusText}
             </span>
 
-            {!isFailed && !isCompleted && !isExtracting && (
+            {!isFinished && !isExtracting && !isQueued && (
               <span className="text-[11px] font-medium min-w-[36px] text-right">
                 {`${dl.percent.toFixed(0)}%`}
               </span>
             )}
+
+            {!isFinished && (
+              <Button
+                size="sm"
+                variant="ghost"
+                className="h-6 w-6 p-0 shrink-0"
+                title="Cancel"
+                onClick={() => cancel(dl.id)}
+              >
+                <X className="h-3.5 w-3.5" />
+              </Button>
+            )}
           </div>
         )
       })}
@@ -1,15 +1,22 @@
 import { Outlet } from "react-router"
 import { useEffect } from "react"
+import { listen } from "@tauri-apps/api/event"
+import { toast } from "sonner"
 import { Sidebar } from "./Sidebar"
 import { TopBar } from "./TopBar"
 import { GlobalDownloadBar } from "./GlobalDownloadBar"
 import { ErrorBoundary } from "@/components/shared/ErrorBoundary"
 import { useAppStore } from "@/stores/appStore"
 import { useServiceStore } from "@/stores/serviceStore"
+import { useMailStore } from "@/stores/mailStore"
+import type { ServiceStatusEvent } from "@/types/service"
+import type { MailSummary } from "@/types/mail"
 
 export function MainLayout() {
   const initialize = useAppStore((s) => s.initialize)
   const fetchServices = useServiceStore((s) => s.fetchServices)
+  const fetchExits = useServiceStore((s) => s.fetchExits)
+  const addReceivedMail = useMailStore((s) => s.addReceived)
 
   useEffect(() => {
     initialize()
@@ -22,6 +29,34 @@ export function MainLayout() {
     return () => clearInterval(interval)
   }, [fetchServices])
 
+  // Crashes and automatic restarts reported by the supervisor
+  useEffect(() => {
+    const unlisten = listen<ServiceStatusEvent>("service-status", (event) => {
+      const { service, message, exit } = event.payload
+      if (service.status === "running") {
+        toast.success(message)
+      } else {
+        toast.error(exit ? `${service.name} crashed` : `${service.name} is down`, { description: message })
+      }
+      fetchServices()
+      fetchExits()
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [fetchServices, fetchExits])
+
+  // Messages caught by the mail catcher
+  useEffect(() => {
+    const unlisten = listen<MailSummary>("mail-received", (event) => {
+      addReceivedMail(event.payload)
+      toast.info(event.payload.subject, { description: `Mail from ${event.payload.from}` })
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [addReceivedMail])
+
   return (
     <div className="flex h-screen overflow-hidden bg-background">
       <Sidebar />
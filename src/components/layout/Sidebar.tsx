@@ -7,6 +7,7 @@ import {
   Database,
   Shield,
   ScrollText,
+  Mail,
   Settings,
   Bot,
   Github,
@@ -27,6 +28,7 @@ const iconMap = {
   Database,
   Shield,
   ScrollText,
+  Mail,
   Settings,
   Bot,
 } as const
@@ -39,6 +41,7 @@ const navItems = [
   { path: "/database", label: "Database", icon: "Database" as const },
   { path: "/ssl", label: "SSL", icon: "Shield" as const },
   { path: "/logs", label: "Logs", icon: "ScrollText" as const },
+  { path: "/mail", label: "Mail", icon: "Mail" as const },
   { path: "/ai", label: "AI Assistant", icon: "Bot" as const },
   { path: "/settings", label: "Settings", icon: "Settings" as const },
 ]
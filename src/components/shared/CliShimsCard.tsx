@@ -0,0 +1,87 @@
+import { useEffect, useState } from "react"
+import { Copy, Terminal } from "lucide-react"
+import { toast } from "sonner"
+import { Card } from "@/components/ui/card"
+import { Button } from "@/components/ui/button"
+import { Badge } from "@/components/ui/badge"
+import type { ShimsInfo } from "@/types/config"
+import * as tauri from "@/lib/tauri"
+
+export function CliShimsCard() {
+  const [info, setInfo] = useState<ShimsInfo | null>(null)
+  const [busy, setBusy] = useState(false)
+
+  useEffect(() => {
+    tauri.cliGetShimsInfo().then(setInfo).catch(() => setInfo(null))
+  }, [])
+
+  const run = async (action: () => Promise<ShimsInfo>, message: string) => {
+    setBusy(true)
+    try {
+      setInfo(await action())
+      toast.success(message)
+    } catch (err) {
+      toast.error("Failed to update CLI shims", { description: String(err) })
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const copyPathLine = async () => {
+    if (!info) return
+    await navigator.clipboard.writeText(info.pathLine)
+    toast.success("Copied to clipboard")
+  }
+
+  if (!info) return null
+
+  return (
+    <Card className="p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-sm font-medium flex items-center gap-1.5">
+            <Terminal className="h-4 w-4" />
+            Command Line
+          </h3>
+          <p className="text-[11px] text-muted-foreground">
+            {info.shims.join(", ")} in your terminal, using the site's PHP version, a .php-version file or the
+            default version
+          </p>
+        </div>
+        <div className="flex items-center gap-2">
+          {info.installed && (
+            <Badge variant="outline" className="text-[10px]">
+              {info.onPath ? "On PATH" : "Not on PATH"}
+            </Badge>
+          )}
+          {info.installed && (
+            <Button
+              size="sm"
+              variant="outline"
+              disabled={busy}
+              onClick={() => run(tauri.cliRemoveShims, "CLI shims removed")}
+            >
+              Remove
+            </Button>
+          )}
+          <Button size="sm" disabled={busy} onClick={() => run(tauri.cliInstallShims, "CLI shims written")}>
+            {info.installed ? "Regenerate" : "Install"}
+          </Button>
+        </div>
+      </div>
+      {info.installed && !info.onPath && (
+        <div className="space-y-1.5">
+          <p className="text-[11px] text-muted-foreground">Add this line to your shell profile:</p>
+          <div className="flex items-center gap-2">
+            <code className="flex-1 rounded bg-muted/50 px-2 py-1.5 font-mono text-xs truncate" title={info.pathLine}>
+              {info.pathLine}
+            </code>
+            <Button size="icon" variant="ghost" className="h-7 w-7" title="Copy" onClick={copyPathLine}>
+              <Copy className="h-3.5 w-3.5" />
+            </Button>
+          </div>
+        </div>
+      )}
+    </Card>
+  )
+}
@@ -0,0 +1,142 @@
+import { useCallback, useEffect, useState } from "react"
+import { Activity, AlertTriangle, RefreshCw } from "lucide-react"
+import { Card } from "@/components/ui/card"
+import { Button } from "@/components/ui/button"
+import { Badge } from "@/components/ui/badge"
+import type { FpmStatus, SlowlogEntry } from "@/types/php"
+import * as tauri from "@/lib/tauri"
+
+interface FpmMonitorCardProps {
+  version: string
+  running: boolean
+}
+
+const POLL_INTERVAL = 5000
+
+export function FpmMonitorCard({ version, running }: FpmMonitorCardProps) {
+  const [status, setStatus] = useState<FpmStatus | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [slowlog, setSlowlog] = useState<SlowlogEntry[]>([])
+  const [expanded, setExpanded] = useState<number | null>(null)
+
+  const refresh = useCallback(async () => {
+    try {
+      setSlowlog(await tauri.phpFpmSlowlog(version, undefined, 20))
+    } catch {}
+    if (!running) {
+      setStatus(null)
+      return
+    }
+    try {
+      setStatus(await tauri.phpFpmStatus(version))
+      setError(null)
+    } catch (err) {
+      setStatus(null)
+      setError(String(err))
+    }
+  }, [version, running])
+
+  useEffect(() => {
+    refresh()
+    if (!running) return
+    const timer = setInterval(refresh, POLL_INTERVAL)
+    return () => clearInterval(timer)
+  }, [refresh, running])
+
+  const stats = status
+    ? [
+        { label: "Active", value: `${status.activeProcesses} / ${status.maxChildren}` },
+        { label: "Idle", value: status.idleProcesses },
+        { label: "Listen Queue", value: status.listenQueue },
+        { label: "Max Children Reached", value: status.maxChildrenReached },
+        { label: "Slow Requests", value: status.slowRequests },
+        { label: "Accepted", value: status.acceptedConn },
+      ]
+    : []
+  const busy = status?.processes.filter((p) => p.state !== "Idle") ?? []
+
+  return (
+    <Card className="p-4 mb-6">
+      <div className="flex items-center justify-between mb-3">
+        <h3 className="text-sm font-medium flex items-center gap-1.5">
+          <Activity className="h-4 w-4" />
+          Workers
+        </h3>
+        <Button size="icon" variant="ghost" className="h-7 w-7" title="Refresh" onClick={refresh}>
+          <RefreshCw className="h-3.5 w-3.5" />
+        </Button>
+      </div>
+
+      {!running ? (
+        <p className="text-xs text-muted-foreground mb-4">Start PHP-FPM {version} to see its workers.</p>
+      ) : error ? (
+        <p className="text-xs text-muted-foreground mb-4">{error}</p>
+      ) : (
+        status && (
+          <>
+            {status.saturated && (
+              <div className="flex items-center gap-2 mb-3 text-xs text-amber-500">
+                <AlertTriangle className="h-3.5 w-3.5" />
+                All {status.maxChildren} workers are busy; new requests wait in the queue. Raise Max Children or find
+                the slow requests below.
+              </div>
+            )}
+            <div className="grid grid-cols-6 gap-3 mb-4">
+              {stats.map((s) => (
+                <div key={s.label}>
+                  <p className="text-[11px] text-muted-foreground">{s.label}</p>
+                  <p className="text-sm font-medium">{s.value}</p>
+                </div>
+              ))}
+            </div>
+            {busy.length > 0 && (
+              <div className="space-y-0.5 mb-4">
+                {busy.map((p) => (
+                  <div key={p.pid} className="flex items-center gap-2 text-xs font-mono">
+                    <Badge variant="outline" className="text-[10px]">{p.state}</Badge>
+                    <span className="text-muted-foreground">{p.pid}</span>
+                    <span className="truncate">
+                      {p.requestMethod} {p.requestUri}
+                    </span>
+                    <span className="ml-auto text-muted-foreground shrink-0">
+                      {(p.requestDuration / 1_000_000).toFixed(1)}s
+                    </span>
+                  </div>
+                ))}
+              </div>
+            )}
+          </>
+        )
+      )}
+
+      <h3 className="text-sm font-medium mb-2">Slowlog</h3>
+      {slowlog.length === 0 ? (
+        <p className="text-xs text-muted-foreground">No slow requests logged.</p>
+      ) : (
+        <div className="space-y-0.5 max-h-72 overflow-y-auto">
+          {slowlog.map((entry, i) => (
+            <div key={`${entry.time}-${entry.pid}`}>
+              <button
+                className="w-full flex items-center gap-2 py-1 px-2 rounded hover:bg-muted/50 text-left"
+                onClick={() => setExpanded(expanded === i ? null : i)}
+              >
+                <span className="text-xs text-muted-foreground shrink-0">{entry.time}</span>
+                <span className="text-xs font-mono truncate">{entry.script}</span>
+                <span className="ml-auto text-xs text-muted-foreground shrink-0 font-mono">{entry.trace[0]}</span>
+              </button>
+              {expanded === i && (
+                <div className="mx-2 mb-2 rounded bg-muted/50 p-2 font-mono text-xs space-y-0.5">
+                  {entry.trace.map((frame, j) => (
+                    <p key={j} className="truncate" title={frame}>
+                      {frame}
+                    </p>
+                  ))}
+                </div>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </Card>
+  )
+}
@@ -0,0 +1,106 @@
+import { useEffect, useState } from "react"
+import { Card } from "@/components/ui/card"
+import { Button } from "@/components/ui/button"
+import { Input } from "@/components/ui/input"
+import { Label } from "@/components/ui/label"
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select"
+import type { FpmSettings } from "@/types/php"
+
+interface FpmSettingsCardProps {
+  version: string
+  settings: FpmSettings | null
+  onSave: (settings: FpmSettings) => Promise<void>
+}
+
+const NUMBER_FIELDS: { key: keyof FpmSettings; label: string; modes?: FpmSettings["pm"][]; suffix?: string }[] = [
+  { key: "maxChildren", label: "Max Children" },
+  { key: "startServers", label: "Start Servers", modes: ["dynamic"] },
+  { key: "minSpareServers", label: "Min Spare Servers", modes: ["dynamic"] },
+  { key: "maxSpareServers", label: "Max Spare Servers", modes: ["dynamic"] },
+  { key: "processIdleTimeout", label: "Idle Timeout", modes: ["ondemand"], suffix: "sec" },
+  { key: "requestTerminateTimeout", label: "Request Timeout", suffix: "sec" },
+  { key: "slowlogTimeout", label: "Slowlog After", suffix: "sec" },
+  { key: "maxRequests", label: "Max Requests" },
+]
+
+export function FpmSettingsCard({ version, settings, onSave }: FpmSettingsCardProps) {
+  const [form, setForm] = useState<FpmSettings | null>(settings)
+  const [saving, setSaving] = useState(false)
+
+  useEffect(() => {
+    setForm(settings)
+  }, [settings])
+
+  if (!form) return null
+
+  const handleSave = async () => {
+    setSaving(true)
+    try {
+      await onSave(form)
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  return (
+    <Card className="p-4 mb-6">
+      <div className="flex items-center justify-between mb-4">
+        <div>
+          <h3 className="text-sm font-medium">PHP-FPM {version}</h3>
+          <p className="text-xs text-muted-foreground">Process manager of the shared pool. 0 means unlimited.</p>
+        </div>
+        <Button size="sm" onClick={handleSave} disabled={saving}>
+          {saving ? "Saving..." : "Save & Reload"}
+        </Button>
+      </div>
+      <div className="grid grid-cols-3 gap-x-6 gap-y-4">
+        <div className="space-y-1.5">
+          <Label className="text-sm">Mode</Label>
+          <Select value={form.pm} onValueChange={(pm) => setForm({ ...form, pm: pm as FpmSettings["pm"] })}>
+            <SelectTrigger className="w-full">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="static">static</SelectItem>
+              <SelectItem value="dynamic">dynamic</SelectItem>
+              <SelectItem value="ondemand">ondemand</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-1.5">
+          <Label className="text-sm">Log Level</Label>
+          <Select
+            value={form.logLevel}
+            onValueChange={(logLevel) => setForm({ ...form, logLevel: logLevel as FpmSettings["logLevel"] })}
+          >
+            <SelectTrigger className="w-full">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {["alert", "error", "warning", "notice", "debug"].map((level) => (
+                <SelectItem key={level} value={level}>
+                  {level}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        {NUMBER_FIELDS.filter((f) => !f.modes || f.modes.includes(form.pm)).map((field) => (
+          <div key={field.key} className="space-y-1.5">
+            <Label className="text-sm">{field.label}</Label>
+            <div className="flex gap-1.5 items-center">
+              <Input
+                type="number"
+                min={0}
+                value={form[field.key] as number}
+                className="flex-1"
+                onChange={(e) => setForm({ ...form, [field.key]: parseInt(e.target.value) || 0 })}
+              />
+              {field.suffix && <span className="text-xs text-muted-foreground w-8">{field.suffix}</span>}
+            </div>
+          </div>
+        ))}
+      </div>
+    </Card>
+  )
+}
@@ -0,0 +1,197 @@
+import { useState } from "react"
+import { History, RotateCcw, Save, Trash2, GitCompare } from "lucide-react"
+import { toast } from "sonner"
+import { Card } from "@/components/ui/card"
+import { Button } from "@/components/ui/button"
+import { Badge } from "@/components/ui/badge"
+import { Input } from "@/components/ui/input"
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select"
+import { ConfirmDialog } from "@/components/shared/ConfirmDialog"
+import type { IniChange, IniPreset, IniSnapshot, PhpIniDirective } from "@/types/php"
+import * as tauri from "@/lib/tauri"
+
+interface IniHistoryCardProps {
+  version: string
+  presets: IniPreset[]
+  snapshots: IniSnapshot[]
+  directives: PhpIniDirective[]
+  onApplyPreset: (name: string) => Promise<void>
+  onSavePreset: (preset: IniPreset) => Promise<void>
+  onDeletePreset: (name: string) => Promise<void>
+  onRestore: (id: string) => Promise<void>
+  onDeleteSnapshot: (id: string) => Promise<void>
+}
+
+export function IniHistoryCard({
+  version,
+  presets,
+  snapshots,
+  directives,
+  onApplyPreset,
+  onSavePreset,
+  onDeletePreset,
+  onRestore,
+  onDeleteSnapshot,
+}: IniHistoryCardProps) {
+  const [selectedPreset, setSelectedPreset] = useState("")
+  const [newPresetName, setNewPresetName] = useState("")
+  const [diff, setDiff] = useState<{ id: string; changes: IniChange[] } | null>(null)
+
+  const preset = presets.find((p) => p.name === selectedPreset)
+
+  // Capture current values; extensions are managed on their own tab
+  const handleSaveCurrent = async () => {
+    const name = newPresetName.trim()
+    if (!name) return
+    const values = Object.fromEntries(
+      directives.filter((d) => !d.key.endsWith("extension")).map((d) => [d.key, d.value]),
+    )
+    await onSavePreset({ name, description: `Captured from PHP ${version}`, builtin: false, directives: values })
+    setNewPresetName("")
+  }
+
+  const showDiff = async (id: string) => {
+    if (diff?.id === id) {
+      setDiff(null)
+      return
+    }
+    try {
+      const changes = await tauri.phpDiffIniSnapshots(version, id)
+      setDiff({ id, changes })
+    } catch (err) {
+      toast.error("Failed to compare snapshot", { description: String(err) })
+    }
+  }
+
+  return (
+    <Card className="p-4 mb-6">
+      <h3 className="text-sm font-medium mb-1">Presets</h3>
+      <p className="text-xs text-muted-foreground mb-3">
+        Switch PHP {version} between development and production-like values. php.ini is snapshotted before every change.
+      </p>
+      <div className="flex items-center gap-2 mb-2">
+        <Select value={selectedPreset} onValueChange={setSelectedPreset}>
+          <SelectTrigger className="w-56 h-8">
+            <SelectValue placeholder="Choose a preset" />
+          </SelectTrigger>
+          <SelectContent>
+            {presets.map((p) => (
+              <SelectItem key={p.name} value={p.name}>
+                {p.name}
+                {p.builtin ? "" : " (custom)"}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <Button size="sm" disabled={!preset} onClick={() => preset && onApplyPreset(preset.name)}>
+          Apply
+        </Button>
+        {preset && !preset.builtin && (
+          <ConfirmDialog
+            trigger={
+              <Button size="sm" variant="ghost" className="text-destructive">
+                <Trash2 className="h-3.5 w-3.5" />
+              </Button>
+            }
+            title={`Delete preset "${preset.name}"?`}
+            description="The preset is removed. php.ini files it was applied to are not changed."
+            confirmLabel="Delete"
+            onConfirm={() => {
+              onDeletePreset(preset.name)
+              setSelectedPreset("")
+            }}
+          />
+        )}
+      </div>
+      {preset && (
+        <div className="mb-3 rounded bg-muted/50 p-2">
+          {preset.description && <p className="text-xs text-muted-foreground mb-1">{preset.description}</p>}
+          <div className="flex flex-wrap gap-1">
+            {Object.entries(preset.directives).slice(0, 24).map(([k, v]) => (
+              <Badge key={k} variant="outline" className="text-[10px] font-mono font-normal">
+                {k} = {v}
+              </Badge>
+            ))}
+          </div>
+        </div>
+      )}
+      <div className="flex items-center gap-2 mb-5">
+        <Input
+          placeholder="Save current values as preset..."
+          value={newPresetName}
+          onChange={(e) => setNewPresetName(e.target.value)}
+          className="w-56 h-8"
+        />
+        <Button size="sm" variant="outline" disabled={!newPresetName.trim()} onClick={handleSaveCurrent}>
+          <Save className="h-3.5 w-3.5 mr-1.5" />
+          Save
+        </Button>
+      </div>
+
+      <h3 className="text-sm font-medium mb-2 flex items-center gap-1.5">
+        <History className="h-4 w-4" />
+        Snapshots
+      </h3>
+      {snapshots.length === 0 ? (
+        <p className="text-xs text-muted-foreground">No snapshots yet. One is taken before every php.ini change.</p>
+      ) : (
+        <div className="space-y-0.5 max-h-72 overflow-y-auto">
+          {snapshots.map((s) => (
+            <div key={s.id}>
+              <div className="flex items-center justify-between py-1 px-2 rounded hover:bg-muted/50 group">
+                <div className="flex items-center gap-2 min-w-0">
+                  <span className="text-xs text-muted-foreground shrink-0">
+                    {new Date(s.createdAt).toLocaleString()}
+                  </span>
+                  <span className="text-xs font-mono truncate">{s.reason}</span>
+                </div>
+                <div className="flex items-center gap-1 opacity-0 group-hover:opacity-100 transition-opacity">
+                  <Button size="icon" variant="ghost" className="h-7 w-7" title="Compare with current" onClick={() => showDiff(s.id)}>
+                    <GitCompare className="h-3.5 w-3.5" />
+                  </Button>
+                  <ConfirmDialog
+                    trigger={
+                      <Button size="icon" variant="ghost" className="h-7 w-7" title="Restore">
+                        <RotateCcw className="h-3.5 w-3.5" />
+                      </Button>
+                    }
+                    title="Restore this snapshot?"
+                    description={`php.ini of PHP ${version} is replaced with the snapshot. The current file is snapshotted first.`}
+                    confirmLabel="Restore"
+                    onConfirm={() => onRestore(s.id)}
+                  />
+                  <Button
+                    size="icon"
+                    variant="ghost"
+                    className="h-7 w-7 text-destructive"
+                    title="Delete"
+                    onClick={() => onDeleteSnapshot(s.id)}
+                  >
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </Button>
+                </div>
+              </div>
+              {diff?.id === s.id && (
+                <div className="mx-2 mb-2 rounded bg-muted/50 p-2 font-mono text-xs space-y-0.5">
+                  {diff.changes.length === 0 ? (
+                    <p className="text-muted-foreground">Identical to the current php.ini</p>
+                  ) : (
+                    diff.changes.map((c, i) => (
+                      <div key={`${c.section}-${c.key}-${i}`} className="flex gap-2">
+                        <span className="text-muted-foreground shrink-0">[{c.section}]</span>
+                        <span>{c.key}</span>
+                        <span className="text-red-500">{c.before ?? "(unset)"}</span>
+                        <span className="text-muted-foreground">→</span>
+                        <span className="text-green-500">{c.after ?? "(unset)"}</span>
+                      </div>
+                    ))
+                  )}
+                </div>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </Card>
+  )
+}
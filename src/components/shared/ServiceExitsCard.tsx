@@ -0,0 +1,62 @@
+import { useState } from "react"
+import { AlertTriangle } from "lucide-react"
+import { Card } from "@/components/ui/card"
+import type { ServiceExit } from "@/types/service"
+
+interface ServiceExitsCardProps {
+  exits: ServiceExit[]
+  names: Record<string, string>
+}
+
+function describe(exit: ServiceExit) {
+  if (exit.signal !== null) return `signal ${exit.signal}`
+  if (exit.code !== null) return `exit code ${exit.code}`
+  return "unknown status"
+}
+
+export function ServiceExitsCard({ exits, names }: ServiceExitsCardProps) {
+  const [expanded, setExpanded] = useState<string | null>(null)
+
+  if (exits.length === 0) return null
+
+  return (
+    <Card className="p-4 mt-6">
+      <h3 className="text-sm font-medium flex items-center gap-1.5 mb-2">
+        <AlertTriangle className="h-4 w-4 text-amber-500" />
+        Recent Crashes
+      </h3>
+      <div className="space-y-0.5">
+        {exits.map((exit) => (
+          <div key={exit.serviceId}>
+            <button
+              className="w-full flex items-center gap-2 py-1 px-2 rounded hover:bg-muted/50 text-left"
+              onClick={() => setExpanded(expanded === exit.serviceId ? null : exit.serviceId)}
+            >
+              <span className="text-xs font-medium">{names[exit.serviceId] ?? exit.serviceId}</span>
+              <span className="text-xs text-muted-foreground">{describe(exit)}</span>
+              {exit.restarts > 0 && (
+                <span className="text-xs text-muted-foreground">· {exit.restarts} restarts</span>
+              )}
+              <span className="ml-auto text-xs text-muted-foreground shrink-0">
+                {new Date(exit.exitedAt).toLocaleString()}
+              </span>
+            </button>
+            {expanded === exit.serviceId && (
+              <div className="mx-2 mb-2 rounded bg-muted/50 p-2 font-mono text-xs space-y-0.5 max-h-60 overflow-y-auto">
+                {exit.logTail.length === 0 ? (
+                  <p className="text-muted-foreground">The log was empty.</p>
+                ) : (
+                  exit.logTail.map((line, i) => (
+                    <p key={i} className="break-all">
+                      {line}
+                    </p>
+                  ))
+                )}
+              </div>
+            )}
+          </div>
+        ))}
+      </div>
+    </Card>
+  )
+}
@@ -0,0 +1,132 @@
+import { useEffect, useRef, useState } from "react"
+import { listen } from "@tauri-apps/api/event"
+import { Package } from "lucide-react"
+import { toast } from "sonner"
+import { Button } from "@/components/ui/button"
+import { Dialog, DialogContent, DialogHeader, DialogTitle, DialogTrigger } from "@/components/ui/dialog"
+import { Input } from "@/components/ui/input"
+import { Label } from "@/components/ui/label"
+import { Switch } from "@/components/ui/switch"
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select"
+import type { Site } from "@/types/nginx"
+import type { ComposerAction, ComposerInfo, ComposerOutput } from "@/types/composer"
+import * as tauri from "@/lib/tauri"
+
+interface SiteComposerDialogProps {
+  site: Site
+}
+
+export function SiteComposerDialog({ site }: SiteComposerDialogProps) {
+  const [open, setOpen] = useState(false)
+  const [info, setInfo] = useState<ComposerInfo | null>(null)
+  const [action, setAction] = useState<ComposerAction>("install")
+  const [packages, setPackages] = useState("")
+  const [dev, setDev] = useState(false)
+  const [running, setRunning] = useState(false)
+  const [output, setOutput] = useState<ComposerOutput[]>([])
+  const outputEnd = useRef<HTMLDivElement>(null)
+
+  useEffect(() => {
+    if (!open) return
+    tauri.composerGetInfo().then(setInfo).catch(() => setInfo(null))
+    const unlisten = listen<ComposerOutput>("composer-output", (event) => {
+      if (event.payload.siteId === site.id) {
+        setOutput((prev) => [...prev.slice(-999), event.payload])
+      }
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [open, site.id])
+
+  useEffect(() => {
+    outputEnd.current?.scrollIntoView({ block: "end" })
+  }, [output])
+
+  const handleRun = async () => {
+    const names = packages.split(/\s+/).filter(Boolean)
+    setOutput([])
+    setRunning(true)
+    try {
+      await tauri.composerRun(site.id, action, names, action === "require" ? dev : undefined)
+      toast.success(`composer ${action} finished`)
+      tauri.composerGetInfo().then(setInfo).catch(() => {})
+    } catch (err) {
+      toast.error(`composer ${action} failed`, { description: String(err).split("\n")[0] })
+    } finally {
+      setRunning(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={setOpen}>
+      <DialogTrigger asChild>
+        <Button size="sm" variant="ghost" title="Composer">
+          <Package className="h-3.5 w-3.5" />
+        </Button>
+      </DialogTrigger>
+      <DialogContent className="max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>Composer — {site.name}</DialogTitle>
+        </DialogHeader>
+        <div className="space-y-4 pt-2">
+          <p className="text-[11px] text-muted-foreground">
+            Runs in {site.documentRoot} with PHP {site.phpVersion}.{" "}
+            {info?.installed
+              ? `Composer ${info.version ?? ""}`.trim() + "."
+              : "composer.phar is downloaded on first use."}
+          </p>
+          <div className="flex items-end gap-2">
+            <div className="space-y-1.5 w-32">
+              <Label>Command</Label>
+              <Select value={action} onValueChange={(v) => setAction(v as ComposerAction)}>
+                <SelectTrigger className="h-8">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="install">install</SelectItem>
+                  <SelectItem value="update">update</SelectItem>
+                  <SelectItem value="require">require</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+            {action !== "install" && (
+              <div className="space-y-1.5 flex-1">
+                <Label>Packages</Label>
+                <Input
+                  className="h-8 font-mono text-xs"
+                  placeholder={action === "require" ? "vendor/package:^1.0" : "All packages"}
+                  value={packages}
+                  onChange={(e) => setPackages(e.target.value)}
+                />
+              </div>
+            )}
+            {action === "require" && (
+              <div className="flex items-center gap-2 pb-1.5">
+                <Switch checked={dev} onCheckedChange={setDev} />
+                <Label className="text-xs">--dev</Label>
+              </div>
+            )}
+            <Button
+              size="sm"
+              onClick={handleRun}
+              disabled={running || (action === "require" && !packages.trim())}
+            >
+              {running ? "Running..." : "Run"}
+            </Button>
+          </div>
+          {output.length > 0 && (
+            <div className="rounded bg-muted/50 p-2 font-mono text-[11px] max-h-72 overflow-y-auto">
+              {output.map((o, i) => (
+                <p key={i} className={o.stream === "stderr" ? "text-muted-foreground" : undefined}>
+                  {o.line || " "}
+                </p>
+              ))}
+              <div ref={outputEnd} />
+            </div>
+          )}
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,102 @@
+import { useState } from "react"
+import { Button } from "@/components/ui/button"
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from "@/components/ui/dialog"
+import { Label } from "@/components/ui/label"
+import { Textarea } from "@/components/ui/textarea"
+import { FileCog } from "lucide-react"
+import type { Site, SiteIniOverrides } from "@/types/nginx"
+
+interface SiteIniDialogProps {
+  site: Site
+  onSubmit: (iniOverrides: SiteIniOverrides) => Promise<void>
+}
+
+// "key=value" per line <-> record
+const toLines = (values: Record<string, string>) =>
+  Object.entries(values).map(([k, v]) => `${k}=${v}`).join("\n")
+
+const fromLines = (text: string) =>
+  Object.fromEntries(
+    text
+      .split("\n")
+      .map((line) => line.trim())
+      .filter((line) => line.includes("="))
+      .map((line) => {
+        const idx = line.indexOf("=")
+        return [line.slice(0, idx).trim(), line.slice(idx + 1).trim()]
+      }),
+  )
+
+export function SiteIniDialog({ site, onSubmit }: SiteIniDialogProps) {
+  const [open, setOpen] = useState(false)
+  const [values, setValues] = useState(toLines(site.iniOverrides?.values ?? {}))
+  const [adminValues, setAdminValues] = useState(toLines(site.iniOverrides?.adminValues ?? {}))
+  const [submitting, setSubmitting] = useState(false)
+  const sharedPool = !site.pool?.enabled
+
+  const handleSubmit = async () => {
+    setSubmitting(true)
+    try {
+      await onSubmit({ values: fromLines(values), adminValues: fromLines(adminValues) })
+      setOpen(false)
+    } finally {
+      setSubmitting(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={setOpen}>
+      <DialogTrigger asChild>
+        <Button size="sm" variant="ghost" title="PHP ini overrides">
+          <FileCog className="h-3.5 w-3.5" />
+        </Button>
+      </DialogTrigger>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>PHP Settings — {site.name}</DialogTitle>
+        </DialogHeader>
+        <div className="space-y-4 pt-2">
+          <p className="text-[11px] text-muted-foreground">
+            Overrides the php.ini of PHP {site.phpVersion} for this site only. One directive=value per line.
+          </p>
+          <div className="space-y-1.5">
+            <Label>PHP_VALUE</Label>
+            <Textarea
+              rows={4}
+              className="font-mono text-xs"
+              placeholder={"memory_limit=1G\nmax_execution_time=0"}
+              value={values}
+              onChange={(e) => setValues(e.target.value)}
+            />
+          </div>
+          <div className="space-y-1.5">
+            <Label>PHP_ADMIN_VALUE</Label>
+            <p className="text-[11px] text-muted-foreground">Cannot be changed by ini_set()</p>
+            <Textarea
+              rows={3}
+              className="font-mono text-xs"
+              placeholder="disable_functions=exec"
+              value={adminValues}
+              onChange={(e) => setAdminValues(e.target.value)}
+            />
+          </div>
+          {sharedPool && (
+            <p className="text-[11px] text-amber-500">
+              PHP-FPM keeps these values in the worker after the request. Enable a dedicated pool so other sites on
+              PHP {site.phpVersion} are not affected.
+            </p>
+          )}
+          <Button className="w-full" onClick={handleSubmit} disabled={submitting}>
+            {submitting ? "Saving..." : "Save"}
+          </Button>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,153 @@
+import { useState } from "react"
+import { Button } from "@/components/ui/button"
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogTrigger,
+} from "@/components/ui/dialog"
+import { Input } from "@/components/ui/input"
+import { Label } from "@/components/ui/label"
+import { Switch } from "@/components/ui/switch"
+import { Textarea } from "@/components/ui/textarea"
+import { Cpu } from "lucide-react"
+import type { Site, SitePool } from "@/types/nginx"
+
+interface SitePoolDialogProps {
+  site: Site
+  onSubmit: (pool: SitePool) => Promise<void>
+}
+
+const DEFAULT_POOL: SitePool = {
+  enabled: false,
+  maxChildren: 5,
+  maxRequests: 500,
+  slowlogTimeout: 0,
+  env: {},
+  adminValues: {},
+}
+
+// "KEY=value" per line <-> record
+const toLines = (values: Record<string, string>) =>
+  Object.entries(values).map(([k, v]) => `${k}=${v}`).join("\n")
+
+const fromLines = (text: string) =>
+  Object.fromEntries(
+    text
+      .split("\n")
+      .map((line) => line.trim())
+      .filter((line) => line.includes("="))
+      .map((line) => {
+        const idx = line.indexOf("=")
+        return [line.slice(0, idx).trim(), line.slice(idx + 1).trim()]
+      }),
+  )
+
+export function SitePoolDialog({ site, onSubmit }: SitePoolDialogProps) {
+  const initial = site.pool ?? DEFAULT_POOL
+  const [open, setOpen] = useState(false)
+  const [enabled, setEnabled] = useState(initial.enabled)
+  const [maxChildren, setMaxChildren] = useState(initial.maxChildren)
+  const [maxRequests, setMaxRequests] = useState(initial.maxRequests)
+  const [slowlogTimeout, setSlowlogTimeout] = useState(initial.slowlogTimeout)
+  const [env, setEnv] = useState(toLines(initial.env))
+  const [adminValues, setAdminValues] = useState(toLines(initial.adminValues))
+  const [submitting, setSubmitting] = useState(false)
+
+  const handleSubmit = async () => {
+    setSubmitting(true)
+    try {
+      await onSubmit({
+        enabled,
+        maxChildren,
+        maxRequests,
+        slowlogTimeout,
+        env: fromLines(env),
+        adminValues: fromLines(adminValues),
+      })
+      setOpen(false)
+    } finally {
+      setSubmitting(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={setOpen}>
+      <DialogTrigger asChild>
+        <Button size="sm" variant="ghost" title="PHP-FPM pool">
+          <Cpu className="h-3.5 w-3.5" />
+        </Button>
+      </DialogTrigger>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>PHP-FPM Pool — {site.name}</DialogTitle>
+        </DialogHeader>
+        <div className="space-y-4 pt-2">
+          <div className="flex items-center justify-between">
+            <div>
+              <Label htmlFor="pool-enabled">Dedicated pool</Label>
+              <p className="text-[11px] text-muted-foreground">
+                Own workers for this site instead of the shared PHP {site.phpVersion} pool
+              </p>
+            </div>
+            <Switch id="pool-enabled" checked={enabled} onCheckedChange={setEnabled} />
+          </div>
+          <div className="grid grid-cols-3 gap-3">
+            <div className="space-y-1.5">
+              <Label>Max children</Label>
+              <Input
+                type="number"
+                min={1}
+                max={256}
+                value={maxChildren}
+                onChange={(e) => setMaxChildren(parseInt(e.target.value) || 1)}
+              />
+            </div>
+            <div className="space-y-1.5">
+              <Label>Max requests</Label>
+              <Input
+                type="number"
+                min={0}
+                value={maxRequests}
+                onChange={(e) => setMaxRequests(parseInt(e.target.value) || 0)}
+              />
+            </div>
+            <div className="space-y-1.5">
+              <Label>Slowlog (s)</Label>
+              <Input
+                type="number"
+                min={0}
+                value={slowlogTimeout}
+                onChange={(e) => setSlowlogTimeout(parseInt(e.target.value) || 0)}
+              />
+            </div>
+          </div>
+          <div className="space-y-1.5">
+            <Label>Environment</Label>
+            <Textarea
+              rows={3}
+              className="font-mono text-xs"
+              placeholder="APP_ENV=local"
+              value={env}
+              onChange={(e) => setEnv(e.target.value)}
+            />
+          </div>
+          <div className="space-y-1.5">
+            <Label>php_admin_value overrides</Label>
+            <Textarea
+              rows={3}
+              className="font-mono text-xs"
+              placeholder="memory_limit=1G"
+              value={adminValues}
+              onChange={(e) => setAdminValues(e.target.value)}
+            />
+          </div>
+          <Button className="w-full" onClick={handleSubmit} disabled={submitting}>
+            {submitting ? "Saving..." : "Save Pool"}
+          </Button>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
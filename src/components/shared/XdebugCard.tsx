@@ -0,0 +1,192 @@
+import { useEffect, useState } from "react"
+import { Bug, Copy, Trash2 } from "lucide-react"
+import { toast } from "sonner"
+import { Card } from "@/components/ui/card"
+import { Button } from "@/components/ui/button"
+import { Badge } from "@/components/ui/badge"
+import { Input } from "@/components/ui/input"
+import { Label } from "@/components/ui/label"
+import { Switch } from "@/components/ui/switch"
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select"
+import { ConfirmDialog } from "@/components/shared/ConfirmDialog"
+import type { XdebugConfig, XdebugProfile, XdebugStatus } from "@/types/php"
+import * as tauri from "@/lib/tauri"
+
+interface XdebugCardProps {
+  version: string
+  status: XdebugStatus | null
+  profiles: XdebugProfile[]
+  onSave: (enabled: boolean, config: XdebugConfig) => Promise<void>
+  onDeleteProfile: (name: string) => Promise<void>
+  onClearProfiles: () => Promise<void>
+}
+
+const MODES = ["develop", "debug", "profile", "coverage", "trace"]
+
+const formatSize = (bytes: number) =>
+  bytes >= 1024 * 1024 ? `${(bytes / 1024 / 1024).toFixed(1)} MB` : `${Math.max(1, Math.round(bytes / 1024))} KB`
+
+export function XdebugCard({ version, status, profiles, onSave, onDeleteProfile, onClearProfiles }: XdebugCardProps) {
+  const [enabled, setEnabled] = useState(false)
+  const [form, setForm] = useState<XdebugConfig | null>(null)
+  const [saving, setSaving] = useState(false)
+
+  useEffect(() => {
+    setEnabled(status?.enabled ?? false)
+    setForm(status?.config ?? null)
+  }, [status])
+
+  if (!status || !form) return null
+
+  const toggleMode = (mode: string) => {
+    const selected = form.mode.filter((m) => m !== "off")
+    const next = selected.includes(mode) ? selected.filter((m) => m !== mode) : [...selected, mode]
+    setForm({ ...form, mode: next.length ? next : ["off"] })
+  }
+
+  const handleSave = async () => {
+    setSaving(true)
+    try {
+      await onSave(enabled, form)
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  const copyPath = async (name: string) => {
+    try {
+      const path = await tauri.phpGetXdebugProfilePath(name)
+      await navigator.clipboard.writeText(path)
+      toast.success("Path copied", { description: "Open it with KCachegrind, QCachegrind or PhpStorm" })
+    } catch (err) {
+      toast.error("Failed to copy path", { description: String(err) })
+    }
+  }
+
+  return (
+    <Card className="p-4 mb-4">
+      <div className="flex items-center justify-between mb-1">
+        <h3 className="text-sm font-medium flex items-center gap-1.5">
+          <Bug className="h-4 w-4" />
+          Xdebug
+        </h3>
+        <div className="flex items-center gap-3">
+          <Switch checked={enabled} onCheckedChange={setEnabled} disabled={!status.installed} />
+          <Button size="sm" onClick={handleSave} disabled={saving || !status.installed}>
+            {saving ? "Saving..." : "Save & Restart"}
+          </Button>
+        </div>
+      </div>
+      <p className="text-xs text-muted-foreground mb-4">
+        {status.installed
+          ? "With \"trigger\", only requests carrying XDEBUG_TRIGGER are debugged; turn on debugging per site on the Sites page."
+          : `Install xdebug for PHP ${version} above first.`}
+      </p>
+
+      <div className="grid grid-cols-3 gap-x-6 gap-y-4 mb-4">
+        <div className="space-y-1.5 col-span-3">
+          <Label className="text-sm">Mode</Label>
+          <div className="flex flex-wrap gap-1.5">
+            {MODES.map((mode) => (
+              <Button
+                key={mode}
+                size="sm"
+                variant={form.mode.includes(mode) ? "default" : "outline"}
+                className="h-7 text-xs"
+                onClick={() => toggleMode(mode)}
+              >
+                {mode}
+              </Button>
+            ))}
+          </div>
+        </div>
+        <div className="space-y-1.5">
+          <Label className="text-sm">Client Host</Label>
+          <Input
+            className="h-8"
+            value={form.clientHost}
+            onChange={(e) => setForm({ ...form, clientHost: e.target.value })}
+          />
+        </div>
+        <div className="space-y-1.5">
+          <Label className="text-sm">Client Port</Label>
+          <Input
+            className="h-8"
+            type="number"
+            min={1}
+            max={65535}
+            value={form.clientPort}
+            onChange={(e) => setForm({ ...form, clientPort: Number(e.target.value) })}
+          />
+        </div>
+        <div className="space-y-1.5">
+          <Label className="text-sm">Start With Request</Label>
+          <Select value={form.startWithRequest} onValueChange={(v) => setForm({ ...form, startWithRequest: v })}>
+            <SelectTrigger className="w-full h-8">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="trigger">trigger</SelectItem>
+              <SelectItem value="yes">yes (every request)</SelectItem>
+              <SelectItem value="default">default</SelectItem>
+              <SelectItem value="no">no</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
+      </div>
+
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-sm font-medium">Profiles & Traces</h3>
+        {profiles.length > 0 && (
+          <ConfirmDialog
+            trigger={
+              <Button size="sm" variant="ghost" className="text-destructive h-7">
+                <Trash2 className="h-3.5 w-3.5 mr-1.5" />
+                Clear
+              </Button>
+            }
+            title="Delete all profiles?"
+            description={`Every profile and trace in ${status.outputDir} is deleted.`}
+            confirmLabel="Delete"
+            onConfirm={onClearProfiles}
+          />
+        )}
+      </div>
+      {profiles.length === 0 ? (
+        <p className="text-xs text-muted-foreground font-mono truncate" title={status.outputDir}>
+          Nothing in {status.outputDir} yet.
+        </p>
+      ) : (
+        <div className="space-y-0.5 max-h-60 overflow-y-auto">
+          {profiles.map((p) => (
+            <div key={p.name} className="flex items-center justify-between py-1 px-2 rounded hover:bg-muted/50 group">
+              <div className="flex items-center gap-2 min-w-0">
+                <Badge variant="outline" className="text-[10px] shrink-0">{p.kind}</Badge>
+                <span className="text-xs font-mono truncate">{p.name}</span>
+              </div>
+              <div className="flex items-center gap-2 shrink-0">
+                <span className="text-xs text-muted-foreground">
+                  {formatSize(p.size)} · {new Date(p.modified).toLocaleString()}
+                </span>
+                <div className="flex items-center gap-1 opacity-0 group-hover:opacity-100 transition-opacity">
+                  <Button size="icon" variant="ghost" className="h-7 w-7" title="Copy path" onClick={() => copyPath(p.name)}>
+                    <Copy className="h-3.5 w-3.5" />
+                  </Button>
+                  <Button
+                    size="icon"
+                    variant="ghost"
+                    className="h-7 w-7 text-destructive"
+                    title="Delete"
+                    onClick={() => onDeleteProfile(p.name)}
+                  >
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </Button>
+                </div>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </Card>
+  )
+}
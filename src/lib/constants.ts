@@ -15,5 +15,6 @@ export const NAV_ITEMS = [
   { path: "/database", label: "Database", icon: "Database" },
   { path: "/ssl", label: "SSL", icon: "Shield" },
   { path: "/logs", label: "Logs", icon: "ScrollText" },
+  { path: "/mail", label: "Mail", icon: "Mail" },
   { path: "/settings", label: "Settings", icon: "Settings" },
 ] as const
@@ -1,12 +1,16 @@
 import { invoke } from "@tauri-apps/api/core"
 import { check } from "@tauri-apps/plugin-updater"
 import { relaunch } from "@tauri-apps/plugin-process"
-import type { ServiceInfo } from "@/types/service"
-import type { SystemInfo, AppConfig } from "@/types/config"
-import type { PhpVersion, PhpIniDirective, PhpExtension } from "@/types/php"
-import type { NginxInfo, Site } from "@/types/nginx"
+import type { ServiceExit, ServiceInfo } from "@/types/service"
+import type { SystemInfo, AppConfig, ShimsInfo } from "@/types/config"
+import type { PhpVersion, PhpIniDirective, PhpExtension, InstallableExtension, XdebugConfig, XdebugStatus, XdebugProfile, FpmSettings, FpmStatus, SlowlogEntry, IniPreset, IniSnapshot, IniChange } from "@/types/php"
+import type { NginxInfo, Site, SitePool, SiteIniOverrides } from "@/types/nginx"
 import type { MariaDbInfo, DatabaseEntry, PhpMyAdminInfo } from "@/types/database"
 import type { CertificateInfo, DnsEntry, LogFile } from "@/types/ssl"
+import type { CachedArtifact, DownloadJob } from "@/types/download"
+import type { AvailableUpgrade } from "@/types/upgrade"
+import type { ComposerAction, ComposerInfo } from "@/types/composer"
+import type { MailCatcherInfo, MailMessage, MailSummary } from "@/types/mail"
 
 // Service commands
 export async function getAllServices(): Promise<ServiceInfo[]> {
@@ -29,6 +33,10 @@ export async function restartService(serviceId: string): Promise<ServiceInfo> {
   return invoke<ServiceInfo>("restart_service", { serviceId })
 }
 
+export async function serviceLastExits(): Promise<ServiceExit[]> {
+  return invoke<ServiceExit[]>("service_last_exits")
+}
+
 // System commands
 export async function getSystemInfo(): Promise<SystemInfo> {
   return invoke<SystemInfo>("get_system_info")
@@ -43,6 +51,10 @@ export async function phpListVersions(): Promise<PhpVersion[]> {
   return invoke<PhpVersion[]>("php_list_versions")
 }
 
+export async function phpRefreshCatalogue(): Promise<PhpVersion[]> {
+  return invoke<PhpVersion[]>("php_refresh_catalogue")
+}
+
 export async function phpInstallVersion(version: string): Promise<PhpVersion> {
   return invoke<PhpVersion>("php_install_version", { version })
 }
@@ -67,8 +79,52 @@ export async function phpGetIni(version: string): Promise<PhpIniDirective[]> {
   return invoke<PhpIniDirective[]>("php_get_ini", { version })
 }
 
-export async function phpSetIniDirective(version: string, key: string, value: string): Promise<void> {
-  return invoke<void>("php_set_ini_directive", { version, key, value })
+export async function phpSetIniDirective(version: string, key: string, value: string, section?: string): Promise<void> {
+  return invoke<void>("php_set_ini_directive", { version, key, value, section })
+}
+
+export async function phpRemoveIniDirective(version: string, key: string, section?: string): Promise<void> {
+  return invoke<void>("php_remove_ini_directive", { version, key, section })
+}
+
+export async function phpUncommentIniDirective(version: string, key: string, section?: string): Promise<string> {
+  return invoke<string>("php_uncomment_ini_directive", { version, key, section })
+}
+
+export async function phpGetEffectiveIniValue(version: string, key: string): Promise<string | null> {
+  return invoke<string | null>("php_get_effective_ini_value", { version, key })
+}
+
+export async function phpListIniPresets(): Promise<IniPreset[]> {
+  return invoke<IniPreset[]>("php_list_ini_presets")
+}
+
+export async function phpSaveIniPreset(preset: IniPreset): Promise<IniPreset> {
+  return invoke<IniPreset>("php_save_ini_preset", { preset })
+}
+
+export async function phpDeleteIniPreset(name: string): Promise<void> {
+  return invoke<void>("php_delete_ini_preset", { name })
+}
+
+export async function phpApplyIniPreset(version: string, name: string): Promise<void> {
+  return invoke<void>("php_apply_ini_preset", { version, name })
+}
+
+export async function phpListIniSnapshots(version: string): Promise<IniSnapshot[]> {
+  return invoke<IniSnapshot[]>("php_list_ini_snapshots", { version })
+}
+
+export async function phpDiffIniSnapshots(version: string, from: string, to?: string): Promise<IniChange[]> {
+  return invoke<IniChange[]>("php_diff_ini_snapshots", { version, from, to })
+}
+
+export async function phpRestoreIniSnapshot(version: string, id: string): Promise<void> {
+  return invoke<void>("php_restore_ini_snapshot", { version, id })
+}
+
+export async function phpDeleteIniSnapshot(version: string, id: string): Promise<void> {
+  return invoke<void>("php_delete_ini_snapshot", { version, id })
 }
 
 export async function phpListExtensions(version: string): Promise<PhpExtension[]> {
@@ -79,6 +135,54 @@ export async function phpToggleExtension(version: string, extension: string, ena
   return invoke<void>("php_toggle_extension", { version, extension, enable })
 }
 
+export async function phpListInstallableExtensions(version: string): Promise<InstallableExtension[]> {
+  return invoke<InstallableExtension[]>("php_list_installable_extensions", { version })
+}
+
+export async function phpInstallExtension(version: string, name: string): Promise<PhpExtension[]> {
+  return invoke<PhpExtension[]>("php_install_extension", { version, name })
+}
+
+export async function phpFpmStatus(version: string, siteId?: string): Promise<FpmStatus> {
+  return invoke<FpmStatus>("php_fpm_status", { version, siteId })
+}
+
+export async function phpFpmSlowlog(version: string, siteId?: string, limit?: number): Promise<SlowlogEntry[]> {
+  return invoke<SlowlogEntry[]>("php_fpm_slowlog", { version, siteId, limit })
+}
+
+export async function phpGetXdebug(version: string): Promise<XdebugStatus> {
+  return invoke<XdebugStatus>("php_get_xdebug", { version })
+}
+
+export async function phpSetXdebug(version: string, enabled: boolean, config: XdebugConfig): Promise<XdebugStatus> {
+  return invoke<XdebugStatus>("php_set_xdebug", { version, enabled, config })
+}
+
+export async function phpListXdebugProfiles(): Promise<XdebugProfile[]> {
+  return invoke<XdebugProfile[]>("php_list_xdebug_profiles")
+}
+
+export async function phpGetXdebugProfilePath(name: string): Promise<string> {
+  return invoke<string>("php_get_xdebug_profile_path", { name })
+}
+
+export async function phpDeleteXdebugProfile(name: string): Promise<void> {
+  return invoke<void>("php_delete_xdebug_profile", { name })
+}
+
+export async function phpClearXdebugProfiles(): Promise<number> {
+  return invoke<number>("php_clear_xdebug_profiles")
+}
+
+export async function phpGetFpmSettings(version: string): Promise<FpmSettings> {
+  return invoke<FpmSettings>("php_get_fpm_settings", { version })
+}
+
+export async function phpSetFpmSettings(version: string, settings: FpmSettings): Promise<FpmSettings> {
+  return invoke<FpmSettings>("php_set_fpm_settings", { version, settings })
+}
+
 // Nginx commands
 export async function nginxInstall(): Promise<NginxInfo> {
   return invoke<NginxInfo>("nginx_install")
@@ -124,8 +228,10 @@ export async function siteCreate(
   phpVersion: string,
   ssl: boolean,
   template?: string,
+  pool?: SitePool,
+  iniOverrides?: SiteIniOverrides,
 ): Promise<Site> {
-  return invoke<Site>("site_create", { name, domain, documentRoot, phpVersion, ssl, template })
+  return invoke<Site>("site_create", { name, domain, documentRoot, phpVersion, ssl, template, pool, iniOverrides })
 }
 
 export async function siteSetupTemplate(siteId: string, template: string): Promise<void> {
@@ -140,8 +246,11 @@ export async function siteUpdate(
   phpVersion?: string,
   ssl?: boolean,
   active?: boolean,
+  pool?: SitePool,
+  iniOverrides?: SiteIniOverrides,
+  xdebug?: boolean,
 ): Promise<Site> {
-  return invoke<Site>("site_update", { id, name, domain, documentRoot, phpVersion, ssl, active })
+  return invoke<Site>("site_update", { id, name, domain, documentRoot, phpVersion, ssl, active, pool, iniOverrides, xdebug })
 }
 
 export async function siteDelete(id: string): Promise<void> {
@@ -194,6 +303,37 @@ export async function phpmyadminGetInfo(): Promise<PhpMyAdminInfo> {
   return invoke<PhpMyAdminInfo>("phpmyadmin_get_info")
 }
 
+// Composer commands
+export async function composerGetInfo(): Promise<ComposerInfo> {
+  return invoke<ComposerInfo>("composer_get_info")
+}
+
+export async function composerInstall(): Promise<ComposerInfo> {
+  return invoke<ComposerInfo>("composer_install")
+}
+
+export async function composerRun(
+  siteId: string,
+  action: ComposerAction,
+  packages?: string[],
+  dev?: boolean,
+): Promise<void> {
+  return invoke<void>("composer_run", { siteId, action, packages, dev })
+}
+
+// CLI shim commands
+export async function cliGetShimsInfo(): Promise<ShimsInfo> {
+  return invoke<ShimsInfo>("cli_get_shims_info")
+}
+
+export async function cliInstallShims(): Promise<ShimsInfo> {
+  return invoke<ShimsInfo>("cli_install_shims")
+}
+
+export async function cliRemoveShims(): Promise<ShimsInfo> {
+  return invoke<ShimsInfo>("cli_remove_shims")
+}
+
 // SSL commands
 export async function sslInstallMkcert(): Promise<void> {
   return invoke<void>("ssl_install_mkcert")
@@ -268,6 +408,40 @@ export async function logClearFile(path: string): Promise<void> {
   return invoke<void>("log_clear_file", { path })
 }
 
+// Download commands
+export async function downloadsList(): Promise<DownloadJob[]> {
+  return invoke<DownloadJob[]>("downloads_list")
+}
+
+export async function downloadsClearHistory(): Promise<void> {
+  return invoke<void>("downloads_clear_history")
+}
+
+export async function downloadCancel(id: string): Promise<boolean> {
+  return invoke<boolean>("download_cancel", { id })
+}
+
+export async function downloadCacheList(): Promise<CachedArtifact[]> {
+  return invoke<CachedArtifact[]>("download_cache_list")
+}
+
+export async function downloadCacheEvict(url: string): Promise<void> {
+  return invoke<void>("download_cache_evict", { url })
+}
+
+export async function downloadCacheClear(): Promise<void> {
+  return invoke<void>("download_cache_clear")
+}
+
+// Upgrade commands
+export async function upgradesCheck(): Promise<AvailableUpgrade[]> {
+  return invoke<AvailableUpgrade[]>("upgrades_check")
+}
+
+export async function upgradeComponent(id: string): Promise<AvailableUpgrade> {
+  return invoke<AvailableUpgrade>("upgrade_component", { id })
+}
+
 // Update commands
 export interface UpdateInfo {
   version: string
@@ -306,6 +480,35 @@ export async function downloadAndInstallUpdate(
   await relaunch()
 }
 
+// Mail commands
+export async function mailGetInfo(): Promise<MailCatcherInfo> {
+  return invoke<MailCatcherInfo>("mail_get_info")
+}
+
+export async function mailList(): Promise<MailSummary[]> {
+  return invoke<MailSummary[]>("mail_list")
+}
+
+export async function mailGet(id: string): Promise<MailMessage> {
+  return invoke<MailMessage>("mail_get", { id })
+}
+
+export async function mailGetRaw(id: string): Promise<string> {
+  return invoke<string>("mail_get_raw", { id })
+}
+
+export async function mailGetAttachmentPath(id: string, index: number): Promise<string> {
+  return invoke<string>("mail_get_attachment_path", { id, index })
+}
+
+export async function mailDelete(id: string): Promise<void> {
+  return invoke<void>("mail_delete", { id })
+}
+
+export async function mailClear(): Promise<number> {
+  return invoke<number>("mail_clear")
+}
+
 // AI commands
 import type { AiModel, AiMessage } from "@/types/ai"
 
@@ -0,0 +1,211 @@
+import { useEffect, useState } from "react"
+import { Mail, Paperclip, Trash2, Copy } from "lucide-react"
+import { toast } from "sonner"
+import { Card } from "@/components/ui/card"
+import { Badge } from "@/components/ui/badge"
+import { Button } from "@/components/ui/button"
+import { ScrollArea } from "@/components/ui/scroll-area"
+import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs"
+import { PageHeader } from "@/components/shared/PageHeader"
+import { EmptyState } from "@/components/shared/EmptyState"
+import { ConfirmDialog } from "@/components/shared/ConfirmDialog"
+import { useMailStore } from "@/stores/mailStore"
+import { formatBytes } from "@/lib/utils"
+import * as tauri from "@/lib/tauri"
+
+export function MailPage() {
+  const {
+    info,
+    messages,
+    selected,
+    raw,
+    fetchInfo,
+    fetchMessages,
+    selectMessage,
+    fetchRaw,
+    deleteMessage,
+    clearMessages,
+  } = useMailStore()
+  const [view, setView] = useState("html")
+
+  useEffect(() => {
+    fetchInfo()
+    fetchMessages()
+  }, [fetchInfo, fetchMessages])
+
+  useEffect(() => {
+    if (selected) {
+      setView(selected.html ? "html" : "text")
+    }
+  }, [selected?.id])
+
+  const copyAttachmentPath = async (index: number) => {
+    if (!selected) return
+    try {
+      const path = await tauri.mailGetAttachmentPath(selected.id, index)
+      await navigator.clipboard.writeText(path)
+      toast.success("Path copied")
+    } catch (err) {
+      toast.error("Failed to copy path", { description: String(err) })
+    }
+  }
+
+  const description = info
+    ? info.running
+      ? `Catching mail on SMTP 127.0.0.1:${info.port}`
+      : "The mail catcher is stopped — start it on the Services page"
+    : "Mail sent by your sites"
+
+  return (
+    <div>
+      <PageHeader title="Mail" description={description}>
+        {messages.length > 0 && (
+          <ConfirmDialog
+            trigger={
+              <Button size="sm" variant="outline" className="text-destructive">
+                <Trash2 className="mr-1.5 h-3.5 w-3.5" /> Delete All
+              </Button>
+            }
+            title="Delete all messages?"
+            description="Every caught message and its attachments will be removed."
+            confirmLabel="Delete"
+            onConfirm={clearMessages}
+          />
+        )}
+      </PageHeader>
+
+      {messages.length === 0 ? (
+        <EmptyState
+          icon={Mail}
+          title="No mail yet"
+          description="Messages sent with mail(), WordPress or Laravel show up here instead of being delivered."
+        />
+      ) : (
+        <div className="grid grid-cols-[300px_1fr] gap-4 h-[calc(100vh-200px)]">
+          {/* Message list */}
+          <Card className="p-2 overflow-y-auto">
+            <div className="space-y-0.5">
+              {messages.map((message) => (
+                <button
+                  key={message.id}
+                  className={`w-full text-left px-2 py-1.5 rounded text-sm transition-colors ${
+                    selected?.id === message.id ? "bg-accent text-accent-foreground" : "hover:bg-muted/50"
+                  }`}
+                  onClick={() => selectMessage(message.id)}
+                >
+                  <div className="flex items-center justify-between gap-2">
+                    <p className="truncate font-medium text-xs">{message.subject}</p>
+                    {message.attachments > 0 && <Paperclip className="h-3 w-3 shrink-0 text-muted-foreground" />}
+                  </div>
+                  <p className="truncate text-[11px] text-muted-foreground">{message.from}</p>
+                  <p className="truncate text-[10px] text-muted-foreground/70">
+                    {new Date(message.receivedAt).toLocaleString()}
+                    {message.preview && ` — ${message.preview}`}
+                  </p>
+                </button>
+              ))}
+            </div>
+          </Card>
+
+          {/* Message viewer */}
+          <Card className="flex flex-col overflow-hidden">
+            {selected ? (
+              <>
+                <div className="px-3 py-2 border-b space-y-0.5">
+                  <div className="flex items-center justify-between gap-2">
+                    <span className="text-sm font-medium truncate">{selected.subject}</span>
+                    <Button
+                      size="sm"
+                      variant="ghost"
+                      className="h-7 text-xs text-destructive"
+                      onClick={() => deleteMessage(selected.id)}
+                    >
+                      <Trash2 className="mr-1 h-3 w-3" /> Delete
+                    </Button>
+                  </div>
+                  <p className="text-[11px] text-muted-foreground">From: {selected.from}</p>
+                  <p className="text-[11px] text-muted-foreground">To: {selected.to.join(", ") || "—"}</p>
+                  {selected.cc.length > 0 && (
+                    <p className="text-[11px] text-muted-foreground">Cc: {selected.cc.join(", ")}</p>
+                  )}
+                  <p className="text-[11px] text-muted-foreground">
+                    Envelope: {selected.envelopeFrom || "—"} → {selected.envelopeTo.join(", ")} ·{" "}
+                    {formatBytes(selected.size)}
+                  </p>
+                  {selected.attachments.length > 0 && (
+                    <div className="flex flex-wrap gap-1 pt-1">
+                      {selected.attachments.map((attachment) => (
+                        <Badge
+                          key={attachment.index}
+                          variant="secondary"
+                          className="cursor-pointer text-[10px]"
+                          title={`${attachment.contentType} — copy path`}
+                          onClick={() => copyAttachmentPath(attachment.index)}
+                        >
+                          <Paperclip className="mr-1 h-3 w-3" />
+                          {attachment.filename} ({formatBytes(attachment.size)})
+                          <Copy className="ml-1 h-3 w-3" />
+                        </Badge>
+                      ))}
+                    </div>
+                  )}
+                </div>
+                <Tabs
+                  value={view}
+                  onValueChange={(value) => {
+                    setView(value)
+                    if (value === "raw" && raw === null) fetchRaw(selected.id)
+                  }}
+                  className="flex flex-1 flex-col overflow-hidden px-3 py-2"
+                >
+                  <TabsList>
+                    <TabsTrigger value="html" disabled={!selected.html}>HTML</TabsTrigger>
+                    <TabsTrigger value="text" disabled={!selected.text}>Text</TabsTrigger>
+                    <TabsTrigger value="headers">Headers</TabsTrigger>
+                    <TabsTrigger value="raw">Raw</TabsTrigger>
+                  </TabsList>
+                  <TabsContent value="html" className="flex-1 overflow-hidden">
+                    {/* Sandboxed without scripts so the message cannot reach the app */}
+                    <iframe
+                      title="HTML body"
+                      sandbox=""
+                      srcDoc={selected.html ?? ""}
+                      className="h-full w-full rounded border bg-white"
+                    />
+                  </TabsContent>
+                  <TabsContent value="text" className="flex-1 overflow-hidden">
+                    <ScrollArea className="h-full">
+                      <pre className="whitespace-pre-wrap text-xs font-mono">{selected.text}</pre>
+                    </ScrollArea>
+                  </TabsContent>
+                  <TabsContent value="headers" className="flex-1 overflow-hidden">
+                    <ScrollArea className="h-full">
+                      <div className="space-y-0.5 text-[11px] font-mono">
+                        {selected.headers.map((header, i) => (
+                          <div key={i} className="break-all">
+                            <span className="text-muted-foreground">{header.name}:</span> {header.value}
+                          </div>
+                        ))}
+                      </div>
+                    </ScrollArea>
+                  </TabsContent>
+                  <TabsContent value="raw" className="flex-1 overflow-hidden">
+                    <ScrollArea className="h-full">
+                      <pre className="whitespace-pre-wrap break-all text-[11px] font-mono text-muted-foreground">
+                        {raw ?? "Loading..."}
+                      </pre>
+                    </ScrollArea>
+                  </TabsContent>
+                </Tabs>
+              </>
+            ) : (
+              <div className="flex items-center justify-center h-full text-sm text-muted-foreground">
+                Select a message to view
+              </div>
+            )}
+          </Card>
+        </div>
+      )}
+    </div>
+  )
+}
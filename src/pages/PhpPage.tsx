@@ -1,5 +1,5 @@
 import { useEffect, useState, useMemo, useRef, useCallback } from "react"
-import { Play, Square, RotateCw, Trash2, Search, Pencil, Check, X, Plus } from "lucide-react"
+import { Play, Square, RotateCw, Trash2, Search, Pencil, Check, X, Plus, RefreshCw } from "lucide-react"
 import { Card } from "@/components/ui/card"
 import { Button } from "@/components/ui/button"
 import { Badge } from "@/components/ui/badge"
@@ -13,6 +13,10 @@ import { PageHeader } from "@/components/shared/PageHeader"
 import { DownloadButton } from "@/components/shared/DownloadButton"
 import { StatusIndicator } from "@/components/layout/StatusIndicator"
 import { ConfirmDialog } from "@/components/shared/ConfirmDialog"
+import { FpmSettingsCard } from "@/components/shared/FpmSettingsCard"
+import { FpmMonitorCard } from "@/components/shared/FpmMonitorCard"
+import { IniHistoryCard } from "@/components/shared/IniHistoryCard"
+import { XdebugCard } from "@/components/shared/XdebugCard"
 import { usePhpStore } from "@/stores/phpStore"
 import { useDownloadProgress } from "@/hooks/useDownloadProgress"
 
@@ -38,15 +42,41 @@ export function PhpPage() {
     iniDirectives,
     installing,
     fetchVersions,
+    refreshCatalogue,
     installVersion,
+    upgradeVersion,
     removeVersion,
     startFpm,
     stopFpm,
     restartFpm,
     fetchExtensions,
     toggleExtension,
+    installableExtensions,
+    fetchInstallableExtensions,
+    installExtension,
+    xdebug,
+    xdebugProfiles,
+    fetchXdebug,
+    saveXdebug,
+    fetchXdebugProfiles,
+    deleteXdebugProfile,
+    clearXdebugProfiles,
     fetchIni,
     setIniDirective,
+    removeIniDirective,
+    uncommentIniDirective,
+    fpmSettings,
+    fetchFpmSettings,
+    saveFpmSettings,
+    iniPresets,
+    iniSnapshots,
+    fetchIniPresets,
+    saveIniPreset,
+    deleteIniPreset,
+    applyIniPreset,
+    fetchIniSnapshots,
+    restoreIniSnapshot,
+    deleteIniSnapshot,
   } = usePhpStore()
 
   const progress = useDownloadProgress()
@@ -74,16 +104,22 @@ export function PhpPage() {
 
   useEffect(() => {
     fetchVersions()
-  }, [fetchVersions])
+    fetchIniPresets()
+    fetchXdebugProfiles()
+  }, [fetchVersions, fetchIniPresets, fetchXdebugProfiles])
 
   const installedVersions = versions.filter((v) => v.installed)
 
   useEffect(() => {
     if (installedVersions.length > 0 && installedVersions.find((v) => v.version === selectedVersion)) {
       fetchExtensions(selectedVersion)
+      fetchInstallableExtensions(selectedVersion)
+      fetchXdebug(selectedVersion)
       fetchIni(selectedVersion)
+      fetchFpmSettings(selectedVersion)
+      fetchIniSnapshots(selectedVersion)
     }
-  }, [selectedVersion, versions, fetchExtensions, fetchIni])
+  }, [selectedVersion, versions, fetchExtensions, fetchInstallableExtensions, fetchXdebug, fetchIni, fetchFpmSettings, fetchIniSnapshots])
 
   const filteredDirectives = useMemo(() => {
     if (!searchQuery.trim()) return iniDirectives
@@ -105,6 +141,11 @@ export function PhpPage() {
         </TabsList>
 
         <TabsContent value="versions" className="mt-4">
+          <div className="flex justify-end mb-3">
+            <Button size="sm" variant="outline" onClick={() => refreshCatalogue()}>
+              <RefreshCw className="mr-1 h-3 w-3" /> Check for new releases
+            </Button>
+          </div>
           <div className="grid grid-cols-2 gap-4">
             {versions.map((v) => (
               <Card key={v.version} className="p-4">
@@ -112,17 +153,23 @@ export function PhpPage() {
                   <div className="flex items-center gap-2">
                     <StatusIndicator status={v.running ? "running" : v.installed ? "stopped" : "stopped"} />
                     <span className="font-medium text-sm">PHP {v.version}</span>
+                    {v.status === "eol" && (
+                      <Badge variant="outline" className="text-[10px]">EOL</Badge>
+                    )}
                   </div>
                   <Badge variant={v.installed ? (v.running ? "default" : "secondary") : "outline"}>
                     {v.installed ? (v.running ? "Running" : "Installed") : "Not Installed"}
                   </Badge>
                 </div>
 
-                {v.installed && (
+                {v.installed ? (
                   <div className="text-xs text-muted-foreground mb-3 space-y-0.5">
-                    <p>Port: {v.port}</p>
+                    {v.fullVersion && <p>Version: {v.fullVersion}</p>}
+                    {v.socket ? <p className="truncate">Socket: {v.socket}</p> : <p>Port: {v.port}</p>}
                     {v.pid && <p>PID: {v.pid}</p>}
                   </div>
+                ) : (
+                  <p className="text-xs text-muted-foreground mb-3">Latest: {v.latest}</p>
                 )}
 
                 <div className="flex gap-2">
@@ -135,6 +182,14 @@ export function PhpPage() {
                     />
                   ) : (
                     <>
+                      {v.fullVersion && v.fullVersion !== v.latest && (
+                        <DownloadButton
+                          label={`Upgrade to ${v.latest}`}
+                          progress={progress[`php-${v.version}`]}
+                          onClick={() => upgradeVersion(v.version)}
+                          disabled={installing !== null}
+                        />
+                      )}
                       {!v.running ? (
                         <Button size="sm" variant="outline" onClick={() => startFpm(v.version)}>
                           <Play className="mr-1 h-3 w-3" /> Start
@@ -187,11 +242,59 @@ export function PhpPage() {
                   </Button>
                 ))}
               </div>
+              <Card className="p-4 mb-4">
+                <h3 className="text-sm font-medium mb-1">Install from PECL</h3>
+                <p className="text-xs text-muted-foreground mb-3">
+                  Prebuilt where available, otherwise compiled with phpize. PHP-FPM {selectedVersion} is restarted after
+                  install.
+                </p>
+                <div className="grid gap-2 sm:grid-cols-2 lg:grid-cols-3">
+                  {installableExtensions.map((ext) => (
+                    <div key={ext.name} className="flex items-center justify-between gap-2 py-1 px-2 rounded bg-muted/40">
+                      <span className="text-sm">{ext.name}</span>
+                      {ext.enabled ? (
+                        <Badge variant="outline" className="text-[10px]">enabled</Badge>
+                      ) : ext.installed ? (
+                        <Button size="sm" variant="outline" onClick={() => installExtension(selectedVersion, ext.name)}>
+                          Enable
+                        </Button>
+                      ) : (
+                        <div className="w-32 flex justify-end">
+                          <DownloadButton
+                            label="Install"
+                            progress={progress[`php-${selectedVersion}-ext-${ext.name}`]}
+                            onClick={() => installExtension(selectedVersion, ext.name)}
+                            disabled={installing !== null}
+                          />
+                        </div>
+                      )}
+                    </div>
+                  ))}
+                </div>
+              </Card>
+              <XdebugCard
+                version={selectedVersion}
+                status={xdebug}
+                profiles={xdebugProfiles}
+                onSave={(enabled, config) => saveXdebug(selectedVersion, enabled, config)}
+                onDeleteProfile={deleteXdebugProfile}
+                onClearProfiles={clearXdebugProfiles}
+              />
               <div className="space-y-1">
                 {extensions.map((ext) => (
                   <div key={ext.name} className="flex items-center justify-between py-1.5 px-2 rounded hover:bg-muted/50">
-                    <span className="text-sm">{ext.name}</span>
-                    <div className="flex items-center gap-2">
+                    <div className="min-w-0">
+                      <span className="text-sm">{ext.name}</span>
+                      {ext.iniFile && (
+                        <p className="text-[11px] text-muted-foreground font-mono truncate" title={ext.iniFile}>
+                          {ext.iniFile}
+                        </p>
+                      )}
+                    </div>
+                    <div className="flex items-center gap-2 shrink-0">
+                      {ext.zend && (
+                        <Badge variant="outline" className="text-[10px]">zend</Badge>
+                      )}
                       {ext.builtin && (
                         <Badge variant="outline" className="text-[10px]">built-in</Badge>
                       )}
@@ -226,6 +329,29 @@ export function PhpPage() {
                 ))}
               </div>
 
+              <FpmSettingsCard
+                version={selectedVersion}
+                settings={fpmSettings}
+                onSave={(settings) => saveFpmSettings(selectedVersion, settings)}
+              />
+
+              <FpmMonitorCard
+                version={selectedVersion}
+                running={installedVersions.some((v) => v.version === selectedVersion && v.running)}
+              />
+
+              <IniHistoryCard
+                version={selectedVersion}
+                presets={iniPresets}
+                snapshots={iniSnapshots}
+                directives={iniDirectives}
+                onApplyPreset={(name) => applyIniPreset(selectedVersion, name)}
+                onSavePreset={saveIniPreset}
+                onDeletePreset={deleteIniPreset}
+                onRestore={(id) => restoreIniSnapshot(selectedVersion, id)}
+                onDeleteSnapshot={(id) => deleteIniSnapshot(selectedVersion, id)}
+              />
+
               {/* Quick Settings */}
               <Card className="p-4 mb-6">
                 <h3 className="text-sm font-medium mb-4">Quick Settings</h3>
@@ -379,7 +505,7 @@ export function PhpPage() {
                             autoFocus
                             onKeyDown={(e) => {
                               if (e.key === "Enter") {
-                                setIniDirective(selectedVersion, d.key, editValue)
+                                setIniDirective(selectedVersion, d.key, editValue, d.section)
                                 setEditingKey(null)
                               }
                               if (e.key === "Escape") {
@@ -392,7 +518,7 @@ export function PhpPage() {
                             variant="ghost"
                             className="h-7 w-7"
                             onClick={() => {
-                              setIniDirective(selectedVersion, d.key, editValue)
+                              setIniDirective(selectedVersion, d.key, editValue, d.section)
                               setEditingKey(null)
                             }}
                           >
@@ -412,6 +538,14 @@ export function PhpPage() {
                           <span className="text-sm font-mono text-muted-foreground truncate max-w-48">
                             {d.value}
                           </span>
+                          {d.effectiveValue !== null && d.effectiveValue !== d.value && (
+                            <span
+                              className="text-xs font-mono text-amber-500 truncate max-w-32"
+                              title="Effective value reported by php -i"
+                            >
+                              → {d.effectiveValue}
+                            </span>
+                          )}
                           <Button
                             size="icon"
                             variant="ghost"
@@ -423,6 +557,15 @@ export function PhpPage() {
                           >
                             <Pencil className="h-3 w-3" />
                           </Button>
+                          <Button
+                            size="icon"
+                            variant="ghost"
+                            className="h-7 w-7 opacity-0 group-hover:opacity-100 transition-opacity"
+                            title="Reset to default"
+                            onClick={() => removeIniDirective(selectedVersion, d.key, d.section)}
+                          >
+                            <X className="h-3 w-3" />
+                          </Button>
                         </div>
                       )}
                     </div>
@@ -447,7 +590,7 @@ export function PhpPage() {
                       className="flex-1 h-8"
                     />
                     <Input
-                      placeholder="Value"
+                      placeholder="Value (empty: enable documented default)"
                       value={iniValue}
                       onChange={(e) => setIniValue(e.target.value)}
                       className="flex-1 h-8"
@@ -455,13 +598,16 @@ export function PhpPage() {
                     <Button
                       size="sm"
                       onClick={() => {
-                        if (iniKey && iniValue) {
+                        if (!iniKey) return
+                        if (iniValue) {
                           setIniDirective(selectedVersion, iniKey, iniValue)
-                          setIniKey("")
-                          setIniValue("")
+                        } else {
+                          uncommentIniDirective(selectedVersion, iniKey)
                         }
+                        setIniKey("")
+                        setIniValue("")
                       }}
-                      disabled={!iniKey || !iniValue}
+                      disabled={!iniKey}
                     >
                       Set
                     </Button>
@@ -1,5 +1,7 @@
+import { useEffect } from "react"
 import { PageHeader } from "@/components/shared/PageHeader"
 import { ServiceCard } from "@/components/shared/ServiceCard"
+import { ServiceExitsCard } from "@/components/shared/ServiceExitsCard"
 import { useServiceStore } from "@/stores/serviceStore"
 
 export function ServicesPage() {
@@ -9,6 +11,12 @@ export function ServicesPage() {
   const startService = useServiceStore((s) => s.startService)
   const stopService = useServiceStore((s) => s.stopService)
   const restartService = useServiceStore((s) => s.restartService)
+  const exits = useServiceStore((s) => s.exits)
+  const fetchExits = useServiceStore((s) => s.fetchExits)
+
+  useEffect(() => {
+    fetchExits()
+  }, [fetchExits])
 
   return (
     <div>
@@ -30,6 +38,8 @@ export function ServicesPage() {
           />
         ))}
       </div>
+
+      <ServiceExitsCard exits={exits} names={Object.fromEntries(services.map((s) => [s.id, s.name]))} />
     </div>
   )
 }
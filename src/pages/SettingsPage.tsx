@@ -13,11 +13,13 @@ import {
 } from "@/components/ui/select"
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs"
 import { PageHeader } from "@/components/shared/PageHeader"
+import { CliShimsCard } from "@/components/shared/CliShimsCard"
 import { Textarea } from "@/components/ui/textarea"
 import { ScrollArea } from "@/components/ui/scroll-area"
 import { RefreshCw, Loader2, CheckCircle2, ExternalLink, Search, ChevronsUpDown, Check } from "lucide-react"
 import { useSettingsStore } from "@/stores/settingsStore"
 import { useAppStore } from "@/stores/appStore"
+import { usePhpStore } from "@/stores/phpStore"
 import { aiFetchModels } from "@/lib/tauri"
 import type { AppConfig } from "@/types/config"
 import type { AiModel } from "@/types/ai"
@@ -25,6 +27,7 @@ import type { AiModel } from "@/types/ai"
 export function SettingsPage() {
   const { config, fetchSettings, saveSettings, resetSettings } = useSettingsStore()
   const systemInfo = useAppStore((s) => s.systemInfo)
+  const { versions: phpVersions, fetchVersions: fetchPhpVersions } = usePhpStore()
   const {
     updateAvailable,
     updateVersion,
@@ -45,7 +48,8 @@ export function SettingsPage() {
 
   useEffect(() => {
     fetchSettings()
-  }, [fetchSettings])
+    fetchPhpVersions()
+  }, [fetchSettings, fetchPhpVersions])
 
   useEffect(() => {
     if (config) {
@@ -53,6 +57,12 @@ export function SettingsPage() {
     }
   }, [config])
 
+  // Ports as assigned by the backend registry
+  const installedPhpPorts = phpVersions
+    .filter((v) => v.installed && !v.socket)
+    .map((v) => `PHP ${v.version} = ${v.port}`)
+    .join(", ")
+
   // Fetch models when API key is available
   const fetchModels = async (apiKey: string) => {
     if (!apiKey || apiKey.length < 10) return
@@ -140,6 +150,21 @@ export function SettingsPage() {
               <p className="text-[11px] text-muted-foreground">Domain suffix for local sites (e.g. .test)</p>
             </div>
           </Card>
+          <Card className="p-4 space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <Label>Require Verified Binaries</Label>
+                <p className="text-[11px] text-muted-foreground">
+                  Refuse PHP, Nginx, MariaDB, extension and Composer builds that have no known SHA-256 checksum
+                </p>
+              </div>
+              <Switch
+                checked={form.requireVerifiedBinaries}
+                onCheckedChange={(checked) => setForm({ ...form, requireVerifiedBinaries: checked })}
+              />
+            </div>
+          </Card>
+          <CliShimsCard />
         </TabsContent>
 
         <TabsContent value="services" className="mt-4 space-y-4">
@@ -164,14 +189,91 @@ export function SettingsPage() {
                   <SelectValue />
                 </SelectTrigger>
                 <SelectContent>
-                  <SelectItem value="8.1">PHP 8.1</SelectItem>
-                  <SelectItem value="8.2">PHP 8.2</SelectItem>
-                  <SelectItem value="8.3">PHP 8.3</SelectItem>
-                  <SelectItem value="8.4">PHP 8.4</SelectItem>
+                  {phpVersions.map((php) => (
+                    <SelectItem key={php.version} value={php.version}>
+                      PHP {php.version}
+                    </SelectItem>
+                  ))}
                 </SelectContent>
               </Select>
             </div>
           </Card>
+          <Card className="p-4 space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <Label>Restart Crashed Services</Label>
+                <p className="text-[11px] text-muted-foreground">
+                  Restart PHP-FPM and MariaDB when they exit without being stopped, waiting longer after each attempt
+                </p>
+              </div>
+              <Switch
+                checked={form.restartPolicy.enabled}
+                onCheckedChange={(checked) =>
+                  setForm({ ...form, restartPolicy: { ...form.restartPolicy, enabled: checked } })
+                }
+              />
+            </div>
+            {form.restartPolicy.enabled && (
+              <div className="grid grid-cols-2 gap-3">
+                <div className="space-y-1.5">
+                  <Label>Max Restarts</Label>
+                  <Input
+                    type="number"
+                    min={1}
+                    value={form.restartPolicy.maxRestarts}
+                    onChange={(e) =>
+                      setForm({
+                        ...form,
+                        restartPolicy: { ...form.restartPolicy, maxRestarts: parseInt(e.target.value) || 1 },
+                      })
+                    }
+                  />
+                </div>
+                <div className="space-y-1.5">
+                  <Label>First Retry After (s)</Label>
+                  <Input
+                    type="number"
+                    min={1}
+                    value={form.restartPolicy.backoffSecs}
+                    onChange={(e) =>
+                      setForm({
+                        ...form,
+                        restartPolicy: { ...form.restartPolicy, backoffSecs: parseInt(e.target.value) || 1 },
+                      })
+                    }
+                  />
+                </div>
+              </div>
+            )}
+            <div className="flex items-center justify-between">
+              <div>
+                <Label>Crash Notifications</Label>
+                <p className="text-[11px] text-muted-foreground">Show a desktop notification when a service crashes</p>
+              </div>
+              <Switch
+                checked={form.restartPolicy.notify}
+                onCheckedChange={(checked) =>
+                  setForm({ ...form, restartPolicy: { ...form.restartPolicy, notify: checked } })
+                }
+              />
+            </div>
+          </Card>
+          <Card className="p-4 space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <Label>Mail Catcher</Label>
+                <p className="text-[11px] text-muted-foreground">
+                  Catch mail sent by PHP and site templates on a local SMTP server instead of delivering it
+                </p>
+              </div>
+              <Switch
+                checked={form.mailCatcher.enabled}
+                onCheckedChange={(checked) =>
+                  setForm({ ...form, mailCatcher: { ...form.mailCatcher, enabled: checked } })
+                }
+              />
+            </div>
+          </Card>
         </TabsContent>
 
         <TabsContent value="ports" className="mt-4 space-y-4">
@@ -200,9 +302,37 @@ export function SettingsPage() {
                 onChange={(e) => setForm({ ...form, phpFpmBasePort: parseInt(e.target.value) || 9081 })}
               />
               <p className="text-[11px] text-muted-foreground">
-                PHP 8.1 = {form.phpFpmBasePort}, 8.2 = {form.phpFpmBasePort + 1}, 8.3 = {form.phpFpmBasePort + 2}, 8.4 = {form.phpFpmBasePort + 3}
+                Each PHP version gets its own port from here on. Ports already in use are skipped; changes apply when PHP-FPM restarts.
+                {installedPhpPorts && ` Current: ${installedPhpPorts}.`}
+              </p>
+            </div>
+            <div className="space-y-1.5">
+              <Label>Mail Catcher SMTP Port</Label>
+              <Input
+                type="number"
+                value={form.mailCatcher.port}
+                onChange={(e) =>
+                  setForm({ ...form, mailCatcher: { ...form.mailCatcher, port: parseInt(e.target.value) || 1025 } })
+                }
+              />
+              <p className="text-[11px] text-muted-foreground">
+                Running PHP-FPM versions pick up a new port when they restart.
               </p>
             </div>
+            {systemInfo?.os !== "windows" && (
+              <div className="flex items-center justify-between">
+                <div>
+                  <Label>PHP-FPM Unix Sockets</Label>
+                  <p className="text-[11px] text-muted-foreground">
+                    Connect Nginx to PHP-FPM over sockets instead of TCP ports. Applies when PHP-FPM and Nginx restart.
+                  </p>
+                </div>
+                <Switch
+                  checked={form.phpFpmUnixSockets}
+                  onCheckedChange={(checked) => setForm({ ...form, phpFpmUnixSockets: checked })}
+                />
+              </div>
+            )}
           </Card>
         </TabsContent>
 
@@ -1,5 +1,5 @@
 import { useEffect } from "react"
-import { Globe, Trash2, ExternalLink, RotateCcw, Loader2 } from "lucide-react"
+import { Globe, Trash2, ExternalLink, RotateCcw, Loader2, Bug } from "lucide-react"
 import { open } from "@tauri-apps/plugin-shell"
 import { toast } from "sonner"
 import { Card } from "@/components/ui/card"
@@ -8,6 +8,9 @@ import { Badge } from "@/components/ui/badge"
 import { PageHeader } from "@/components/shared/PageHeader"
 import { EmptyState } from "@/components/shared/EmptyState"
 import { SiteCreateDialog } from "@/components/shared/SiteCreateDialog"
+import { SitePoolDialog } from "@/components/shared/SitePoolDialog"
+import { SiteIniDialog } from "@/components/shared/SiteIniDialog"
+import { SiteComposerDialog } from "@/components/shared/SiteComposerDialog"
 import { StatusIndicator } from "@/components/layout/StatusIndicator"
 import { ConfirmDialog } from "@/components/shared/ConfirmDialog"
 import { useSiteStore } from "@/stores/siteStore"
@@ -15,7 +18,7 @@ import { usePhpStore } from "@/stores/phpStore"
 import { useServiceStore } from "@/stores/serviceStore"
 
 export function SitesPage() {
-  const { sites, nginxInfo, fetchSites, fetchNginxInfo, createSite, deleteSite, setupTemplate } = useSiteStore()
+  const { sites, nginxInfo, fetchSites, fetchNginxInfo, createSite, updateSite, deleteSite, setupTemplate } = useSiteStore()
   const { versions, fetchVersions } = usePhpStore()
   const { services, fetchServices } = useServiceStore()
 
@@ -114,6 +117,16 @@ export function SitesPage() {
                           SSL
                         </Badge>
                       )}
+                      {site.pool?.enabled && (
+                        <Badge variant="secondary" className="text-[10px]">
+                          Own Pool
+                        </Badge>
+                      )}
+                      {site.xdebug && (
+                        <Badge variant="secondary" className="text-[10px]">
+                          Xdebug
+                        </Badge>
+                      )}
                       {site.templateStatus === "installing" && (
                         <Badge variant="secondary" className="text-[10px] gap-1">
                           <Loader2 className="h-2.5 w-2.5 animate-spin" />
@@ -151,6 +164,18 @@ export function SitesPage() {
                       <RotateCcw className="h-3.5 w-3.5" />
                     </Button>
                   )}
+                  <SitePoolDialog site={site} onSubmit={(pool) => updateSite(site.id, { pool })} />
+                  <SiteIniDialog site={site} onSubmit={(iniOverrides) => updateSite(site.id, { iniOverrides })} />
+                  <SiteComposerDialog site={site} />
+                  <Button
+                    size="sm"
+                    variant="ghost"
+                    className={site.xdebug ? "text-amber-500" : undefined}
+                    title={site.xdebug ? "Debugging on: every request starts Xdebug" : "Debug every request with Xdebug"}
+                    onClick={() => updateSite(site.id, { xdebug: !site.xdebug })}
+                  >
+                    <Bug className="h-3.5 w-3.5" />
+                  </Button>
                   <Button
                     size="sm"
                     variant="ghost"
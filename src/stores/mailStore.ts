@@ -0,0 +1,95 @@
+import { create } from "zustand"
+import { toast } from "sonner"
+import type { MailCatcherInfo, MailMessage, MailSummary } from "@/types/mail"
+import * as tauri from "@/lib/tauri"
+
+interface MailStore {
+  info: MailCatcherInfo | null
+  messages: MailSummary[]
+  selected: MailMessage | null
+  raw: string | null
+  error: string | null
+  fetchInfo: () => Promise<void>
+  fetchMessages: () => Promise<void>
+  selectMessage: (id: string) => Promise<void>
+  fetchRaw: (id: string) => Promise<void>
+  deleteMessage: (id: string) => Promise<void>
+  clearMessages: () => Promise<void>
+  addReceived: (message: MailSummary) => void
+}
+
+export const useMailStore = create<MailStore>((set, get) => ({
+  info: null,
+  messages: [],
+  selected: null,
+  raw: null,
+  error: null,
+
+  fetchInfo: async () => {
+    try {
+      const info = await tauri.mailGetInfo()
+      set({ info })
+    } catch (err) {
+      set({ error: String(err) })
+    }
+  },
+
+  fetchMessages: async () => {
+    try {
+      const messages = await tauri.mailList()
+      set({ messages })
+    } catch (err) {
+      set({ error: String(err) })
+    }
+  },
+
+  selectMessage: async (id: string) => {
+    try {
+      const selected = await tauri.mailGet(id)
+      set({ selected, raw: null })
+    } catch (err) {
+      toast.error("Failed to open message", { description: String(err) })
+    }
+  },
+
+  fetchRaw: async (id: string) => {
+    try {
+      const raw = await tauri.mailGetRaw(id)
+      set({ raw })
+    } catch (err) {
+      toast.error("Failed to load message source", { description: String(err) })
+    }
+  },
+
+  deleteMessage: async (id: string) => {
+    try {
+      await tauri.mailDelete(id)
+      set((state) => ({
+        messages: state.messages.filter((m) => m.id !== id),
+        selected: state.selected?.id === id ? null : state.selected,
+        raw: state.selected?.id === id ? null : state.raw,
+      }))
+      get().fetchInfo()
+    } catch (err) {
+      toast.error("Failed to delete message", { description: String(err) })
+    }
+  },
+
+  clearMessages: async () => {
+    try {
+      const count = await tauri.mailClear()
+      set({ messages: [], selected: null, raw: null })
+      toast.success(`Deleted ${count} message${count === 1 ? "" : "s"}`)
+      get().fetchInfo()
+    } catch (err) {
+      toast.error("Failed to delete messages", { description: String(err) })
+    }
+  },
+
+  addReceived: (message) => {
+    set((state) => ({
+      messages: [message, ...state.messages.filter((m) => m.id !== message.id)],
+      info: state.info ? { ...state.info, messageCount: state.info.messageCount + 1 } : state.info,
+    }))
+  },
+}))
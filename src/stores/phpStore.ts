@@ -1,31 +1,63 @@
 import { create } from "zustand"
 import { toast } from "sonner"
-import type { PhpVersion, PhpIniDirective, PhpExtension } from "@/types/php"
+import type { PhpVersion, PhpIniDirective, PhpExtension, InstallableExtension, XdebugConfig, XdebugStatus, XdebugProfile, FpmSettings, IniPreset, IniSnapshot } from "@/types/php"
 import * as tauri from "@/lib/tauri"
 
 interface PhpStore {
   versions: PhpVersion[]
   extensions: PhpExtension[]
+  installableExtensions: InstallableExtension[]
+  xdebug: XdebugStatus | null
+  xdebugProfiles: XdebugProfile[]
   iniDirectives: PhpIniDirective[]
+  fpmSettings: FpmSettings | null
+  iniPresets: IniPreset[]
+  iniSnapshots: IniSnapshot[]
   loading: boolean
   installing: string | null
   error: string | null
   fetchVersions: () => Promise<void>
+  refreshCatalogue: () => Promise<void>
   installVersion: (version: string) => Promise<void>
+  upgradeVersion: (version: string) => Promise<void>
   removeVersion: (version: string) => Promise<void>
   startFpm: (version: string) => Promise<void>
   stopFpm: (version: string) => Promise<void>
   restartFpm: (version: string) => Promise<void>
   fetchExtensions: (version: string) => Promise<void>
   toggleExtension: (version: string, extension: string, enable: boolean) => Promise<void>
+  fetchInstallableExtensions: (version: string) => Promise<void>
+  installExtension: (version: string, name: string) => Promise<void>
+  fetchXdebug: (version: string) => Promise<void>
+  saveXdebug: (version: string, enabled: boolean, config: XdebugConfig) => Promise<void>
+  fetchXdebugProfiles: () => Promise<void>
+  deleteXdebugProfile: (name: string) => Promise<void>
+  clearXdebugProfiles: () => Promise<void>
   fetchIni: (version: string) => Promise<void>
-  setIniDirective: (version: string, key: string, value: string) => Promise<void>
+  setIniDirective: (version: string, key: string, value: string, section?: string) => Promise<void>
+  removeIniDirective: (version: string, key: string, section?: string) => Promise<void>
+  uncommentIniDirective: (version: string, key: string) => Promise<void>
+  fetchFpmSettings: (version: string) => Promise<void>
+  saveFpmSettings: (version: string, settings: FpmSettings) => Promise<void>
+  fetchIniPresets: () => Promise<void>
+  saveIniPreset: (preset: IniPreset) => Promise<void>
+  deleteIniPreset: (name: string) => Promise<void>
+  applyIniPreset: (version: string, name: string) => Promise<void>
+  fetchIniSnapshots: (version: string) => Promise<void>
+  restoreIniSnapshot: (version: string, id: string) => Promise<void>
+  deleteIniSnapshot: (version: string, id: string) => Promise<void>
 }
 
 export const usePhpStore = create<PhpStore>((set, get) => ({
   versions: [],
   extensions: [],
+  installableExtensions: [],
+  xdebug: null,
+  xdebugProfiles: [],
   iniDirectives: [],
+  fpmSettings: null,
+  iniPresets: [],
+  iniSnapshots: [],
   loading: false,
   installing: null,
   error: null,
@@ -40,6 +72,17 @@ export const usePhpStore = create<PhpStore>((set, get) => ({
     }
   },
 
+  refreshCatalogue: async () => {
+    set({ loading: true, error: null })
+    try {
+      const versions = await tauri.phpRefreshCatalogue()
+      set({ versions, loading: false })
+    } catch (err) {
+      set({ error: String(err), loading: false })
+      toast.error(`Failed to refresh PHP releases: ${err}`)
+    }
+  },
+
   installVersion: async (version: string) => {
     set({ installing: version, error: null })
     try {
@@ -54,6 +97,20 @@ export const usePhpStore = create<PhpStore>((set, get) => ({
     }
   },
 
+  upgradeVersion: async (version: string) => {
+    set({ installing: version, error: null })
+    try {
+      const result = await tauri.upgradeComponent(`php-${version}`)
+      await get().fetchVersions()
+      set({ installing: null })
+      toast.success(`PHP ${version} upgraded to ${result.installedVersion}`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg, installing: null })
+      toast.error(`Failed to upgrade PHP ${version}`, { description: msg })
+    }
+  },
+
   removeVersion: async (version: string) => {
     try {
       await tauri.phpRemoveVersion(version)
@@ -115,11 +172,95 @@ export const usePhpStore = create<PhpStore>((set, get) => ({
     try {
       await tauri.phpToggleExtension(version, extension, enable)
       await get().fetchExtensions(version)
+      get().fetchIniSnapshots(version)
+      toast.success(`${extension} ${enable ? "enabled" : "disabled"}`, {
+        description: "Restart PHP-FPM to apply",
+      })
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error(`Failed to ${enable ? "enable" : "disable"} ${extension}`, { description: msg })
+    }
+  },
+
+  fetchInstallableExtensions: async (version: string) => {
+    try {
+      const installableExtensions = await tauri.phpListInstallableExtensions(version)
+      set({ installableExtensions })
     } catch (err) {
       set({ error: String(err) })
     }
   },
 
+  installExtension: async (version: string, name: string) => {
+    set({ installing: `${version}-${name}`, error: null })
+    try {
+      const extensions = await tauri.phpInstallExtension(version, name)
+      set({ extensions, installing: null })
+      await get().fetchInstallableExtensions(version)
+      await get().fetchXdebug(version)
+      await get().fetchVersions()
+      get().fetchIniSnapshots(version)
+      toast.success(`${name} installed for PHP ${version}`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg, installing: null })
+      toast.error(`Failed to install ${name}`, { description: msg })
+    }
+  },
+
+  fetchXdebug: async (version: string) => {
+    try {
+      const xdebug = await tauri.phpGetXdebug(version)
+      set({ xdebug })
+    } catch (err) {
+      set({ xdebug: null, error: String(err) })
+    }
+  },
+
+  saveXdebug: async (version: string, enabled: boolean, config: XdebugConfig) => {
+    try {
+      const xdebug = await tauri.phpSetXdebug(version, enabled, config)
+      set({ xdebug })
+      await get().fetchExtensions(version)
+      await get().fetchVersions()
+      get().fetchIniSnapshots(version)
+      toast.success(`Xdebug ${enabled ? "enabled" : "disabled"} for PHP ${version}`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error("Failed to save Xdebug settings", { description: msg })
+    }
+  },
+
+  fetchXdebugProfiles: async () => {
+    try {
+      const xdebugProfiles = await tauri.phpListXdebugProfiles()
+      set({ xdebugProfiles })
+    } catch (err) {
+      set({ error: String(err) })
+    }
+  },
+
+  deleteXdebugProfile: async (name: string) => {
+    try {
+      await tauri.phpDeleteXdebugProfile(name)
+      await get().fetchXdebugProfiles()
+    } catch (err) {
+      toast.error("Failed to delete profile", { description: String(err) })
+    }
+  },
+
+  clearXdebugProfiles: async () => {
+    try {
+      const count = await tauri.phpClearXdebugProfiles()
+      await get().fetchXdebugProfiles()
+      toast.success(`Deleted ${count} file${count === 1 ? "" : "s"}`)
+    } catch (err) {
+      toast.error("Failed to clear profiles", { description: String(err) })
+    }
+  },
+
   fetchIni: async (version: string) => {
     try {
       const iniDirectives = await tauri.phpGetIni(version)
@@ -129,12 +270,143 @@ export const usePhpStore = create<PhpStore>((set, get) => ({
     }
   },
 
-  setIniDirective: async (version: string, key: string, value: string) => {
+  setIniDirective: async (version: string, key: string, value: string, section?: string) => {
     try {
-      await tauri.phpSetIniDirective(version, key, value)
+      await tauri.phpSetIniDirective(version, key, value, section)
       await get().fetchIni(version)
+      get().fetchIniSnapshots(version)
     } catch (err) {
       set({ error: String(err) })
     }
   },
+
+  removeIniDirective: async (version: string, key: string, section?: string) => {
+    try {
+      await tauri.phpRemoveIniDirective(version, key, section)
+      await get().fetchIni(version)
+      get().fetchIniSnapshots(version)
+      toast.success(`${key} reset to default`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error(`Failed to remove ${key}`, { description: msg })
+    }
+  },
+
+  uncommentIniDirective: async (version: string, key: string) => {
+    try {
+      const value = await tauri.phpUncommentIniDirective(version, key)
+      await get().fetchIni(version)
+      get().fetchIniSnapshots(version)
+      toast.success(`${key} enabled`, { description: value || undefined })
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error(`Failed to enable ${key}`, { description: msg })
+    }
+  },
+
+  fetchFpmSettings: async (version: string) => {
+    try {
+      const fpmSettings = await tauri.phpGetFpmSettings(version)
+      set({ fpmSettings })
+    } catch (err) {
+      set({ error: String(err) })
+    }
+  },
+
+  saveFpmSettings: async (version: string, settings: FpmSettings) => {
+    try {
+      const fpmSettings = await tauri.phpSetFpmSettings(version, settings)
+      set({ fpmSettings })
+      toast.success(`PHP-FPM ${version} settings saved`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error(`Failed to save PHP-FPM ${version} settings`, { description: msg })
+    }
+  },
+
+  fetchIniPresets: async () => {
+    try {
+      const iniPresets = await tauri.phpListIniPresets()
+      set({ iniPresets })
+    } catch (err) {
+      set({ error: String(err) })
+    }
+  },
+
+  saveIniPreset: async (preset: IniPreset) => {
+    try {
+      await tauri.phpSaveIniPreset(preset)
+      await get().fetchIniPresets()
+      toast.success(`Preset "${preset.name}" saved`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error("Failed to save preset", { description: msg })
+    }
+  },
+
+  deleteIniPreset: async (name: string) => {
+    try {
+      await tauri.phpDeleteIniPreset(name)
+      await get().fetchIniPresets()
+      toast.success(`Preset "${name}" deleted`)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error("Failed to delete preset", { description: msg })
+    }
+  },
+
+  applyIniPreset: async (version: string, name: string) => {
+    try {
+      await tauri.phpApplyIniPreset(version, name)
+      await get().fetchIni(version)
+      await get().fetchIniSnapshots(version)
+      toast.success(`Applied "${name}" to PHP ${version}`, {
+        description: "Restart PHP-FPM to use the new values",
+      })
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error(`Failed to apply preset "${name}"`, { description: msg })
+    }
+  },
+
+  fetchIniSnapshots: async (version: string) => {
+    try {
+      const iniSnapshots = await tauri.phpListIniSnapshots(version)
+      set({ iniSnapshots })
+    } catch (err) {
+      set({ error: String(err) })
+    }
+  },
+
+  restoreIniSnapshot: async (version: string, id: string) => {
+    try {
+      await tauri.phpRestoreIniSnapshot(version, id)
+      await get().fetchIni(version)
+      await get().fetchIniSnapshots(version)
+      toast.success(`php.ini of PHP ${version} restored`, {
+        description: "Restart PHP-FPM to use the restored values",
+      })
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error("Failed to restore snapshot", { description: msg })
+    }
+  },
+
+  deleteIniSnapshot: async (version: string, id: string) => {
+    try {
+      await tauri.phpDeleteIniSnapshot(version, id)
+      await get().fetchIniSnapshots(version)
+    } catch (err) {
+      const msg = String(err)
+      set({ error: msg })
+      toast.error("Failed to delete snapshot", { description: msg })
+    }
+  },
 }))
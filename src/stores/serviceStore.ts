@@ -1,13 +1,15 @@
 import { create } from "zustand"
 import { toast } from "sonner"
-import type { ServiceInfo } from "@/types/service"
+import type { ServiceExit, ServiceInfo } from "@/types/service"
 import * as tauri from "@/lib/tauri"
 
 interface ServiceStore {
   services: ServiceInfo[]
   loading: boolean
   error: string | null
+  exits: ServiceExit[]
   fetchServices: () => Promise<void>
+  fetchExits: () => Promise<void>
   installService: (id: string) => Promise<void>
   initializeService: (id: string) => Promise<void>
   startService: (id: string) => Promise<void>
@@ -19,6 +21,7 @@ export const useServiceStore = create<ServiceStore>((set, get) => ({
   services: [],
   loading: false,
   error: null,
+  exits: [],
 
   fetchServices: async () => {
     set({ loading: true, error: null })
@@ -30,6 +33,13 @@ export const useServiceStore = create<ServiceStore>((set, get) => ({
     }
   },
 
+  fetchExits: async () => {
+    try {
+      const exits = await tauri.serviceLastExits()
+      set({ exits })
+    } catch {}
+  },
+
   installService: async (id: string) => {
     try {
       if (id === "nginx") {
@@ -115,7 +115,7 @@ export const useSiteStore = create<SiteStore>((set, get) => ({
     const oldSite = get().sites.find((s) => s.id === id)
 
     try {
-      await tauri.siteUpdate(id, data.name, data.domain, data.documentRoot, data.phpVersion, data.ssl, data.active)
+      await tauri.siteUpdate(id, data.name, data.domain, data.documentRoot, data.phpVersion, data.ssl, data.active, data.pool, data.iniOverrides, data.xdebug)
     } catch (err) {
       const msg = String(err)
       set({ error: msg })
@@ -0,0 +1,15 @@
+export interface ComposerInfo {
+  installed: boolean
+  version: string | null
+  path: string
+}
+
+/** Payload of the `composer-output` event */
+export interface ComposerOutput {
+  siteId: string
+  /** "stdout" or "stderr"; Composer reports progress on stderr */
+  stream: string
+  line: string
+}
+
+export type ComposerAction = "install" | "update" | "require"
@@ -1,3 +1,5 @@
+import type { FpmSettings } from "./php"
+
 export interface AppConfig {
   autoStartServices: boolean
   autoStartList: string[]
@@ -8,9 +10,37 @@ export interface AppConfig {
   nginxSslPort: number
   mariadbPort: number
   phpFpmBasePort: number
+  phpFpmUnixSockets: boolean
   openrouterApiKey: string
   aiModel: string
   aiSystemPrompt: string
+  downloadMirrors: string[]
+  downloadCacheEnabled: boolean
+  requireVerifiedDownloads: boolean
+  /** Refuse binaries without a known SHA-256 (off by default) */
+  requireVerifiedBinaries: boolean
+  maxConcurrentDownloads: number
+  phpFpm: Record<string, FpmSettings>
+  restartPolicy: RestartPolicy
+  mailCatcher: MailCatcherSettings
+}
+
+/** Built-in SMTP server that keeps mail instead of delivering it */
+export interface MailCatcherSettings {
+  enabled: boolean
+  port: number
+}
+
+/** Automatic restart of crashed services, with exponential backoff */
+export interface RestartPolicy {
+  enabled: boolean
+  /** Restarts in a row before giving up */
+  maxRestarts: number
+  /** Delay before the first restart, doubled for each further one */
+  backoffSecs: number
+  maxBackoffSecs: number
+  /** Show a desktop notification when a service crashes */
+  notify: boolean
 }
 
 export interface SystemInfo {
@@ -19,3 +49,13 @@ export interface SystemInfo {
   dataDir: string
   appVersion: string
 }
+
+export interface ShimsInfo {
+  installed: boolean
+  binDir: string
+  /** Line to add to the shell profile (PowerShell on Windows) */
+  pathLine: string
+  /** The bin dir is already on the PATH the app was started with */
+  onPath: boolean
+  shims: string[]
+}
@@ -0,0 +1,19 @@
+export interface CachedArtifact {
+  url: string
+  sha256: string
+  size: number
+  cachedAt: string
+  verified: boolean
+}
+
+export type DownloadJobStatus = "queued" | "running" | "completed" | "failed" | "cancelled"
+
+export interface DownloadJob {
+  id: string
+  label: string
+  status: DownloadJobStatus
+  createdAt: string
+  startedAt: string | null
+  finishedAt: string | null
+  error: string | null
+}
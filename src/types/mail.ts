@@ -0,0 +1,50 @@
+export interface MailCatcherInfo {
+  running: boolean
+  port: number
+  messageCount: number
+}
+
+/** List entry, also the payload of the `mail-received` event */
+export interface MailSummary {
+  id: string
+  from: string
+  to: string[]
+  subject: string
+  receivedAt: string
+  size: number
+  attachments: number
+  preview: string
+}
+
+export interface MailHeader {
+  name: string
+  value: string
+}
+
+export interface MailAttachment {
+  index: number
+  filename: string
+  contentType: string
+  size: number
+  /** File name under the message's `attachments/` folder */
+  file: string
+}
+
+export interface MailMessage {
+  id: string
+  from: string
+  to: string[]
+  cc: string[]
+  subject: string
+  /** `Date` header as sent */
+  date: string | null
+  receivedAt: string
+  size: number
+  /** SMTP envelope, which includes Bcc recipients */
+  envelopeFrom: string
+  envelopeTo: string[]
+  text: string | null
+  html: string | null
+  headers: MailHeader[]
+  attachments: MailAttachment[]
+}
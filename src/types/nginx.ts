@@ -20,4 +20,27 @@ export interface Site {
   template?: string
   templateStatus?: string
   dnsWarning?: string
+  pool?: SitePool
+  iniOverrides?: SiteIniOverrides
+  /** Start an Xdebug session on every request */
+  xdebug?: boolean
+}
+
+/** Dedicated PHP-FPM pool of a site (macOS/Linux only) */
+export interface SitePool {
+  enabled: boolean
+  maxChildren: number
+  maxRequests: number
+  /** Seconds before a request is written to the slowlog (0 = off) */
+  slowlogTimeout: number
+  env: Record<string, string>
+  adminValues: Record<string, string>
+}
+
+/** php.ini values passed to PHP-FPM with each request of a site */
+export interface SiteIniOverrides {
+  /** PHP_VALUE: scripts can still change these with ini_set() */
+  values: Record<string, string>
+  /** PHP_ADMIN_VALUE: locked against ini_set() */
+  adminValues: Record<string, string>
 }
@@ -3,20 +3,140 @@ export interface PhpVersion {
   installed: boolean
   running: boolean
   port: number
+  socket: string | null
   pid: number | null
   path: string | null
+  fullVersion: string | null
+  latest: string
+  status: "active" | "security" | "eol"
+}
+
+export type FpmPmMode = "static" | "dynamic" | "ondemand"
+
+export interface FpmSettings {
+  pm: FpmPmMode
+  maxChildren: number
+  startServers: number
+  minSpareServers: number
+  maxSpareServers: number
+  processIdleTimeout: number
+  requestTerminateTimeout: number
+  /** Log a backtrace of requests slower than this (0 = off) */
+  slowlogTimeout: number
+  maxRequests: number
+  logLevel: "alert" | "error" | "warning" | "notice" | "debug"
 }
 
 export interface PhpIniDirective {
   key: string
   value: string
   section: string
+  effectiveValue: string | null
+}
+
+export interface IniPreset {
+  name: string
+  description: string
+  builtin: boolean
+  directives: Record<string, string>
+}
+
+export interface IniSnapshot {
+  id: string
+  version: string
+  createdAt: string
+  reason: string
+  size: number
+}
+
+export interface IniChange {
+  section: string
+  key: string
+  before: string | null
+  after: string | null
 }
 
 export interface PhpExtension {
   name: string
   enabled: boolean
   builtin: boolean
+  /** Loaded with zend_extension= */
+  zend: boolean
+  /** Ini file holding the extension= line */
+  iniFile: string | null
+  /** Module file in extension_dir */
+  file: string | null
+}
+
+export interface InstallableExtension {
+  name: string
+  /** Module file present in extension_dir */
+  installed: boolean
+  enabled: boolean
+}
+
+export interface FpmProcess {
+  pid: number
+  state: string
+  requests: number
+  /** Microseconds */
+  requestDuration: number
+  requestMethod: string
+  requestUri: string
+  script: string
+  lastRequestCpu: number
+  lastRequestMemory: number
+}
+
+export interface FpmStatus {
+  pool: string
+  processManager: string
+  startSince: number
+  acceptedConn: number
+  listenQueue: number
+  maxListenQueue: number
+  idleProcesses: number
+  activeProcesses: number
+  totalProcesses: number
+  maxActiveProcesses: number
+  maxChildrenReached: number
+  slowRequests: number
+  processes: FpmProcess[]
+  maxChildren: number
+  /** Every worker busy and pm.max_children reached */
+  saturated: boolean
+}
+
+export interface SlowlogEntry {
+  time: string
+  pool: string
+  pid: number
+  script: string
+  trace: string[]
+}
+
+export interface XdebugConfig {
+  mode: string[]
+  clientHost: string
+  clientPort: number
+  /** "trigger" or "yes" (also "no", "default") */
+  startWithRequest: string
+}
+
+export interface XdebugStatus {
+  version: string
+  installed: boolean
+  enabled: boolean
+  config: XdebugConfig
+  outputDir: string
+}
+
+export interface XdebugProfile {
+  name: string
+  /** "profile" or "trace" */
+  kind: string
+  size: number
+  modified: string
 }
 
 export interface DownloadProgress {
@@ -26,4 +146,5 @@ export interface DownloadProgress {
   percent: number
   status: string
   message: string | null
+  verification?: "verified" | "unverified" | "mismatch"
 }
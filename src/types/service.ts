@@ -10,3 +10,25 @@ export interface ServiceInfo {
   installed: boolean
   initialized: boolean
 }
+
+/** How a supervised process ended */
+export interface ServiceExit {
+  serviceId: string
+  code: number | null
+  /** Terminating signal (Unix only) */
+  signal: number | null
+  exitedAt: string
+  /** Last lines of the service's log at the time of the exit */
+  logTail: string[]
+  /** Restarts already attempted since the service was last healthy */
+  restarts: number
+}
+
+/** Payload of the `service-status` event */
+export interface ServiceStatusEvent {
+  service: ServiceInfo
+  message: string
+  exit: ServiceExit | null
+  /** Seconds until the next restart attempt, if one is scheduled */
+  restartIn: number | null
+}
@@ -1,2 +1,2 @@
 // Re-export from nginx types for backward compatibility
-export type { Site } from "./nginx"
+export type { Site, SitePool, SiteIniOverrides } from "./nginx"
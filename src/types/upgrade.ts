@@ -0,0 +1,9 @@
+export interface AvailableUpgrade {
+  id: string
+  name: string
+  serviceId: string
+  installedVersion: string | null
+  latestVersion: string | null
+  updateAvailable: boolean
+  running: boolean
+}